@@ -0,0 +1,34 @@
+//! Time the array gain of a 256 element array over the whole sphere
+//!
+//! cargo run --release --example gain_bench [--features simd]
+
+use std::time::Instant;
+
+use antenna_pattern_generator_lib as apg;
+
+use apg::GainIface;
+
+fn main() {
+    let frequency = 10e9;
+    let spacing = apg::SPEED_OF_LIGHT / frequency / 2.0;
+    let elements = (0..256)
+        .map(|n| Box::new(apg::OmniElementBuilder::default()
+            .position(apg::PointBuilder::default()
+                .x((n % 16) as f64 * spacing)
+                .y((n / 16) as f64 * spacing)
+                .build()
+                .unwrap())
+            .gain(1.0)
+            .build()
+            .unwrap()) as Box<dyn apg::ElementIface>)
+        .collect();
+    let mut array = apg::ElementArray::new(elements);
+    array.steer(frequency, 0.3, 0.5);
+
+    let step = 1.0 * apg::PI / 180.0;
+    let start = Instant::now();
+    let grid = array.sample_sphere(frequency, step, step);
+    let elapsed = start.elapsed();
+
+    println!("{} x {} samples in {:.3} s", grid.num_phi(), grid.num_theta(), elapsed.as_secs_f64());
+}
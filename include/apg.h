@@ -0,0 +1,32 @@
+/* C interface to antenna-pattern-generator-lib (built with the `ffi` feature) */
+#ifndef APG_H
+#define APG_H
+
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define APG_OK 0
+#define APG_NULL_POINTER -1
+#define APG_NO_GAIN -2
+#define APG_PANIC -3
+
+typedef struct ApgArray ApgArray;
+
+/* Linear array of `num` isotropic elements along x, `spacing` meters apart; NULL on failure */
+ApgArray *apg_uniform_linear_create(size_t num, double spacing);
+
+/* Release a handle; NULL is ignored */
+void apg_array_destroy(ApgArray *handle);
+
+/* Complex gain towards (theta, phi) radians at `frequency` Hz; returns APG_OK or an error code */
+int apg_array_gain(const ApgArray *handle, double frequency, double theta, double phi,
+                   double *out_re, double *out_im);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,106 @@
+//! Azimuth/elevation sampling
+//!
+//! Radar displays are usually drawn in azimuth and elevation rather than the
+//! theta/phi used everywhere else in this crate. Boresight (az = 0, el = 0)
+//! is the +z axis, which is broadside for an array in the XY plane.
+
+use num::complex::Complex;
+
+use crate::PI;
+
+/// Order of the azimuth and elevation rotations
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AzElConvention {
+    /// Azimuth over elevation: elevation is measured from the XZ plane
+    AzOverEl,
+    /// Elevation over azimuth: azimuth is measured from the YZ plane
+    ElOverAz,
+}
+
+impl AzElConvention {
+    /// Convert an azimuth/elevation pair to theta/phi (radians)
+    pub fn to_theta_phi(&self, az: f64, el: f64) -> (f64, f64) {
+        let (x, y, z) = match self {
+            AzElConvention::AzOverEl => (az.sin() * el.cos(), el.sin(), az.cos() * el.cos()),
+            AzElConvention::ElOverAz => (az.sin(), az.cos() * el.sin(), az.cos() * el.cos()),
+        };
+
+        let theta = z.clamp(-1.0, 1.0).acos();
+        let phi = y.atan2(x).rem_euclid(2.0 * PI);
+        (theta, phi)
+    }
+}
+
+/// Complex gain sampled on a regular azimuth/elevation grid
+///
+/// Azimuth runs from -PI to PI and elevation from -PI/2 to PI/2, both ends
+/// included. Samples are stored one elevation row at a time.
+#[derive(Clone, Debug)]
+pub struct AzElGrid {
+    convention: AzElConvention,
+    az_step: f64,
+    el_step: f64,
+    // values[el_idx][az_idx]
+    values: Vec<Vec<Complex<f64>>>,
+}
+
+impl AzElGrid {
+    /// Build a grid by evaluating `f(theta, phi)` at every az/el sample
+    pub fn from_fn<F>(convention: AzElConvention, az_step: f64, el_step: f64, mut f: F) -> AzElGrid
+    where
+        F: FnMut(f64, f64) -> Complex<f64>,
+    {
+        let num_az = (2.0 * PI / az_step).round() as usize + 1;
+        let num_el = (PI / el_step).round() as usize + 1;
+
+        let values = (0..num_el)
+            .map(|el_idx| {
+                let el = -PI / 2.0 + el_idx as f64 * el_step;
+                (0..num_az)
+                    .map(|az_idx| {
+                        let az = -PI + az_idx as f64 * az_step;
+                        let (theta, phi) = convention.to_theta_phi(az, el);
+                        f(theta, phi)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        AzElGrid { convention, az_step, el_step, values }
+    }
+
+    /// Convention used to convert the samples to theta/phi
+    pub fn convention(&self) -> AzElConvention {
+        self.convention
+    }
+
+    /// Number of azimuth samples in each elevation row
+    pub fn num_az(&self) -> usize {
+        self.values[0].len()
+    }
+
+    /// Number of elevation rows
+    pub fn num_el(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Azimuth of the given sample index (radians)
+    pub fn az(&self, az_idx: usize) -> f64 {
+        -PI + az_idx as f64 * self.az_step
+    }
+
+    /// Elevation of the given sample index (radians)
+    pub fn el(&self, el_idx: usize) -> f64 {
+        -PI / 2.0 + el_idx as f64 * self.el_step
+    }
+
+    /// Complex gain at the given sample
+    pub fn get(&self, el_idx: usize, az_idx: usize) -> Complex<f64> {
+        self.values[el_idx][az_idx]
+    }
+
+    /// All samples, one elevation row at a time
+    pub fn values(&self) -> &[Vec<Complex<f64>>] {
+        &self.values
+    }
+}
@@ -0,0 +1,105 @@
+//! Excitations kept apart from the array
+//!
+//! The weights normally live in the elements. A `Beamformer` holds a set of
+//! weights on its own so several excitations can be kept for one array and
+//! swapped in as needed. Saving to a file is in the `io` module.
+
+use std::fmt;
+use std::str::FromStr;
+
+use num::complex::Complex;
+
+use crate::data_files::parse_numbers;
+use crate::{DataFileError, ElementArray, Point};
+
+/// One complex weight per element, in the same order as the array's elements
+///
+/// The text form (`Display`/`FromStr`) is one `re im` pair per line. Blank
+/// lines and lines starting with `#` are skipped when reading.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Beamformer {
+    weights: Vec<Complex<f64>>,
+}
+
+impl Beamformer {
+    /// Create a beamformer from its weights
+    pub fn new(weights: Vec<Complex<f64>>) -> Beamformer {
+        Beamformer { weights }
+    }
+
+    /// Capture the weights currently set on an array
+    pub fn from_array(array: &ElementArray) -> Beamformer {
+        Beamformer::new(array.elements().iter().map(|n| n.weight()).collect())
+    }
+
+    /// The weights, one per element
+    pub fn weights(&self) -> &[Complex<f64>] {
+        &self.weights
+    }
+
+    /// Number of weights
+    pub fn len(&self) -> usize {
+        self.weights.len()
+    }
+
+    /// Whether there are no weights
+    pub fn is_empty(&self) -> bool {
+        self.weights.is_empty()
+    }
+
+    /// Set the weight of every element of `array`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the array doesn't have one element per weight.
+    ///
+    pub fn apply(&self, array: &mut ElementArray) {
+        assert_eq!(array.elements().len(), self.weights.len(), "one weight per element");
+        for (n, weight) in array.elements_mut().iter_mut().zip(&self.weights) {
+            n.set_weight(*weight);
+        }
+    }
+}
+
+impl ElementArray {
+    /// Position and weight of every element, the feed manifest for the hardware
+    ///
+    /// See `io::write_excitation_csv` to write it out.
+    ///
+    pub fn excitation_table(&self) -> Vec<(Point, Complex<f64>)> {
+        self.elements.iter().map(|n| (n.position().clone(), n.weight())).collect()
+    }
+}
+
+impl fmt::Display for Beamformer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for weight in &self.weights {
+            // `{:e}` round-trips f64 exactly
+            writeln!(f, "{:e} {:e}", weight.re, weight.im)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Beamformer {
+    type Err = DataFileError;
+
+    fn from_str(contents: &str) -> Result<Beamformer, DataFileError> {
+        let mut weights = Vec::new();
+        for (idx, line) in contents.lines().enumerate() {
+            if line.trim().is_empty() || line.trim_start().starts_with('#') {
+                continue;
+            }
+            match parse_numbers(line, idx + 1)?[..] {
+                [re, im] => weights.push(Complex::new(re, im)),
+                _ => {
+                    return Err(DataFileError::Parse {
+                        line: idx + 1,
+                        message: "expected the real and imaginary parts of a weight".to_string(),
+                    })
+                }
+            }
+        }
+        Ok(Beamformer::new(weights))
+    }
+}
@@ -0,0 +1,125 @@
+//! Beamwidth estimates from the aperture
+//!
+//! A quick beamwidth doesn't need the pattern: it follows from the size of
+//! the aperture in wavelengths and from how strongly it is tapered.
+
+use std::collections::BTreeMap;
+
+use crate::{effective_weight, ElementArray, GainIface, Point, PI, SPEED_OF_LIGHT};
+
+/// Half-power beamwidth of a uniformly illuminated line source is this times `wavelength / length`
+const UNIFORM_HPBW_FACTOR: f64 = 0.886;
+
+/// Angle stepped away from the beam peak while looking for the half-power points (radians)
+const HPBW_SEARCH_STEP: f64 = 0.1 * PI / 180.0;
+
+/// Effective length and taper efficiency of the element amplitudes projected onto one axis
+///
+/// Elements are grouped into columns by `coordinate` (to the nearest
+/// nanometer) and the amplitudes in each column are summed.
+fn projected_aperture(array: &ElementArray, coordinate: impl Fn(&Point) -> f64) -> (f64, f64) {
+    let mut columns: BTreeMap<i64, f64> = BTreeMap::new();
+    for (idx, n) in array.elements.iter().enumerate() {
+        let key = (coordinate(n.position()) * 1e9).round() as i64;
+        *columns.entry(key).or_insert(0.0) += effective_weight(array, idx, n.weight()).norm();
+    }
+    let count = columns.len();
+    if count < 2 {
+        return (0.0, 1.0);
+    }
+
+    // each of the `count` samples stands for `extent / (count - 1)` of aperture
+    let first = *columns.keys().next().unwrap() as f64 * 1e-9;
+    let last = *columns.keys().next_back().unwrap() as f64 * 1e-9;
+    let length = (last - first) * count as f64 / (count - 1) as f64;
+
+    let sum: f64 = columns.values().sum();
+    let sum_sq: f64 = columns.values().map(|a| a * a).sum();
+    (length, sum * sum / (count as f64 * sum_sq))
+}
+
+impl ElementArray {
+    /// Approximate half-power beamwidths (radians) at broadside in the XZ and YZ planes
+    ///
+    /// Uses `0.886 * wavelength / L` for the aperture length `L` along x
+    /// (for the XZ plane) and along y (for the YZ plane), where `L` is the
+    /// extent of the element positions plus one element spacing. A taper
+    /// broadens the beam; this is approximated by dividing by the taper
+    /// efficiency of the weight amplitudes projected onto each axis, which
+    /// is within about 10% for the usual tapers. Element patterns are
+    /// ignored. An array with no extent along an axis has an infinite
+    /// beamwidth in that plane.
+    ///
+    pub fn estimated_hpbw(&self, frequency: f64) -> (f64, f64) {
+        let wavelength = SPEED_OF_LIGHT / frequency;
+        let hpbw = |(length, efficiency): (f64, f64)| {
+            if length > 0.0 {
+                UNIFORM_HPBW_FACTOR * wavelength / length / efficiency
+            } else {
+                f64::INFINITY
+            }
+        };
+        (
+            hpbw(projected_aperture(self, |position| position.x)),
+            hpbw(projected_aperture(self, |position| position.y)),
+        )
+    }
+
+    /// Theoretical widening of the main beam when it is scanned to `theta0`
+    ///
+    /// The aperture seen from the scan direction shrinks by `cos(theta0)`,
+    /// so the half-power beamwidth in the scan plane grows by
+    /// `1 / cos(theta0)`. This stops holding close to endfire, where the
+    /// beam merges with its mirror image.
+    ///
+    pub fn beam_broadening_factor(&self, theta0: f64) -> f64 {
+        1.0 / theta0.cos()
+    }
+
+    /// Measured half-power beamwidth in the scan plane at `(theta0, phi0)` over the one at broadside
+    ///
+    /// The weights are steered the same way as `steer` on a copy of the
+    /// array, and the half-power points either side of the beam are found in
+    /// the plane at `phi0`. Compare with `beam_broadening_factor` to see how
+    /// far the element patterns and the array size move the real array from
+    /// the ideal.
+    ///
+    pub fn measured_broadening_factor(&self, frequency: f64, theta0: f64, phi0: f64) -> f64 {
+        scanned_hpbw(self, frequency, theta0, phi0) / scanned_hpbw(self, frequency, 0.0, phi0)
+    }
+}
+
+/// Full half-power beamwidth (radians) in the plane at `phi0` with the beam steered to `(theta0, phi0)`
+fn scanned_hpbw(array: &ElementArray, frequency: f64, theta0: f64, phi0: f64) -> f64 {
+    let mut array = array.clone();
+    array.steer(frequency, theta0, phi0);
+
+    // signed angle in the plane, negative going over the pole to phi0 + PI
+    let power = |angle: f64| {
+        let (theta, phi) = if angle < 0.0 { (-angle, phi0 + PI) } else { (angle, phi0) };
+        array.get_gain(frequency, theta, phi).unwrap().norm_sqr()
+    };
+    let half = power(theta0) / 2.0;
+
+    let edge = |direction: f64| {
+        let mut inside = theta0;
+        let mut outside = theta0 + direction * HPBW_SEARCH_STEP;
+        while power(outside) >= half {
+            if outside.abs() >= PI {
+                return outside.clamp(-PI, PI);
+            }
+            inside = outside;
+            outside += direction * HPBW_SEARCH_STEP;
+        }
+        for _ in 0..40 {
+            let middle = (inside + outside) / 2.0;
+            if power(middle) >= half {
+                inside = middle;
+            } else {
+                outside = middle;
+            }
+        }
+        (inside + outside) / 2.0
+    };
+    edge(1.0) - edge(-1.0)
+}
@@ -0,0 +1,65 @@
+//! Calibration against a known source
+//!
+//! Every channel behind an element has its own unknown amplitude and phase
+//! error. Measuring each element's response towards a source in a known
+//! direction and comparing it with what the model predicts gives a
+//! correction per channel. The correction can either be folded into the
+//! weights (`calibrate`) or kept alongside them (`set_calibration`).
+
+use num::complex::Complex;
+
+use crate::ElementArray;
+
+impl ElementArray {
+    /// Fold channel corrections from measured element responses into the weights
+    ///
+    /// `measured[n]` is the complex response of element `n` towards a
+    /// calibration source at `(theta, phi)`, measured with the array's current
+    /// weights. The model predicts `element_contributions` for the same
+    /// direction; each weight is multiplied by predicted / measured, which
+    /// cancels that channel's error once the new weights are commanded.
+    /// Elements with a zero measured response (e.g. dead) are left alone.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there isn't one measurement per element.
+    ///
+    pub fn calibrate(&mut self, frequency: f64, measured: &[Complex<f64>], theta: f64, phi: f64) {
+        assert_eq!(measured.len(), self.elements.len(), "one measurement per element");
+        let predicted = self.element_contributions(frequency, theta, phi);
+        for ((n, measured), predicted) in self.elements.iter_mut().zip(measured).zip(predicted) {
+            if measured.norm_sqr() > 0.0 {
+                let weight = n.weight() * predicted / measured;
+                n.set_weight(weight);
+            }
+        }
+    }
+
+    /// Apply a per-element complex correction on top of the weights
+    ///
+    /// `calibration[n]` multiplies the weight of element `n` wherever the
+    /// array is evaluated from its weights: gain and field, track, monopulse
+    /// and near field patterns, scan studies and sensitivity metrics. The
+    /// beamforming weights themselves aren't touched, so steering or
+    /// tapering afterwards keeps the correction, and synthesis routines
+    /// solve for the weights to command through the corrected channels.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there isn't one correction per element.
+    ///
+    pub fn set_calibration(&mut self, calibration: &[Complex<f64>]) {
+        assert_eq!(calibration.len(), self.elements.len(), "one correction per element");
+        self.calibration = Some(calibration.to_vec());
+    }
+
+    /// Per-element correction set by `set_calibration`, if any
+    pub fn calibration(&self) -> Option<&[Complex<f64>]> {
+        self.calibration.as_deref()
+    }
+
+    /// Remove the correction set by `set_calibration`
+    pub fn clear_calibration(&mut self) {
+        self.calibration = None;
+    }
+}
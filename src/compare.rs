@@ -0,0 +1,65 @@
+//! Comparing a pattern with a reference
+//!
+//! Simulations are validated against chamber measurements by a handful of
+//! numbers rather than sample by sample: how far off the peak gain, the
+//! pointing and the beamwidth are, and how well the sidelobes agree.
+
+use crate::{separation, PatternGrid};
+
+/// Lowest level (dB below the peak) used when comparing sidelobes
+const SIDELOBE_FLOOR_DB: f64 = -60.0;
+
+/// Differences between a pattern and a reference, pattern minus reference
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FitReport {
+    /// Difference in peak gain (dB)
+    pub peak_gain_error_db: f64,
+    /// Angle between the two peak directions (radians)
+    pub pointing_error: f64,
+    /// Difference in (equivalent) half-power beamwidth (radians)
+    pub beamwidth_error: f64,
+    /// RMS difference of the normalized patterns outside the main lobe (dB)
+    pub rms_sidelobe_error_db: f64,
+}
+
+impl PatternGrid {
+    /// Compare this pattern with a reference, e.g. a simulation with a measurement
+    ///
+    /// Peak, pointing and beamwidth come from each grid on its own samples.
+    /// For the sidelobes the reference is interpolated onto this grid, both
+    /// are normalized to their peaks (floored at -60 dB), and the RMS
+    /// difference is taken over the samples outside this pattern's main lobe.
+    ///
+    pub fn fit_error_vs(&self, reference: &PatternGrid) -> FitReport {
+        let peak_direction = |grid: &PatternGrid| {
+            let (phi_idx, theta_idx) = grid.peak_index();
+            (grid.theta(theta_idx), grid.phi(phi_idx))
+        };
+
+        let own_peak = self.peak_power();
+        let reference_peak = reference.peak_power();
+        let normalized = |power: f64, peak: f64| (10.0 * (power / peak).log10()).max(SIDELOBE_FLOOR_DB);
+
+        let mask = self.main_lobe_mask();
+        let mut sum = 0.0;
+        let mut count = 0;
+        for (phi_idx, row) in self.values().iter().enumerate() {
+            for (theta_idx, value) in row.iter().enumerate() {
+                if mask[phi_idx][theta_idx] {
+                    continue;
+                }
+                let other = reference.interpolate(self.theta(theta_idx), self.phi(phi_idx));
+                let diff = normalized(value.norm_sqr(), own_peak) - normalized(other.norm_sqr(), reference_peak);
+                sum += diff * diff;
+                count += 1;
+            }
+        }
+
+        FitReport {
+            peak_gain_error_db: 10.0 * (own_peak / reference_peak).log10(),
+            pointing_error: separation(peak_direction(self), peak_direction(reference)),
+            beamwidth_error: self.equivalent_hpbw() - reference.equivalent_hpbw(),
+            rms_sidelobe_error_db: if count > 0 { (sum / count as f64).sqrt() } else { 0.0 },
+        }
+    }
+}
@@ -0,0 +1,89 @@
+//! Arrays of sub-arrays
+//!
+//! Large arrays are often built from identical sub-arrays, each with its own
+//! beamformer, combined by a second level of weights. Sub-arrays keep their
+//! own element positions, so they should be built where they sit in the
+//! full array.
+
+use alloc::{boxed::Box, vec, vec::Vec};
+
+use num::complex::Complex;
+
+use crate::{ArrayIface, ElementArray, ElementIface, GainIface};
+
+/// An array whose members are themselves arrays
+///
+/// The gain is the weighted sum of the sub-array gains. A `CompositeArray`
+/// is an `ArrayIface` too, so hierarchies can go as deep as needed.
+pub struct CompositeArray {
+    subarrays: Vec<Box<dyn ArrayIface>>,
+    // weight applied to each sub-array, on top of its own element weights
+    weights: Vec<Complex<f64>>,
+}
+
+impl CompositeArray {
+    /// Combine sub-arrays, all with a weight of 1
+    pub fn new(subarrays: Vec<Box<dyn ArrayIface>>) -> CompositeArray {
+        let weights = vec![Complex::new(1.0, 0.0); subarrays.len()];
+        CompositeArray { subarrays, weights }
+    }
+
+    /// Sub-arrays of the array
+    pub fn subarrays(&self) -> &[Box<dyn ArrayIface>] {
+        &self.subarrays
+    }
+
+    /// Mutable access to the sub-arrays, e.g. to re-steer them
+    pub fn subarrays_mut(&mut self) -> &mut [Box<dyn ArrayIface>] {
+        &mut self.subarrays
+    }
+
+    /// Weight applied to each sub-array
+    pub fn weights(&self) -> &[Complex<f64>] {
+        &self.weights
+    }
+
+    /// Replace the weight applied to sub-array `idx`
+    pub fn set_weight(&mut self, idx: usize, weight: Complex<f64>) {
+        self.weights[idx] = weight;
+    }
+
+    /// Equivalent single level array of all the elements
+    ///
+    /// Sub-array weights, at every level of nesting, are multiplied into
+    /// the element weights so the flat array has the same gain.
+    ///
+    pub fn flatten(&self) -> ElementArray {
+        ElementArray::new(self.flat_elements())
+    }
+}
+
+impl GainIface for CompositeArray {
+    fn get_gain(&self, frequency: f64, theta: f64, phi: f64) -> Option<Complex<f64>> {
+        self.subarrays
+            .iter()
+            .zip(&self.weights)
+            .map(|(subarray, weight)| Some( subarray.get_gain(frequency, theta, phi)? * weight ))
+            .sum()
+    }
+}
+
+impl ArrayIface for CompositeArray {
+    fn num_elements(&self) -> usize {
+        self.subarrays.iter().map(|subarray| subarray.num_elements()).sum()
+    }
+
+    fn flat_elements(&self) -> Vec<Box<dyn ElementIface>> {
+        self.subarrays
+            .iter()
+            .zip(&self.weights)
+            .flat_map(|(subarray, weight)| {
+                subarray.flat_elements().into_iter().map(move |mut n| {
+                    let combined = n.weight() * weight;
+                    n.set_weight(combined);
+                    n
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,54 @@
+//! Sign convention of the positional phase
+//!
+//! The crate has always placed elements with `exp(+j k r·û)`. Much of the
+//! antenna literature, and a lot of simulator output, uses `exp(-j k r·û)`
+//! instead. Mixing the two conjugates the phase, which mirrors steering
+//! directions and phase centers. The convention is a process-wide setting so
+//! that every element, array and table agrees on it.
+
+use core::sync::atomic::{AtomicU8, Ordering};
+
+/// Sign of the exponent in the positional phase
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PhaseConvention {
+    /// `exp(+j k r·û)`, the default
+    #[default]
+    Positive,
+    /// `exp(-j k r·û)`
+    Negative,
+}
+
+impl PhaseConvention {
+    /// +1 or -1, the sign of the phase exponent
+    pub(crate) fn sign(self) -> f64 {
+        match self {
+            PhaseConvention::Positive => 1.0,
+            PhaseConvention::Negative => -1.0,
+        }
+    }
+}
+
+// 0 for Positive, 1 for Negative
+static CONVENTION: AtomicU8 = AtomicU8::new(0);
+
+/// Select the phase convention used from now on by the whole crate
+///
+/// Patterns, steering weights and scan tables computed under one convention
+/// must not be mixed with ones computed under the other; set it once at
+/// start up.
+///
+pub fn set_phase_convention(convention: PhaseConvention) {
+    let value = match convention {
+        PhaseConvention::Positive => 0,
+        PhaseConvention::Negative => 1,
+    };
+    CONVENTION.store(value, Ordering::Relaxed);
+}
+
+/// The phase convention currently in use
+pub fn phase_convention() -> PhaseConvention {
+    match CONVENTION.load(Ordering::Relaxed) {
+        0 => PhaseConvention::Positive,
+        _ => PhaseConvention::Negative,
+    }
+}
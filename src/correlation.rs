@@ -0,0 +1,50 @@
+//! Pattern overlap between elements
+//!
+//! The correlation (pattern-overlap) matrix is the integral over the sphere
+//! of each pair of embedded element patterns, `C[m][n] = ∮ g_m conj(g_n) dΩ`.
+//! It is the starting point for superdirective and maximum ratio combining
+//! weights. Only available with the `ndarray` feature.
+
+use ndarray::Array2;
+use num::complex::Complex;
+
+use crate::{ElementArray, PatternGrid};
+
+impl ElementArray {
+    /// Correlation matrix of the element patterns, including their positions and weights
+    ///
+    /// The diagonal holds the power radiated by each element and the matrix
+    /// is Hermitian. Accuracy depends on the sphere sampling steps.
+    ///
+    pub fn correlation_matrix(&self, frequency: f64, theta_step: f64, phi_step: f64) -> Array2<Complex<f64>> {
+        let grids: Vec<PatternGrid> = self.elements
+            .iter()
+            .map(|n| n.sample_sphere(frequency, theta_step, phi_step))
+            .collect();
+
+        // sin(theta) * d(theta) * d(phi) for each theta sample
+        let solid_angle: Vec<f64> = match grids.first() {
+            Some(grid) => (0..grid.num_theta())
+                .map(|idx| grid.theta(idx).sin() * theta_step * phi_step)
+                .collect(),
+            None => Vec::new(),
+        };
+
+        let count = grids.len();
+        let mut matrix = Array2::zeros((count, count));
+        for m in 0..count {
+            for n in m..count {
+                let overlap: Complex<f64> = grids[m]
+                    .values()
+                    .iter()
+                    .zip(grids[n].values())
+                    .flat_map(|(row_m, row_n)| row_m.iter().zip(row_n).zip(&solid_angle))
+                    .map(|((gm, gn), d_omega)| gm * gn.conj() * d_omega)
+                    .sum();
+                matrix[[m, n]] = overlap;
+                matrix[[n, m]] = overlap.conj();
+            }
+        }
+        matrix
+    }
+}
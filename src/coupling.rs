@@ -0,0 +1,34 @@
+//! Mutual coupling between elements
+//!
+//! Closed form results for simple geometries, useful when there's no
+//! measured or simulated coupling data to hand.
+
+use num::complex::Complex;
+
+use crate::math::sine_cosine_integrals;
+use crate::{FREE_SPACE_IMPEDANCE, PI, SPEED_OF_LIGHT};
+
+/// Mutual impedance between two parallel, side-by-side dipoles (ohms)
+///
+/// Uses the induced-EMF method with sinusoidal currents, which is only
+/// accurate for thin dipoles of about half a wavelength. `spacing` and
+/// `length` are in meters and `spacing` must be greater than zero (for the
+/// self impedance use the wire radius).
+///
+pub fn mutual_impedance_dipoles(spacing: f64, length: f64, frequency: f64) -> Complex<f64> {
+    let k = 2.0 * PI * frequency / SPEED_OF_LIGHT;
+    let hypot = spacing.hypot(length);
+    let u0 = k * spacing;
+    let u1 = k * (hypot + length);
+    let u2 = k * (hypot - length);
+
+    let (si0, ci0) = sine_cosine_integrals(u0);
+    let (si1, ci1) = sine_cosine_integrals(u1);
+    let (si2, ci2) = sine_cosine_integrals(u2);
+
+    let scale = FREE_SPACE_IMPEDANCE / (4.0 * PI);
+    Complex::new(
+        scale * (2.0 * ci0 - ci1 - ci2),
+        -scale * (2.0 * si0 - si1 - si2),
+    )
+}
@@ -0,0 +1,76 @@
+//! Principal plane pattern cuts
+//!
+//! A cut is the pattern along a single plane through boresight, which is how
+//! patterns are usually plotted and compared. Theta runs from -PI to PI; a
+//! negative theta is the other half of the plane, i.e. phi + PI.
+
+use num::complex::Complex;
+
+use crate::PI;
+
+/// Complex gain sampled along the plane at a fixed phi
+#[derive(Clone, Debug)]
+pub struct PatternCut {
+    phi: f64,
+    theta_step: f64,
+    values: Vec<Complex<f64>>,
+}
+
+impl PatternCut {
+    /// Build a cut by evaluating `f(theta, phi)` along the plane at `phi`
+    ///
+    /// `f` is only called with theta from 0 to PI, the negative half of the
+    /// cut is evaluated at phi + PI.
+    ///
+    pub fn from_fn<F>(phi: f64, theta_step: f64, mut f: F) -> PatternCut
+    where
+        F: FnMut(f64, f64) -> Complex<f64>,
+    {
+        let num = (2.0 * PI / theta_step).round() as usize + 1;
+        let values = (0..num)
+            .map(|idx| {
+                let theta = -PI + idx as f64 * theta_step;
+                if theta < 0.0 {
+                    f(-theta, (phi + PI).rem_euclid(2.0 * PI))
+                } else {
+                    f(theta, phi)
+                }
+            })
+            .collect();
+        PatternCut { phi, theta_step, values }
+    }
+
+    /// Phi of the plane (radians)
+    pub fn phi(&self) -> f64 {
+        self.phi
+    }
+
+    /// Theta of every sample, from -PI to PI (radians)
+    pub fn angles(&self) -> Vec<f64> {
+        (0..self.values.len()).map(|idx| -PI + idx as f64 * self.theta_step).collect()
+    }
+
+    /// Complex gain of every sample
+    pub fn values(&self) -> &[Complex<f64>] {
+        &self.values
+    }
+
+    /// Power of every sample relative to the peak of the cut (dB)
+    pub fn normalize_db(&self) -> Vec<f64> {
+        let peak = self.values.iter().map(|value| value.norm_sqr()).fold(0.0, f64::max);
+        self.values.iter().map(|value| 10.0 * (value.norm_sqr() / peak).log10()).collect()
+    }
+
+    /// Power of every sample relative to an isotropic radiator (dBi)
+    ///
+    /// `total_power` is the power radiated over the whole sphere, e.g. from
+    /// `PatternGrid::radiated_power`, so cuts of different arrays can be
+    /// compared directly.
+    ///
+    pub fn normalized_to_isotropic(&self, total_power: f64) -> Vec<f64> {
+        self.values
+            .iter()
+            .map(|value| 10.0 * (4.0 * PI * value.norm_sqr() / total_power).log10())
+            .collect()
+    }
+}
@@ -0,0 +1,222 @@
+//! Elements defined by a table of data
+//!
+//! Reads and interpolates a data table for the antenna pattern with a
+//! positional offset. The table is the pattern at the phase center of the
+//! element; placing the element in an array adds the positional phase.
+
+use num::complex::Complex;
+
+use crate::touchstone::parse_s1p;
+use crate::{calc_phase, element_field, element_gain, DataFileError, ElementArray, ElementIface, FarField, GainIface, PatternGrid, Point};
+
+/// A special element that relies on a table of data
+///
+/// The table is bilinearly interpolated between samples. It is only valid
+/// at the frequency it was measured/simulated at, so the frequency passed to
+/// `get_gain` only affects the positional phase.
+///
+/// Polarized data is stored as Ludwig-3 co and cross-polar tables. The gain
+/// of the element is the co-polar component.
+#[derive(Clone)]
+pub struct DataElement {
+    // position of the element's phase center in space
+    position: Point,
+    // Weight applied to element pattern
+    weight: Complex<f64>,
+    // time delay in the feed (seconds)
+    delay: f64,
+    // (co-polar) pattern at the phase center
+    data: PatternGrid,
+    // cross-polar pattern at the phase center, if known
+    cross: Option<PatternGrid>,
+    // (frequency (Hz), S11) of the input match, empty if matched
+    s11: Vec<(f64, Complex<f64>)>,
+}
+
+/// How to fill in the phase of magnitude-only data
+#[derive(Clone, Debug)]
+pub enum PhaseModel {
+    /// Zero phase everywhere, i.e. the phase center is the reference point
+    Constant,
+    /// Phase of a point source sitting at `center`, relative to the reference point
+    PhaseCenter {
+        /// position of the assumed phase center
+        center: Point,
+        /// frequency the data was measured at (Hz)
+        frequency: f64,
+    },
+}
+
+impl DataElement {
+    /// Create an element from a table of complex gain
+    pub fn new(data: PatternGrid, position: Point) -> DataElement {
+        DataElement {
+            position,
+            weight: Complex::new(1.0, 0.0),
+            delay: 0.0,
+            data,
+            cross: None,
+            s11: Vec::new(),
+        }
+    }
+
+    /// Create an element from Ludwig-3 co and cross-polar tables
+    ///
+    /// Both tables must be sampled on the same grid.
+    ///
+    pub fn new_polarized(co: PatternGrid, cross: PatternGrid, position: Point) -> DataElement {
+        DataElement {
+            cross: Some(cross),
+            ..DataElement::new(co, position)
+        }
+    }
+
+    /// Create an element from magnitude-only data with a synthesized phase
+    ///
+    /// Only the magnitude of each sample in `magnitudes` is used. The element
+    /// sits at the origin.
+    ///
+    pub fn with_synthesized_phase(magnitudes: &PatternGrid, model: PhaseModel) -> DataElement {
+        let data = PatternGrid::from_fn(magnitudes.theta_step(), magnitudes.phi_step(), |theta, phi| {
+            let magnitude = magnitudes.interpolate(theta, phi).norm();
+            match &model {
+                PhaseModel::Constant => Complex::new(magnitude, 0.0),
+                PhaseModel::PhaseCenter { center, frequency } => {
+                    magnitude * calc_phase(center, *frequency, theta, phi)
+                }
+            }
+        });
+        DataElement::new(data, Point::default())
+    }
+
+    /// Table of complex (co-polar) gain at the phase center
+    pub fn data(&self) -> &PatternGrid {
+        &self.data
+    }
+
+    /// Table of cross-polar gain at the phase center, if there is one
+    pub fn cross(&self) -> Option<&PatternGrid> {
+        self.cross.as_ref()
+    }
+
+    /// Polarized far field of the element at its position, including its weight
+    ///
+    /// Without a cross-polar table the cross-polar component is zero.
+    ///
+    pub fn get_field(&self, frequency: f64, theta: f64, phi: f64) -> Option<FarField> {
+        element_field(self, frequency, theta, phi)
+    }
+
+    /// Attach the input match from the contents of a Touchstone `.s1p` file
+    ///
+    /// The pattern is taken to be the gain of the element on its own;
+    /// `realized_gain` adds the mismatch loss of the match. The option line
+    /// (default `# GHz S MA R 50`) may use any frequency unit and the MA, DB
+    /// or RI formats, but only S parameters.
+    ///
+    pub fn with_match_str(pattern: DataElement, s1p: &str) -> Result<DataElement, DataFileError> {
+        Ok(DataElement {
+            s11: parse_s1p(s1p)?,
+            ..pattern
+        })
+    }
+
+    /// Input reflection coefficient at `frequency`
+    ///
+    /// Linearly interpolated between the tabulated frequencies and held at
+    /// the end values outside them. Zero (matched) without match data.
+    ///
+    pub fn s11(&self, frequency: f64) -> Complex<f64> {
+        if self.s11.is_empty() {
+            return Complex::new(0.0, 0.0);
+        }
+        let idx = self.s11.partition_point(|(f, _)| *f <= frequency);
+        if idx == 0 {
+            return self.s11[0].1;
+        }
+        if idx == self.s11.len() {
+            return self.s11[idx - 1].1;
+        }
+        let ((f0, s0), (f1, s1)) = (self.s11[idx - 1], self.s11[idx]);
+        s0 + (s1 - s0) * ((frequency - f0) / (f1 - f0))
+    }
+
+    /// Fraction of the available power accepted by the element, `1 - |S11|^2`
+    pub fn mismatch_factor(&self, frequency: f64) -> f64 {
+        1.0 - self.s11(frequency).norm_sqr()
+    }
+
+    /// Complex gain including the mismatch loss of the input match
+    ///
+    /// The power gain is scaled by `mismatch_factor`; `get_gain` stays the
+    /// gain of the pattern alone.
+    ///
+    pub fn realized_gain(&self, frequency: f64, theta: f64, phi: f64) -> Option<Complex<f64>> {
+        Some( self.get_gain(frequency, theta, phi)? * self.mismatch_factor(frequency).sqrt() )
+    }
+}
+
+impl ElementArray {
+    /// Replace element `idx` with a measured embedded element pattern
+    ///
+    /// Edge elements of a real array see different surroundings than the
+    /// ones in the middle, so each can be given its own embedded pattern.
+    /// `element` takes over the position, weight and delay of the element
+    /// it replaces; its own are ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is out of range.
+    ///
+    pub fn set_embedded_pattern(&mut self, idx: usize, mut element: DataElement) {
+        let old = &self.elements[idx];
+        element.set_position(old.position().clone());
+        element.set_weight(old.weight());
+        element.set_delay(old.delay());
+        self.elements[idx] = Box::new(element);
+    }
+}
+
+impl GainIface for DataElement {
+    fn get_gain(&self, frequency: f64, theta: f64, phi: f64) -> Option<Complex<f64>> {
+        element_gain(self, frequency, theta, phi)
+    }
+}
+
+impl ElementIface for DataElement {
+    fn position(&self) -> &Point {
+        &self.position
+    }
+
+    fn set_position(&mut self, position: Point) {
+        self.position = position;
+    }
+
+    fn weight(&self) -> Complex<f64> {
+        self.weight
+    }
+
+    fn set_weight(&mut self, weight: Complex<f64>) {
+        self.weight = weight;
+    }
+
+    fn delay(&self) -> f64 {
+        self.delay
+    }
+
+    fn set_delay(&mut self, delay: f64) {
+        self.delay = delay;
+    }
+
+    fn pattern(&self, _frequency: f64, theta: f64, phi: f64) -> Option<Complex<f64>> {
+        Some( self.data.interpolate(theta, phi) )
+    }
+
+    fn pattern_field(&self, _frequency: f64, theta: f64, phi: f64) -> Option<FarField> {
+        let cross = match &self.cross {
+            Some(cross) => cross.interpolate(theta, phi),
+            None => Complex::new(0.0, 0.0),
+        };
+        Some( FarField::from_ludwig3(self.data.interpolate(theta, phi), cross, phi) )
+    }
+}
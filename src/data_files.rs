@@ -0,0 +1,374 @@
+//! Readers for measured/simulated pattern files
+//!
+//! Every reader ends up with the same thing: co and cross-polar (Ludwig-3)
+//! tables on a regular theta/phi grid, wrapped in a `DataElement`. The grid
+//! spacing is inferred from the file.
+
+use std::fmt;
+
+use num::complex::Complex;
+
+use crate::{DataElement, FarField, PatternGrid, Point, PI};
+
+/// Errors from reading pattern files
+#[derive(Debug)]
+pub enum DataFileError {
+    /// The file couldn't be read
+    Io(std::io::Error),
+    /// The file contents don't match the expected format
+    Parse {
+        /// line number (starting at 1) where the problem was found
+        line: usize,
+        /// what was wrong
+        message: String,
+    },
+    /// The samples in the file don't cover a full theta/phi grid
+    IncompleteGrid,
+}
+
+impl fmt::Display for DataFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataFileError::Io(err) => write!(f, "unable to read pattern file: {}", err),
+            DataFileError::Parse { line, message } => write!(f, "line {}: {}", line, message),
+            DataFileError::IncompleteGrid => write!(f, "samples don't cover a full theta/phi grid"),
+        }
+    }
+}
+
+impl std::error::Error for DataFileError {}
+
+impl From<std::io::Error> for DataFileError {
+    fn from(err: std::io::Error) -> DataFileError {
+        DataFileError::Io(err)
+    }
+}
+
+/// Ludwig-3 co and cross-polar components
+type CoCross = (Complex<f64>, Complex<f64>);
+
+/// A single far-field sample (angles in radians)
+struct Sample {
+    theta: f64,
+    phi: f64,
+    field: FarField,
+}
+
+/// Parse every whitespace separated number on a line
+pub(crate) fn parse_numbers(line: &str, line_number: usize) -> Result<Vec<f64>, DataFileError> {
+    line.split_whitespace()
+        .map(|token| {
+            token.parse::<f64>().map_err(|_| DataFileError::Parse {
+                line: line_number,
+                message: format!("expected a number, found '{}'", token),
+            })
+        })
+        .collect()
+}
+
+/// An angle read from a file in degrees, in radians
+///
+/// `nan` and `inf` parse as numbers but aren't directions.
+fn parse_angle(degrees: f64, line_number: usize) -> Result<f64, DataFileError> {
+    if !degrees.is_finite() {
+        return Err(DataFileError::Parse {
+            line: line_number,
+            message: format!("expected a finite angle, found '{}'", degrees),
+        });
+    }
+    Ok(degrees.to_radians())
+}
+
+/// Smallest gap between distinct sorted values
+fn min_step(mut values: Vec<f64>) -> Option<f64> {
+    values.sort_by(f64::total_cmp);
+    values
+        .windows(2)
+        .map(|w| w[1] - w[0])
+        .filter(|step| *step > 1e-9)
+        .fold(None, |min: Option<f64>, step| Some(min.map_or(step, |m| m.min(step))))
+}
+
+/// Put scattered samples on a regular grid of Ludwig-3 co/cross tables
+fn grid_samples(samples: &[Sample]) -> Result<(PatternGrid, PatternGrid), DataFileError> {
+    let theta_step = min_step(samples.iter().map(|s| s.theta).collect()).ok_or(DataFileError::IncompleteGrid)?;
+    // a single cut through the whole sphere is still a grid, just a coarse one in phi
+    let phi_step = min_step(samples.iter().map(|s| s.phi).collect()).unwrap_or(PI);
+
+    let num_theta = (PI / theta_step).round() as usize + 1;
+    let num_phi = ((2.0 * PI / phi_step).round() as usize).max(1);
+    let mut table: Vec<Vec<Option<CoCross>>> = vec![vec![None; num_theta]; num_phi];
+
+    for sample in samples {
+        let theta_pos = sample.theta / theta_step;
+        let phi_pos = sample.phi / phi_step;
+        if (theta_pos - theta_pos.round()).abs() > 1e-6 || (phi_pos - phi_pos.round()).abs() > 1e-6 {
+            return Err(DataFileError::IncompleteGrid);
+        }
+        let theta_idx = theta_pos.round() as usize;
+        let phi_idx = phi_pos.round() as usize % num_phi;
+        if theta_idx < num_theta {
+            table[phi_idx][theta_idx] = Some(sample.field.ludwig3(sample.phi));
+        }
+    }
+
+    // theta = 0 and theta = PI are single points, any sample there fills the whole column
+    for theta_idx in [0, num_theta - 1] {
+        let pole = (0..num_phi).find_map(|phi_idx| {
+            table[phi_idx][theta_idx].map(|(co, cross)| {
+                let phi = phi_idx as f64 * phi_step;
+                (phi, FarField::from_ludwig3(co, cross, phi))
+            })
+        });
+        if let Some((pole_phi, field)) = pole {
+            let cos_theta = (theta_idx as f64 * theta_step).cos().signum();
+            for (phi_idx, row) in table.iter_mut().enumerate() {
+                let phi = phi_idx as f64 * phi_step;
+                if row[theta_idx].is_none() {
+                    row[theta_idx] = Some(rotate_pole(field, cos_theta, pole_phi, phi).ludwig3(phi));
+                }
+            }
+        }
+    }
+
+    if table.iter().flatten().any(|value| value.is_none()) {
+        return Err(DataFileError::IncompleteGrid);
+    }
+
+    let lookup = |theta: f64, phi: f64| {
+        let theta_idx = (theta / theta_step).round() as usize;
+        let phi_idx = (phi / phi_step).round() as usize % num_phi;
+        table[phi_idx][theta_idx].unwrap()
+    };
+    Ok((
+        PatternGrid::from_fn(theta_step, phi_step, |theta, phi| lookup(theta, phi).0),
+        PatternGrid::from_fn(theta_step, phi_step, |theta, phi| lookup(theta, phi).1),
+    ))
+}
+
+/// Re-express a field at a pole in the theta/phi unit vectors of another phi
+///
+/// At the poles the unit vectors lie in the XY plane:
+/// theta_hat = cos(theta) * (cos(phi), sin(phi)) and phi_hat = (-sin(phi), cos(phi)).
+fn rotate_pole(field: FarField, cos_theta: f64, from_phi: f64, to_phi: f64) -> FarField {
+    let ex = field.e_theta * cos_theta * from_phi.cos() - field.e_phi * from_phi.sin();
+    let ey = field.e_theta * cos_theta * from_phi.sin() + field.e_phi * from_phi.cos();
+    FarField {
+        e_theta: (ex * to_phi.cos() + ey * to_phi.sin()) * cos_theta,
+        e_phi: -ex * to_phi.sin() + ey * to_phi.cos(),
+    }
+}
+
+/// Move a sample with negative theta to the equivalent positive theta
+///
+/// (-theta, phi) is the same direction as (theta, phi + PI), but the theta
+/// and phi unit vectors both flip sign between the two.
+fn normalize_direction(theta: f64, phi: f64, field: FarField) -> Sample {
+    let (theta, phi, field) = if theta < 0.0 {
+        (-theta, phi + PI, FarField { e_theta: -field.e_theta, e_phi: -field.e_phi })
+    } else {
+        (theta, phi, field)
+    };
+    Sample { theta, phi: phi.rem_euclid(2.0 * PI), field }
+}
+
+/// A sample given as Ludwig-3 co/cross components, possibly at negative theta
+///
+/// Unlike theta/phi components, Ludwig-3 components don't flip through the
+/// pole.
+fn ludwig3_sample(theta: f64, phi: f64, co: Complex<f64>, cross: Complex<f64>) -> Sample {
+    let (theta, phi) = if theta < 0.0 { (-theta, phi + PI) } else { (theta, phi) };
+    let phi = phi.rem_euclid(2.0 * PI);
+    Sample { theta, phi, field: FarField::from_ludwig3(co, cross, phi) }
+}
+
+impl DataElement {
+    /// Parse the contents of a TICRA GRASP `.cut` file
+    ///
+    /// Polar (ICUT = 1) and conical (ICUT = 2) cuts are supported, with the
+    /// field components given as theta/phi (ICOMP = 1), RHC/LHC (ICOMP = 2)
+    /// or Ludwig-3 co/cross with an x-polarized reference (ICOMP = 3). Any
+    /// components after the first two are ignored. The element sits at the
+    /// origin.
+    ///
+    pub fn from_grasp_cut_str(contents: &str) -> Result<DataElement, DataFileError> {
+        let mut lines = contents.lines().enumerate().map(|(idx, line)| (idx + 1, line));
+        let mut samples = Vec::new();
+
+        // each cut is a text header, a line of parameters and then the samples
+        while let Some((_, header)) = lines.next() {
+            if header.trim().is_empty() {
+                continue;
+            }
+            let (line_number, params) = lines.next().ok_or(DataFileError::Parse {
+                line: 0,
+                message: "cut header without parameters".to_string(),
+            })?;
+            let params = parse_numbers(params, line_number)?;
+            if params.len() < 7 {
+                return Err(DataFileError::Parse {
+                    line: line_number,
+                    message: "expected V_INI V_INC V_NUM C ICOMP ICUT NCOMP".to_string(),
+                });
+            }
+            let v_ini = parse_angle(params[0], line_number)?;
+            let v_inc = parse_angle(params[1], line_number)?;
+            let v_num = params[2] as usize;
+            let c = parse_angle(params[3], line_number)?;
+            let icomp = params[4] as usize;
+            let icut = params[5] as usize;
+
+            for idx in 0..v_num {
+                let (line_number, line) = lines.next().ok_or(DataFileError::Parse {
+                    line: line_number + idx + 1,
+                    message: "file ended in the middle of a cut".to_string(),
+                })?;
+                let values = parse_numbers(line, line_number)?;
+                if values.len() < 4 {
+                    return Err(DataFileError::Parse {
+                        line: line_number,
+                        message: "expected two complex field components".to_string(),
+                    });
+                }
+                let first = Complex::new(values[0], values[1]);
+                let second = Complex::new(values[2], values[3]);
+
+                let v = v_ini + idx as f64 * v_inc;
+                let (theta, phi) = match icut {
+                    1 => (v, c),
+                    2 => (c, v),
+                    _ => {
+                        return Err(DataFileError::Parse {
+                            line: line_number,
+                            message: format!("unsupported ICUT {}", icut),
+                        })
+                    }
+                };
+
+                let sample = match icomp {
+                    1 => normalize_direction(theta, phi, FarField { e_theta: first, e_phi: second }),
+                    2 => {
+                        let e_theta = (first + second) / 2_f64.sqrt();
+                        let e_phi = Complex::new(0.0, 1.0) * (first - second) / 2_f64.sqrt();
+                        normalize_direction(theta, phi, FarField { e_theta, e_phi })
+                    }
+                    3 => ludwig3_sample(theta, phi, first, second),
+                    _ => {
+                        return Err(DataFileError::Parse {
+                            line: line_number,
+                            message: format!("unsupported ICOMP {}", icomp),
+                        })
+                    }
+                };
+                samples.push(sample);
+            }
+        }
+
+        let (co, cross) = grid_samples(&samples)?;
+        Ok(DataElement::new_polarized(co, cross, Point::default()))
+    }
+
+    /// Parse the contents of a CST far-field ASCII export
+    ///
+    /// The columns must be theta, phi, total gain/directivity, then the
+    /// magnitude and phase of the theta and of the phi components, as CST
+    /// writes them. Angles must be in degrees; magnitudes may be in dB
+    /// (dBi, dBV/m, ...) or linear. The element sits at `position`.
+    ///
+    pub fn from_cst_str(contents: &str, position: Point) -> Result<DataElement, DataFileError> {
+        let mut lines = contents.lines().enumerate().map(|(idx, line)| (idx + 1, line));
+
+        let header: String = lines
+            .next()
+            .map(|(_, line)| line.split_whitespace().collect())
+            .unwrap_or_default();
+        let header_error = |message: &str| DataFileError::Parse { line: 1, message: message.to_string() };
+        if !header.starts_with("Theta[deg.]Phi[deg.]Abs(")
+            || !header.contains("Phase(Theta)[deg.]")
+            || !header.contains("Phase(Phi)[deg.]")
+        {
+            return Err(header_error("expected a CST far-field header with angles in degrees"));
+        }
+        let unit = header
+            .split("Abs(Theta)[")
+            .nth(1)
+            .and_then(|rest| rest.split(']').next())
+            .ok_or_else(|| header_error("no Abs(Theta) column"))?;
+        let in_db = if unit.starts_with("dB") {
+            true
+        } else if unit.is_empty() || unit == "V/m" || unit == "A/m" {
+            false
+        } else {
+            return Err(header_error(&format!("unsupported magnitude unit '{}'", unit)));
+        };
+
+        let mut samples = Vec::new();
+        for (line_number, line) in lines {
+            // the header is underlined with dashes
+            if line.trim().is_empty() || line.trim_start().starts_with('-') {
+                continue;
+            }
+            let values = parse_numbers(line, line_number)?;
+            if values.len() < 7 {
+                return Err(DataFileError::Parse {
+                    line: line_number,
+                    message: "expected at least 7 columns".to_string(),
+                });
+            }
+            let component = |magnitude: f64, phase: f64| {
+                let magnitude = if in_db { 10_f64.powf(magnitude / 20.0) } else { magnitude };
+                Complex::from_polar(magnitude, phase.to_radians())
+            };
+            let field = FarField {
+                e_theta: component(values[3], values[4]),
+                e_phi: component(values[5], values[6]),
+            };
+            let (theta, phi) = (parse_angle(values[0], line_number)?, parse_angle(values[1], line_number)?);
+            samples.push(normalize_direction(theta, phi, field));
+        }
+
+        let (co, cross) = grid_samples(&samples)?;
+        Ok(DataElement::new_polarized(co, cross, position))
+    }
+
+    /// Parse the contents of an NSI far-field ASCII export
+    ///
+    /// Lines up to the first one starting with a number are the header and
+    /// are skipped. Every line after that is theta and phi (degrees), then
+    /// the amplitude (dB) and phase (degrees) of the co-polar and then of the
+    /// cross-polar component, Ludwig-3 with the co-polar reference along x.
+    /// Theta may run negative, e.g. -180 to 180 degrees for a full cut. The
+    /// element sits at the origin.
+    ///
+    pub fn from_nsi_str(contents: &str) -> Result<DataElement, DataFileError> {
+        let starts_with_number = |line: &str| {
+            line.split_whitespace().next().is_some_and(|token| token.parse::<f64>().is_ok())
+        };
+
+        let mut samples = Vec::new();
+        let rows = contents
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| (idx + 1, line))
+            .skip_while(|(_, line)| !starts_with_number(line));
+        for (line_number, line) in rows {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let values = parse_numbers(line, line_number)?;
+            if values.len() < 6 {
+                return Err(DataFileError::Parse {
+                    line: line_number,
+                    message: "expected theta, phi and co/cross amplitude (dB) and phase (deg)".to_string(),
+                });
+            }
+            let component = |amplitude_db: f64, phase: f64| Complex::from_polar(10_f64.powf(amplitude_db / 20.0), phase.to_radians());
+            let (co, cross) = (component(values[2], values[3]), component(values[4], values[5]));
+            let (theta, phi) = (parse_angle(values[0], line_number)?, parse_angle(values[1], line_number)?);
+            samples.push(ludwig3_sample(theta, phi, co, cross));
+        }
+
+        let (co, cross) = grid_samples(&samples)?;
+        Ok(DataElement::new_polarized(co, cross, Point::default()))
+    }
+}
@@ -0,0 +1,92 @@
+//! Portable pattern datasets
+//!
+//! A `PatternGrid` on its own doesn't say what frequency it was computed at
+//! or where it came from. `PatternDataset` bundles the samples with their
+//! axes, the frequency and free-form metadata so patterns can be exchanged
+//! without HDF5. With the `serde` feature it works with any serde format;
+//! the `json` feature adds JSON files in `io`.
+
+use std::collections::BTreeMap;
+
+use num::complex::Complex;
+
+use crate::{PatternGrid, PI};
+
+/// A sampled pattern with its angle axes, frequency and metadata
+///
+/// The samples are laid out like `PatternGrid`, one phi row at a time.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PatternDataset {
+    // frequency the pattern was computed/measured at (Hz)
+    frequency: f64,
+    // theta of each column (radians)
+    theta: Vec<f64>,
+    // phi of each row (radians)
+    phi: Vec<f64>,
+    // values[phi_idx][theta_idx]
+    values: Vec<Vec<Complex<f64>>>,
+    // free-form key/value pairs (source, units, author, ...)
+    metadata: BTreeMap<String, String>,
+}
+
+impl PatternDataset {
+    /// Bundle `grid`, sampled at `frequency` (Hz), with empty metadata
+    pub fn new(grid: &PatternGrid, frequency: f64) -> PatternDataset {
+        PatternDataset {
+            frequency,
+            theta: (0..grid.num_theta()).map(|idx| grid.theta(idx)).collect(),
+            phi: (0..grid.num_phi()).map(|idx| grid.phi(idx)).collect(),
+            values: grid.values().to_vec(),
+            metadata: BTreeMap::new(),
+        }
+    }
+
+    /// Frequency of the pattern (Hz)
+    pub fn frequency(&self) -> f64 {
+        self.frequency
+    }
+
+    /// Theta of each sample column (radians)
+    pub fn theta(&self) -> &[f64] {
+        &self.theta
+    }
+
+    /// Phi of each sample row (radians)
+    pub fn phi(&self) -> &[f64] {
+        &self.phi
+    }
+
+    /// All samples, one phi row at a time
+    pub fn values(&self) -> &[Vec<Complex<f64>>] {
+        &self.values
+    }
+
+    /// Metadata attached to the pattern
+    pub fn metadata(&self) -> &BTreeMap<String, String> {
+        &self.metadata
+    }
+
+    /// Mutable access to the metadata attached to the pattern
+    pub fn metadata_mut(&mut self) -> &mut BTreeMap<String, String> {
+        &mut self.metadata
+    }
+
+    /// The samples as a `PatternGrid`
+    ///
+    /// The steps are taken from the first two entries of each axis. An axis
+    /// with a single entry is taken to span the whole range.
+    ///
+    pub fn grid(&self) -> PatternGrid {
+        let step = |axis: &[f64], whole: f64| if axis.len() > 1 { axis[1] - axis[0] } else { whole };
+        PatternGrid::from_values(step(&self.theta, PI), step(&self.phi, 2.0 * PI), self.values.clone())
+    }
+
+    /// Whether there is one row per phi and one sample per theta in every row
+    #[cfg(feature = "json")]
+    pub(crate) fn is_consistent(&self) -> bool {
+        !self.theta.is_empty()
+            && self.values.len() == self.phi.len()
+            && self.values.iter().all(|row| row.len() == self.theta.len())
+    }
+}
@@ -0,0 +1,130 @@
+//! Dual-polarized elements
+//!
+//! A dual-polarized element has two ports, each with its own polarized
+//! pattern. Driving the ports with different weights synthesizes any
+//! polarization, e.g. two orthogonal linear ports in quadrature give
+//! circular polarization.
+
+use num::complex::Complex;
+
+use crate::{element_field, element_gain, ElementIface, FarField, GainIface, PatternGrid, Point};
+
+/// An element with two independently weighted ports
+///
+/// Each port's pattern is a pair of Ludwig-3 (co, cross) tables at the
+/// phase center, as for `DataElement`. The gain of the element is the
+/// co-polar component of the combined field.
+#[derive(Clone)]
+pub struct DualPolElement {
+    // position of the element's phase center in space
+    position: Point,
+    // Ludwig-3 (co, cross) tables for each port
+    ports: [(PatternGrid, PatternGrid); 2],
+    // weight applied to each port
+    port_weights: [Complex<f64>; 2],
+    // Weight applied to the element as a whole
+    weight: Complex<f64>,
+    // time delay in the feed (seconds)
+    delay: f64,
+}
+
+impl DualPolElement {
+    /// Create an element from the Ludwig-3 (co, cross) tables of its two ports
+    ///
+    /// Both ports start with a weight of 1.
+    ///
+    pub fn new(port_a: (PatternGrid, PatternGrid), port_b: (PatternGrid, PatternGrid), position: Point) -> DualPolElement {
+        DualPolElement {
+            position,
+            ports: [port_a, port_b],
+            port_weights: [Complex::new(1.0, 0.0); 2],
+            weight: Complex::new(1.0, 0.0),
+            delay: 0.0,
+        }
+    }
+
+    /// Weights applied to the two ports
+    pub fn port_weights(&self) -> [Complex<f64>; 2] {
+        self.port_weights
+    }
+
+    /// Replace the weights applied to the two ports
+    pub fn set_port_weights(&mut self, port_weights: [Complex<f64>; 2]) {
+        self.port_weights = port_weights;
+    }
+
+    /// Combined far field of both ports at the element's phase center, without the element weight
+    fn port_field(&self, theta: f64, phi: f64) -> FarField {
+        let (co, cross) = self.ports
+            .iter()
+            .zip(&self.port_weights)
+            .map(|((co, cross), weight)| (co.interpolate(theta, phi) * weight, cross.interpolate(theta, phi) * weight))
+            .fold((Complex::new(0.0, 0.0), Complex::new(0.0, 0.0)), |a, b| (a.0 + b.0, a.1 + b.1));
+        FarField::from_ludwig3(co, cross, phi)
+    }
+
+    /// Port-to-port isolation estimated from the overlap of the port patterns (dB)
+    ///
+    /// The isolation is `-10 * log10(|<a, b>|^2 / (|a|^2 |b|^2))` for the
+    /// Ludwig-3 fields `a` and `b` of the two ports in the direction
+    /// `(theta, phi)`, ignoring the port weights. Orthogonal polarizations
+    /// give infinity and identical ones 0 dB. Where either port has no
+    /// pattern in that direction (e.g. behind the ground plane) nothing couples and
+    /// the isolation is also infinity. The tables only hold one frequency,
+    /// so `frequency` doesn't change the result.
+    ///
+    pub fn isolation(&self, _frequency: f64, theta: f64, phi: f64) -> f64 {
+        let [a, b] = self.ports.each_ref().map(|(co, cross)| (co.interpolate(theta, phi), cross.interpolate(theta, phi)));
+        let overlap = (a.0 * b.0.conj() + a.1 * b.1.conj()).norm_sqr();
+        let power = (a.0.norm_sqr() + a.1.norm_sqr()) * (b.0.norm_sqr() + b.1.norm_sqr());
+        if power == 0.0 {
+            return f64::INFINITY;
+        }
+        -10.0 * (overlap / power).log10()
+    }
+
+    /// Polarized far field of the element at its position, including all weights
+    pub fn get_field(&self, frequency: f64, theta: f64, phi: f64) -> Option<FarField> {
+        element_field(self, frequency, theta, phi)
+    }
+}
+
+impl GainIface for DualPolElement {
+    fn get_gain(&self, frequency: f64, theta: f64, phi: f64) -> Option<Complex<f64>> {
+        element_gain(self, frequency, theta, phi)
+    }
+}
+
+impl ElementIface for DualPolElement {
+    fn position(&self) -> &Point {
+        &self.position
+    }
+
+    fn set_position(&mut self, position: Point) {
+        self.position = position;
+    }
+
+    fn weight(&self) -> Complex<f64> {
+        self.weight
+    }
+
+    fn set_weight(&mut self, weight: Complex<f64>) {
+        self.weight = weight;
+    }
+
+    fn delay(&self) -> f64 {
+        self.delay
+    }
+
+    fn set_delay(&mut self, delay: f64) {
+        self.delay = delay;
+    }
+
+    fn pattern(&self, _frequency: f64, theta: f64, phi: f64) -> Option<Complex<f64>> {
+        Some( self.port_field(theta, phi).ludwig3(phi).0 )
+    }
+
+    fn pattern_field(&self, _frequency: f64, theta: f64, phi: f64) -> Option<FarField> {
+        Some( self.port_field(theta, phi) )
+    }
+}
@@ -0,0 +1,24 @@
+//! Effective isotropic radiated power
+//!
+//! EIRP is the transmit power plus the realized gain in each direction. It
+//! is what regulatory masks and coverage budgets are written against.
+
+use crate::{ElementArray, GainIface, PatternGridDb};
+
+/// How far below the peak the EIRP map is clamped (dB)
+const EIRP_DYNAMIC_RANGE_DB: f64 = -100.0;
+
+impl ElementArray {
+    /// EIRP over the sphere (dBm)
+    ///
+    /// The gain at each sample is the directivity of the sampled pattern
+    /// less the feed loss (see `set_feed_loss_db`), so the peak is
+    /// `tx_power_dbm + peak_gain_dbi - feed_loss_db`. Nulls are clamped
+    /// 100 dB below the peak.
+    ///
+    pub fn eirp_map(&self, tx_power_dbm: f64, frequency: f64, theta_step: f64, phi_step: f64) -> PatternGridDb {
+        let grid = self.sample_sphere(frequency, theta_step, phi_step);
+        let peak_eirp = tx_power_dbm + grid.peak_gain_dbi() - self.feed_loss_db();
+        grid.normalize_db(EIRP_DYNAMIC_RANGE_DB).offset(peak_eirp)
+    }
+}
@@ -0,0 +1,50 @@
+//! Endfire evaluation
+//!
+//! Endfire arrays radiate along their own axis. Working out that direction
+//! by hand invites small errors: `cos(PI / 2)` isn't exactly zero, and an
+//! axis along z lands on a pole where phi is undefined.
+
+use num::complex::Complex;
+
+use crate::{ElementArray, GainIface, PI};
+
+/// Direction cosines closer than this to 0 or 1 are snapped to the exact value
+const SNAP: f64 = 1e-12;
+
+impl ElementArray {
+    /// (theta, phi) of the array axis, pointing from the first to the last element
+    ///
+    /// For a linear array this is the endfire direction. An axis in the XY
+    /// plane gets exactly `theta = PI / 2`, and an axis along z gets exactly
+    /// `theta = 0` or `PI` with `phi = 0`. Returns `None` with fewer than two
+    /// elements or when the first and last element coincide.
+    ///
+    pub fn endfire_direction(&self) -> Option<(f64, f64)> {
+        let first = self.elements.first()?.position();
+        let last = self.elements.last()?.position();
+        let (dx, dy, dz) = (last.x - first.x, last.y - first.y, last.z - first.z);
+        let length = (dx * dx + dy * dy + dz * dz).sqrt();
+        if length == 0.0 {
+            return None;
+        }
+
+        let uz = dz / length;
+        if uz.abs() > 1.0 - SNAP {
+            return Some( (if uz > 0.0 { 0.0 } else { PI }, 0.0) );
+        }
+        let theta = if uz.abs() < SNAP { PI / 2.0 } else { uz.acos() };
+        let phi = dy.atan2(dx).rem_euclid(2.0 * PI);
+        Some( (theta, phi) )
+    }
+
+    /// Complex gain along the array axis (see `endfire_direction`)
+    ///
+    /// With endfire phasing (e.g. `steer` to the endfire direction) the
+    /// element contributions add coherently, so a uniform array of isotropic
+    /// elements gives the number of elements.
+    ///
+    pub fn endfire_gain(&self, frequency: f64) -> Option<Complex<f64>> {
+        let (theta, phi) = self.endfire_direction()?;
+        self.get_gain(frequency, theta, phi)
+    }
+}
@@ -0,0 +1,115 @@
+//! Random element failures
+//!
+//! Large arrays are expected to keep working with a few dead elements. A
+//! failure study turns random elements off over many trials and reports how
+//! much the main beam and the sidelobes suffer.
+
+use num::complex::Complex;
+
+use crate::rng::Rng;
+use crate::{ElementArray, PatternGrid, PI};
+
+/// Sphere sampling used by the failure study (radians)
+const STUDY_STEP: f64 = 2.0 * PI / 180.0;
+
+/// Degradation from random element failures, relative to the healthy array
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FailureStats {
+    /// Mean drop in gain at the healthy beam peak (dB)
+    pub mean_gain_loss_db: f64,
+    /// Largest drop in gain at the healthy beam peak (dB)
+    pub worst_gain_loss_db: f64,
+    /// Mean rise of the peak sidelobe relative to the beam (dB)
+    pub mean_sidelobe_rise_db: f64,
+    /// Largest rise of the peak sidelobe relative to the beam (dB)
+    pub worst_sidelobe_rise_db: f64,
+    /// Trials left with no gain at all at the healthy beam peak, e.g. because every element failed
+    ///
+    /// Their losses are infinite, so they're counted here and left out of
+    /// the other statistics.
+    pub lost_beam_trials: usize,
+}
+
+/// Power of the beam sample and of the largest sample outside the main lobe
+fn beam_and_sidelobe(power: &[f64], peak: usize, main_lobe: &[bool]) -> (f64, f64) {
+    let sidelobe = power
+        .iter()
+        .zip(main_lobe)
+        .filter(|(_, in_lobe)| !**in_lobe)
+        .map(|(p, _)| *p)
+        .fold(0.0, f64::max);
+    (power[peak], sidelobe)
+}
+
+impl ElementArray {
+    /// Monte Carlo study of random element failures
+    ///
+    /// In each of `trials` trials every element fails (stops radiating) with
+    /// probability `failure_prob`. The patterns are sampled on a 2 degree
+    /// grid, and the main lobe and beam direction are taken from the healthy
+    /// array. The same `seed` always gives the same result. The element
+    /// weights are left as they were. Trials that lose the beam completely
+    /// are only counted in `lost_beam_trials`. With no trials, no elements
+    /// or no surviving trial every other statistic is zero.
+    ///
+    pub fn failure_study(&self, frequency: f64, failure_prob: f64, trials: usize, seed: u64) -> FailureStats {
+        // every element's contribution at every sample, so each trial is just a partial sum
+        let count = self.elements.len();
+        if count == 0 || trials == 0 {
+            return FailureStats::default();
+        }
+        let mut contributions: Vec<Complex<f64>> = Vec::new();
+        let healthy = PatternGrid::from_fn(STUDY_STEP, STUDY_STEP, |theta, phi| {
+            let gains = self.element_contributions(frequency, theta, phi);
+            let total = gains.iter().sum();
+            contributions.extend(gains);
+            total
+        });
+
+        let (peak_phi, peak_theta) = healthy.peak_index();
+        let peak = peak_phi * healthy.num_theta() + peak_theta;
+        let main_lobe: Vec<bool> = healthy.main_lobe_mask().into_iter().flatten().collect();
+        let power: Vec<f64> = healthy.values().iter().flatten().map(|value| value.norm_sqr()).collect();
+        let (healthy_beam, healthy_sidelobe) = beam_and_sidelobe(&power, peak, &main_lobe);
+
+        let mut rng = Rng::new(seed);
+        let mut gain_loss = Vec::with_capacity(trials);
+        let mut sidelobe_rise = Vec::with_capacity(trials);
+        let mut lost_beam_trials = 0;
+        for _ in 0..trials {
+            let alive: Vec<bool> = (0..count).map(|_| rng.uniform() >= failure_prob).collect();
+            let power: Vec<f64> = contributions
+                .chunks(count)
+                .map(|gains| {
+                    gains.iter()
+                        .zip(&alive)
+                        .filter(|(_, alive)| **alive)
+                        .map(|(gain, _)| gain)
+                        .sum::<Complex<f64>>()
+                        .norm_sqr()
+                })
+                .collect();
+            let (beam, sidelobe) = beam_and_sidelobe(&power, peak, &main_lobe);
+            if beam == 0.0 {
+                lost_beam_trials += 1;
+                continue;
+            }
+
+            gain_loss.push(10.0 * (healthy_beam / beam).log10());
+            sidelobe_rise.push(10.0 * (sidelobe / beam).log10() - 10.0 * (healthy_sidelobe / healthy_beam).log10());
+        }
+
+        if gain_loss.is_empty() {
+            return FailureStats { lost_beam_trials, ..FailureStats::default() };
+        }
+        let mean = |values: &[f64]| values.iter().sum::<f64>() / values.len() as f64;
+        let worst = |values: &[f64]| values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        FailureStats {
+            mean_gain_loss_db: mean(&gain_loss),
+            worst_gain_loss_db: worst(&gain_loss),
+            mean_sidelobe_rise_db: mean(&sidelobe_rise),
+            worst_sidelobe_rise_db: worst(&sidelobe_rise),
+            lost_beam_trials,
+        }
+    }
+}
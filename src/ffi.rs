@@ -0,0 +1,102 @@
+//! C interface
+//!
+//! A handful of `extern "C"` functions so the library can be driven from C
+//! or Python (ctypes). Arrays live behind an opaque handle that the caller
+//! creates, uses and destroys. No panic is allowed to unwind across the
+//! boundary; it is caught and reported as `APG_PANIC`.
+//!
+//! Build a shared library with
+//! `cargo rustc --release --features ffi --crate-type cdylib`. The matching
+//! declarations are in `include/apg.h`.
+
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::{ArrayIface, ElementArray, ElementIface, OmniElementBuilder, Point};
+
+/// The call succeeded
+pub const APG_OK: i32 = 0;
+/// A handle or output pointer was null
+pub const APG_NULL_POINTER: i32 = -1;
+/// The array has no gain in the requested direction
+pub const APG_NO_GAIN: i32 = -2;
+/// The library panicked; the outputs weren't written
+pub const APG_PANIC: i32 = -3;
+
+/// Opaque handle to an array
+pub struct ApgArray {
+    array: Box<dyn ArrayIface>,
+}
+
+impl ApgArray {
+    /// Hand any array over to C
+    ///
+    /// The handle has to be released with `apg_array_destroy`.
+    ///
+    pub fn into_handle(array: Box<dyn ArrayIface>) -> *mut ApgArray {
+        Box::into_raw(Box::new(ApgArray { array }))
+    }
+}
+
+/// Create a linear array of `num` isotropic elements along x, `spacing` meters apart
+///
+/// Returns null if the array couldn't be created.
+///
+#[no_mangle]
+pub extern "C" fn apg_uniform_linear_create(num: usize, spacing: f64) -> *mut ApgArray {
+    let array = panic::catch_unwind(|| {
+        ElementArray::uniform_linear(num, spacing, |position: Point| -> Box<dyn ElementIface> {
+            Box::new(OmniElementBuilder::default().position(position).gain(1.0).build().unwrap())
+        })
+    });
+    match array {
+        Ok(array) => ApgArray::into_handle(Box::new(array)),
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+/// Release an array handle
+///
+/// # Safety
+///
+/// `handle` must be null or a handle from this library that hasn't been
+/// destroyed yet.
+///
+#[no_mangle]
+pub unsafe extern "C" fn apg_array_destroy(handle: *mut ApgArray) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
+}
+
+/// Complex gain of the array, written to `out_re` and `out_im`
+///
+/// Returns `APG_OK` on success or one of the negative error codes.
+///
+/// # Safety
+///
+/// `handle` must be null or a live handle from this library, and `out_re`
+/// and `out_im` must be null or valid for writing an `f64`.
+///
+#[no_mangle]
+pub unsafe extern "C" fn apg_array_gain(
+    handle: *const ApgArray,
+    frequency: f64,
+    theta: f64,
+    phi: f64,
+    out_re: *mut f64,
+    out_im: *mut f64,
+) -> i32 {
+    if handle.is_null() || out_re.is_null() || out_im.is_null() {
+        return APG_NULL_POINTER;
+    }
+    let array = &(*handle).array;
+    match panic::catch_unwind(AssertUnwindSafe(|| array.get_gain(frequency, theta, phi))) {
+        Ok(Some(gain)) => {
+            *out_re = gain.re;
+            *out_im = gain.im;
+            APG_OK
+        }
+        Ok(None) => APG_NO_GAIN,
+        Err(_) => APG_PANIC,
+    }
+}
@@ -0,0 +1,78 @@
+//! Polarized far fields
+//!
+//! Most of the crate works with a single complex gain. Where polarization
+//! matters, the far field is split into its two transverse components.
+
+use num::complex::Complex;
+
+use crate::float;
+
+/// Far field in a single direction, as theta and phi components
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FarField {
+    /// Component along the theta unit vector
+    pub e_theta: Complex<f64>,
+    /// Component along the phi unit vector
+    pub e_phi: Complex<f64>,
+}
+
+impl FarField {
+    /// Build a far field from Ludwig-3 co/cross components
+    ///
+    /// The co-polar reference is the x axis, so at boresight co-pol is
+    /// x-polarized and cross-pol is y-polarized.
+    ///
+    pub fn from_ludwig3(co: Complex<f64>, cross: Complex<f64>, phi: f64) -> FarField {
+        FarField {
+            e_theta: co * float::cos(phi) + cross * float::sin(phi),
+            e_phi: -co * float::sin(phi) + cross * float::cos(phi),
+        }
+    }
+
+    /// Ludwig-3 co/cross components of the far field at this phi
+    pub fn ludwig3(&self, phi: f64) -> (Complex<f64>, Complex<f64>) {
+        (
+            self.e_theta * float::cos(phi) - self.e_phi * float::sin(phi),
+            self.e_theta * float::sin(phi) + self.e_phi * float::cos(phi),
+        )
+    }
+
+    /// Right and left hand circular components, `(rhc, lhc)`
+    ///
+    /// The inverse of `e_theta = (rhc + lhc) / sqrt(2)` and
+    /// `e_phi = j * (rhc - lhc) / sqrt(2)`.
+    ///
+    pub fn circular(&self) -> (Complex<f64>, Complex<f64>) {
+        let j = Complex::new(0.0, 1.0);
+        (
+            (self.e_theta - j * self.e_phi) / float::sqrt(2.0),
+            (self.e_theta + j * self.e_phi) / float::sqrt(2.0),
+        )
+    }
+}
+
+/// Gain in a single direction in the representations usually wanted together
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct GainResult {
+    /// Complex gain (field)
+    pub field: Complex<f64>,
+    /// `|field|`
+    pub magnitude: f64,
+    /// `|field|^2`
+    pub power: f64,
+    /// `10 * log10(power)`
+    pub db: f64,
+}
+
+impl GainResult {
+    /// All representations of a complex gain
+    pub fn new(field: Complex<f64>) -> GainResult {
+        let power = field.norm_sqr();
+        GainResult {
+            field,
+            magnitude: float::sqrt(power),
+            power,
+            db: 10.0 * float::log10(power),
+        }
+    }
+}
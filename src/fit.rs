@@ -0,0 +1,44 @@
+//! Fitting analytic beam models to sampled patterns
+//!
+//! Measured patterns are often summarized by a simple model that is easy to
+//! reuse in link budgets and quick studies.
+
+use crate::{PatternGrid, PI};
+
+/// Best-fit `(n, peak)` for the model `|gain| = peak * cos(theta)^n`
+///
+/// The fit is a least squares line through `ln|gain|` against
+/// `ln(cos(theta))`, using the forward hemisphere samples within 40 dB of
+/// the strongest one (the model has nothing to say about the back lobe or
+/// deep nulls). Returns NaN for both if there aren't at least two usable
+/// theta values.
+///
+pub fn fit_cos_n(grid: &PatternGrid) -> (f64, f64) {
+    let strongest = grid.values().iter().flatten().map(|value| value.norm()).fold(0.0, f64::max);
+
+    let points: Vec<(f64, f64)> = grid.values()
+        .iter()
+        .flat_map(|row| row.iter().enumerate())
+        .filter_map(|(theta_idx, value)| {
+            let theta = grid.theta(theta_idx);
+            let magnitude = value.norm();
+            if theta < PI / 2.0 && magnitude >= 0.01 * strongest && magnitude.is_finite() {
+                Some((theta.cos().ln(), magnitude.ln()))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    let count = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / count;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / count;
+    let sxx: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    let sxy: f64 = points.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    if sxx <= 0.0 {
+        return (f64::NAN, f64::NAN);
+    }
+
+    let n = sxy / sxx;
+    (n, (mean_y - n * mean_x).exp())
+}
@@ -0,0 +1,83 @@
+//! Transcendental functions for the gain math
+//!
+//! The element and array gain calculations call these instead of the `f64`
+//! methods. With the `libm` feature they come from the `libm` crate, which
+//! doesn't need the platform's C library, e.g. on embedded DSP targets, and
+//! they're the only floating point functions the `no_std` build can use.
+
+use num::complex::Complex;
+
+#[cfg(not(feature = "libm"))]
+pub(crate) fn sin(x: f64) -> f64 {
+    x.sin()
+}
+
+#[cfg(not(feature = "libm"))]
+pub(crate) fn cos(x: f64) -> f64 {
+    x.cos()
+}
+
+#[cfg(not(feature = "libm"))]
+pub(crate) fn sqrt(x: f64) -> f64 {
+    x.sqrt()
+}
+
+#[cfg(not(feature = "libm"))]
+pub(crate) fn powf(x: f64, y: f64) -> f64 {
+    x.powf(y)
+}
+
+#[cfg(not(feature = "libm"))]
+pub(crate) fn exp(x: f64) -> f64 {
+    x.exp()
+}
+
+#[cfg(not(feature = "libm"))]
+pub(crate) fn log10(x: f64) -> f64 {
+    x.log10()
+}
+
+#[cfg(not(feature = "libm"))]
+pub(crate) fn round(x: f64) -> f64 {
+    x.round()
+}
+
+#[cfg(feature = "libm")]
+pub(crate) fn sin(x: f64) -> f64 {
+    libm::sin(x)
+}
+
+#[cfg(feature = "libm")]
+pub(crate) fn cos(x: f64) -> f64 {
+    libm::cos(x)
+}
+
+#[cfg(feature = "libm")]
+pub(crate) fn sqrt(x: f64) -> f64 {
+    libm::sqrt(x)
+}
+
+#[cfg(feature = "libm")]
+pub(crate) fn powf(x: f64, y: f64) -> f64 {
+    libm::pow(x, y)
+}
+
+#[cfg(feature = "libm")]
+pub(crate) fn exp(x: f64) -> f64 {
+    libm::exp(x)
+}
+
+#[cfg(feature = "libm")]
+pub(crate) fn log10(x: f64) -> f64 {
+    libm::log10(x)
+}
+
+#[cfg(feature = "libm")]
+pub(crate) fn round(x: f64) -> f64 {
+    libm::round(x)
+}
+
+/// `exp(j * phase)`
+pub(crate) fn cis(phase: f64) -> Complex<f64> {
+    Complex::new(cos(phase), sin(phase))
+}
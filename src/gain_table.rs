@@ -0,0 +1,105 @@
+//! Frequency dependent element gain
+//!
+//! Analytic element patterns get the angular shape right but not how the
+//! gain of a real element rolls off across its band. A measured table of
+//! gain against frequency can be laid on top of any element instead of
+//! replacing it with a full `DataElement`.
+
+use alloc::{boxed::Box, vec::Vec};
+
+use num::complex::Complex;
+
+use crate::{element_gain, ElementIface, FarField, GainIface, Point};
+
+/// An element whose pattern is scaled by a gain-vs-frequency table
+///
+/// The table holds `(frequency, gain)` pairs, with the gain an amplitude
+/// factor like `OmniElement`'s. It is linearly interpolated at the
+/// evaluation frequency and held at its end values outside the tabulated
+/// band. Position, weight and delay are those of the wrapped element.
+#[derive(Clone)]
+pub struct GainTableElement {
+    // element providing the angular pattern
+    element: Box<dyn ElementIface>,
+    // (frequency (Hz), amplitude gain), sorted by frequency
+    table: Vec<(f64, f64)>,
+}
+
+impl GainTableElement {
+    /// Scale the pattern of `element` by `table`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `table` is empty or its frequencies aren't strictly increasing.
+    ///
+    pub fn new(element: Box<dyn ElementIface>, table: Vec<(f64, f64)>) -> GainTableElement {
+        assert!(!table.is_empty(), "gain table needs at least one frequency");
+        assert!(table.windows(2).all(|pair| pair[0].0 < pair[1].0), "gain table frequencies must be increasing");
+        GainTableElement { element, table }
+    }
+
+    /// The wrapped element
+    pub fn element(&self) -> &dyn ElementIface {
+        self.element.as_ref()
+    }
+
+    /// The `(frequency, gain)` table
+    pub fn table(&self) -> &[(f64, f64)] {
+        &self.table
+    }
+
+    /// Amplitude gain interpolated from the table at `frequency`
+    pub fn gain_at(&self, frequency: f64) -> f64 {
+        let idx = self.table.partition_point(|(f, _)| *f <= frequency);
+        if idx == 0 {
+            return self.table[0].1;
+        }
+        if idx == self.table.len() {
+            return self.table[idx - 1].1;
+        }
+        let ((f0, g0), (f1, g1)) = (self.table[idx - 1], self.table[idx]);
+        g0 + (g1 - g0) * (frequency - f0) / (f1 - f0)
+    }
+}
+
+impl GainIface for GainTableElement {
+    fn get_gain(&self, frequency: f64, theta: f64, phi: f64) -> Option<Complex<f64>> {
+        element_gain(self, frequency, theta, phi)
+    }
+}
+
+impl ElementIface for GainTableElement {
+    fn position(&self) -> &Point {
+        self.element.position()
+    }
+
+    fn set_position(&mut self, position: Point) {
+        self.element.set_position(position);
+    }
+
+    fn weight(&self) -> Complex<f64> {
+        self.element.weight()
+    }
+
+    fn set_weight(&mut self, weight: Complex<f64>) {
+        self.element.set_weight(weight);
+    }
+
+    fn delay(&self) -> f64 {
+        self.element.delay()
+    }
+
+    fn set_delay(&mut self, delay: f64) {
+        self.element.set_delay(delay);
+    }
+
+    fn pattern(&self, frequency: f64, theta: f64, phi: f64) -> Option<Complex<f64>> {
+        Some( self.element.pattern(frequency, theta, phi)? * self.gain_at(frequency) )
+    }
+
+    fn pattern_field(&self, frequency: f64, theta: f64, phi: f64) -> Option<FarField> {
+        let field = self.element.pattern_field(frequency, theta, phi)?;
+        let gain = self.gain_at(frequency);
+        Some( FarField { e_theta: field.e_theta * gain, e_phi: field.e_phi * gain } )
+    }
+}
@@ -0,0 +1,78 @@
+//! Idealized directive elements
+//!
+//! A Gaussian main beam with no sidelobes stands in for a real directive
+//! element (horn, small reflector, sub-array) when only its beamwidth
+//! matters, e.g. in system level simulations.
+
+use core::f64::consts::LN_2;
+
+use num::complex::Complex;
+
+use crate::{element_gain, float, ElementIface, GainIface, Point};
+
+/// An element with a Gaussian beam pointing along +z
+///
+/// The power pattern is `gain^2 * exp(-4 * ln(2) * (theta / hpbw)^2)`, so it
+/// is exactly half power at `theta = hpbw / 2` and has no sidelobes.
+#[derive(Builder,Clone,Default)]
+#[cfg_attr(all(feature = "no_std", not(feature = "std")), builder(no_std))]
+pub struct GaussianBeamElement {
+    /// position of the element in space
+    position: Point,
+    /// Full half-power beamwidth (radians)
+    hpbw: f64,
+    /// Amplitude gain at the peak of the beam
+    #[builder(default = "1.0")]
+    gain: f64,
+    /// Weight applied to element pattern
+    #[builder(default = "Complex::new(1.0,0.0)")]
+    weight: Complex<f64>,
+    /// Time delay in the feed (seconds)
+    #[builder(default)]
+    delay: f64,
+}
+
+impl GaussianBeamElement {
+    /// Full half-power beamwidth (radians)
+    pub fn hpbw(&self) -> f64 {
+        self.hpbw
+    }
+}
+
+impl GainIface for GaussianBeamElement {
+    fn get_gain(&self, frequency: f64, theta: f64, phi: f64) -> Option<Complex<f64>> {
+        element_gain(self, frequency, theta, phi)
+    }
+}
+
+impl ElementIface for GaussianBeamElement {
+    fn position(&self) -> &Point {
+        &self.position
+    }
+
+    fn set_position(&mut self, position: Point) {
+        self.position = position;
+    }
+
+    fn weight(&self) -> Complex<f64> {
+        self.weight
+    }
+
+    fn set_weight(&mut self, weight: Complex<f64>) {
+        self.weight = weight;
+    }
+
+    fn delay(&self) -> f64 {
+        self.delay
+    }
+
+    fn set_delay(&mut self, delay: f64) {
+        self.delay = delay;
+    }
+
+    fn pattern(&self, _frequency: f64, theta: f64, _phi: f64) -> Option<Complex<f64>> {
+        // amplitude, so half the exponent of the power pattern
+        let x = theta / self.hpbw;
+        Some( Complex::new(self.gain * float::exp(-2.0 * LN_2 * x * x), 0.0) )
+    }
+}
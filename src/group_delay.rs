@@ -0,0 +1,40 @@
+//! Group delay across a frequency sweep
+//!
+//! Wideband signals are distorted when the phase of the array response
+//! isn't linear in frequency. The group delay, `-d(phase)/d(omega)`, is
+//! constant for a distortion-free (e.g. true time delay steered) array.
+
+use std::iter;
+
+use crate::math::unwrap_phase;
+use crate::{phase_convention, ElementArray, GainIface, PI};
+
+impl ElementArray {
+    /// Group delay (seconds) at each frequency of a sweep towards `(theta, phi)`
+    ///
+    /// The phase of `get_gain` is unwrapped along `freqs` (ascending, in Hz)
+    /// and differentiated with central differences, one-sided at the ends of
+    /// the sweep. The sweep has to be fine enough that the phase moves by
+    /// less than PI between frequencies. A delay is positive under either
+    /// phase convention. Fewer than two frequencies give zeros.
+    ///
+    pub fn group_delay(&self, freqs: &[f64], theta: f64, phi: f64) -> Vec<f64> {
+        if freqs.len() < 2 {
+            return vec![0.0; freqs.len()];
+        }
+
+        let mut phases: Vec<f64> = freqs
+            .iter()
+            .map(|f| self.get_gain(*f, theta, phi).unwrap_or_default().arg())
+            .collect();
+        unwrap_phase(&mut phases);
+
+        let slope = |a: usize, b: usize| (phases[b] - phases[a]) / (2.0 * PI * (freqs[b] - freqs[a]));
+        let last = freqs.len() - 1;
+        iter::once(slope(0, 1))
+            .chain((1..last).map(|idx| slope(idx - 1, idx + 1)))
+            .chain(iter::once(slope(last - 1, last)))
+            .map(|d_phase| -phase_convention().sign() * d_phase)
+            .collect()
+    }
+}
@@ -0,0 +1,183 @@
+//! Reading and writing files
+//!
+//! Everything in the crate that touches the filesystem lives here, behind
+//! the `io` feature (on by default). The rest of the library works on
+//! in-memory data, so it builds with `--no-default-features` for targets
+//! without a filesystem. Each file format with its own dependencies is
+//! behind a further feature.
+
+use std::fs;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+#[cfg(feature = "npy")]
+use ndarray::Array2;
+
+use num::complex::Complex;
+
+#[cfg(feature = "json")]
+use crate::PatternDataset;
+use crate::{Beamformer, DataElement, DataFileError, PatternGrid, Point};
+
+impl DataElement {
+    /// Read a TICRA GRASP `.cut` file
+    ///
+    /// See `from_grasp_cut_str` for the supported contents.
+    ///
+    pub fn from_grasp_cut<P: AsRef<Path>>(path: P) -> Result<DataElement, DataFileError> {
+        DataElement::from_grasp_cut_str(&fs::read_to_string(path)?)
+    }
+
+    /// Read an NSI far-field ASCII export
+    ///
+    /// See `from_nsi_str` for the supported contents.
+    ///
+    pub fn from_nsi<P: AsRef<Path>>(path: P) -> Result<DataElement, DataFileError> {
+        DataElement::from_nsi_str(&fs::read_to_string(path)?)
+    }
+
+    /// Read a CST far-field ASCII export
+    ///
+    /// See `from_cst_str` for the supported contents.
+    ///
+    pub fn from_cst<P: AsRef<Path>>(path: P, position: Point) -> Result<DataElement, DataFileError> {
+        DataElement::from_cst_str(&fs::read_to_string(path)?, position)
+    }
+
+    /// Attach the input match from a Touchstone `.s1p` file
+    ///
+    /// See `with_match_str`.
+    ///
+    pub fn with_match<P: AsRef<Path>>(pattern: DataElement, s1p_path: P) -> Result<DataElement, DataFileError> {
+        DataElement::with_match_str(pattern, &fs::read_to_string(s1p_path)?)
+    }
+}
+
+impl Beamformer {
+    /// Write the weights to a text file
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    /// Read weights written by `save`
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Beamformer, DataFileError> {
+        fs::read_to_string(path)?.parse()
+    }
+}
+
+#[cfg(feature = "json")]
+impl PatternDataset {
+    /// Write the dataset to a JSON file
+    ///
+    /// Complex samples are written as `[re, im]` pairs.
+    ///
+    pub fn save_json<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        let mut file = BufWriter::new(fs::File::create(path)?);
+        serde_json::to_writer(&mut file, self)?;
+        file.flush()
+    }
+
+    /// Read a dataset written by `save_json`
+    pub fn load_json<P: AsRef<Path>>(path: P) -> Result<PatternDataset, DataFileError> {
+        let dataset: PatternDataset = serde_json::from_str(&fs::read_to_string(path)?).map_err(|err| DataFileError::Parse {
+            line: err.line(),
+            message: err.to_string(),
+        })?;
+        if !dataset.is_consistent() {
+            return Err(DataFileError::IncompleteGrid);
+        }
+        Ok(dataset)
+    }
+}
+
+/// Write an excitation table (see `ElementArray::excitation_table`) as CSV
+///
+/// One row per element with the position in meters and the weight as
+/// amplitude (dB) and phase (degrees), under the header
+/// `x_m,y_m,z_m,amplitude_db,phase_deg`.
+///
+pub fn write_excitation_csv(table: &[(Point, Complex<f64>)], path: &Path) -> std::io::Result<()> {
+    let mut file = BufWriter::new(fs::File::create(path)?);
+    writeln!(file, "x_m,y_m,z_m,amplitude_db,phase_deg")?;
+    for (position, weight) in table {
+        writeln!(
+            file,
+            "{},{},{},{},{}",
+            position.x,
+            position.y,
+            position.z,
+            20.0 * weight.norm().log10(),
+            weight.arg().to_degrees()
+        )?;
+    }
+    file.flush()
+}
+
+/// How gain maps to radius in `write_vtk`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GainScale {
+    /// Radius is the gain magnitude
+    Linear,
+    /// Radius is the gain in dB above `floor_db`, relative to the peak (e.g. -40.0)
+    ///
+    /// The peak sits at `-floor_db` and anything at or below the floor at
+    /// the origin.
+    Db {
+        /// lowest level shown (dB below the peak, negative)
+        floor_db: f64,
+    },
+}
+
+/// Write a pattern as a 3D surface to a legacy ASCII VTK file (ParaView etc.)
+///
+/// The file is a structured grid with one vertex per sample, `num_theta` by
+/// `num_phi`, each placed in its direction at a radius given by `scale`.
+/// The gain in dB relative to the peak is attached as point data so the
+/// surface can be colored by it.
+///
+pub fn write_vtk(grid: &PatternGrid, path: &Path, scale: GainScale) -> std::io::Result<()> {
+    let relative_db = grid.normalize_db(-300.0);
+    let mut file = BufWriter::new(fs::File::create(path)?);
+
+    let count = grid.num_theta() * grid.num_phi();
+    writeln!(file, "# vtk DataFile Version 3.0")?;
+    writeln!(file, "antenna pattern")?;
+    writeln!(file, "ASCII")?;
+    writeln!(file, "DATASET STRUCTURED_GRID")?;
+    writeln!(file, "DIMENSIONS {} {} 1", grid.num_theta(), grid.num_phi())?;
+    writeln!(file, "POINTS {} double", count)?;
+    for (phi_idx, (row, row_db)) in grid.values().iter().zip(relative_db.values()).enumerate() {
+        let phi = grid.phi(phi_idx);
+        for (theta_idx, (value, db)) in row.iter().zip(row_db).enumerate() {
+            let theta = grid.theta(theta_idx);
+            let radius = match scale {
+                GainScale::Linear => value.norm(),
+                GainScale::Db { floor_db } => (db - floor_db).max(0.0),
+            };
+            writeln!(
+                file,
+                "{} {} {}",
+                radius * theta.sin() * phi.cos(),
+                radius * theta.sin() * phi.sin(),
+                radius * theta.cos()
+            )?;
+        }
+    }
+
+    writeln!(file, "POINT_DATA {}", count)?;
+    writeln!(file, "SCALARS gain_db double 1")?;
+    writeln!(file, "LOOKUP_TABLE default")?;
+    for value in relative_db.values().iter().flatten() {
+        writeln!(file, "{}", value)?;
+    }
+    file.flush()
+}
+
+/// Write a complex pattern grid to a NumPy `.npy` file
+///
+/// The file loads in Python with `numpy.load` as a complex128 array with the
+/// same shape as `grid`.
+#[cfg(feature = "npy")]
+pub fn write_pattern_npy(grid: &Array2<Complex<f64>>, path: &Path) -> Result<(), ndarray_npy::WriteNpyError> {
+    ndarray_npy::write_npy(path, grid)
+}
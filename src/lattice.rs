@@ -0,0 +1,233 @@
+//! Element lattices
+//!
+//! The lattice an array is built on decides how far it can scan before a
+//! grating lobe enters visible space. Hexagonal (triangular) lattices can
+//! scan further than rectangular ones for the same element spacing.
+
+use crate::{ElementArray, ElementIface, Point, SPEED_OF_LIGHT};
+
+/// Shape of the lattice the elements of an array sit on
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Lattice {
+    /// Evenly spaced elements on a line
+    Linear {
+        /// distance between neighbouring elements (meters)
+        spacing: f64,
+    },
+    /// Rectangular grid in the XY plane
+    Rectangular {
+        /// distance between columns (meters)
+        dx: f64,
+        /// distance between rows (meters)
+        dy: f64,
+    },
+    /// Equilateral triangular grid in the XY plane
+    Hexagonal {
+        /// distance between nearest neighbours (meters)
+        spacing: f64,
+    },
+    /// Anything else
+    Irregular,
+}
+
+impl Lattice {
+    /// Largest scan angle (radians) that keeps grating lobes out of visible space
+    ///
+    /// This is the scan limit in the worst scan plane. Lattices that already
+    /// have a grating lobe at broadside return 0 and irregular lattices
+    /// return `None`.
+    ///
+    pub fn max_scan(&self, frequency: f64) -> Option<f64> {
+        let wavelength = SPEED_OF_LIGHT / frequency;
+        // distance from the main beam to the nearest grating lobe in sin(theta) space
+        let lobe_distance = match self {
+            Lattice::Linear { spacing } => wavelength / spacing,
+            Lattice::Rectangular { dx, dy } => wavelength / dx.max(*dy),
+            Lattice::Hexagonal { spacing } => 2.0 * wavelength / (3.0_f64.sqrt() * spacing),
+            Lattice::Irregular => return None,
+        };
+        Some((lobe_distance - 1.0).clamp(0.0, 1.0).asin())
+    }
+}
+
+/// Largest element spacing (wavelengths) that keeps grating lobes out of visible space
+///
+/// This is the linear/rectangular lattice limit `1 / (1 + sin(max_scan))`
+/// for a beam scanned up to `max_scan_deg` degrees from broadside.
+///
+pub fn max_spacing_for_scan(max_scan_deg: f64) -> f64 {
+    1.0 / (1.0 + max_scan_deg.to_radians().sin())
+}
+
+/// Builds a planar array on a hexagonal lattice in the XY plane
+///
+/// The array is a center element plus `rings` hexagonal rings around it,
+/// `3 * rings * (rings + 1) + 1` elements in total.
+#[derive(Clone, Default)]
+pub struct HexArrayBuilder {
+    rings: usize,
+    spacing: f64,
+}
+
+impl HexArrayBuilder {
+    /// Number of rings around the center element
+    pub fn rings(&mut self, rings: usize) -> &mut Self {
+        self.rings = rings;
+        self
+    }
+
+    /// Distance between nearest neighbours (meters)
+    pub fn spacing(&mut self, spacing: f64) -> &mut Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Build the array, using `element` to create the element at each position
+    pub fn build<F>(&self, element: F) -> ElementArray
+    where
+        F: Fn(Point) -> Box<dyn ElementIface>,
+    {
+        let rings = self.rings as i64;
+        let mut elements = Vec::new();
+        for r in -rings..=rings {
+            for q in -rings..=rings {
+                if (q + r).abs() > rings {
+                    continue;
+                }
+                let x = self.spacing * (q as f64 + r as f64 / 2.0);
+                let y = self.spacing * r as f64 * 3.0_f64.sqrt() / 2.0;
+                elements.push(element(Point { x, y, z: 0.0 }));
+            }
+        }
+        ElementArray::new( elements )
+    }
+}
+
+/// Tolerance used when comparing element positions, relative to the spacing
+const TOLERANCE: f64 = 1e-6;
+
+/// Sorted distinct values, merging values closer than `tol`
+fn distinct(mut values: Vec<f64>, tol: f64) -> Vec<f64> {
+    values.sort_by(f64::total_cmp);
+    values.dedup_by(|a, b| (*a - *b).abs() < tol);
+    values
+}
+
+/// Common step between sorted values, if they are evenly spaced
+fn even_step(values: &[f64], tol: f64) -> Option<f64> {
+    let step = values[1] - values[0];
+    values
+        .windows(2)
+        .all(|w| (w[1] - w[0] - step).abs() < tol)
+        .then_some(step)
+}
+
+/// Basis of the planar lattice through `positions`, if every position is on it
+///
+/// The first basis vector is a nearest-neighbour offset and the second is
+/// the shortest offset between two elements that isn't parallel to it,
+/// which for a 2D lattice is a basis of the whole lattice. Every position
+/// then has to be an integer combination of the two from the first one,
+/// within `tol`.
+fn planar_basis(positions: &[&Point], min_distance: f64, tol: f64) -> Option<([f64; 2], [f64; 2])> {
+    let offsets: Vec<[f64; 2]> = positions
+        .iter()
+        .enumerate()
+        .flat_map(|(idx, a)| positions.iter().skip(idx + 1).map(move |b| [b.x - a.x, b.y - a.y]))
+        .collect();
+    let length = |v: &[f64; 2]| (v[0] * v[0] + v[1] * v[1]).sqrt();
+
+    let a = *offsets.iter().find(|v| (length(v) - min_distance).abs() < tol)?;
+    let b = *offsets
+        .iter()
+        .filter(|v| (a[0] * v[1] - a[1] * v[0]).abs() / min_distance > tol)
+        .min_by(|v, w| length(v).total_cmp(&length(w)))?;
+
+    let det = a[0] * b[1] - a[1] * b[0];
+    let origin = positions[0];
+    let on_lattice = positions.iter().all(|p| {
+        let (dx, dy) = (p.x - origin.x, p.y - origin.y);
+        let i = ((dx * b[1] - dy * b[0]) / det).round();
+        let j = ((a[0] * dy - a[1] * dx) / det).round();
+        length(&[dx - i * a[0] - j * b[0], dy - i * a[1] - j * b[1]]) < tol
+    });
+    on_lattice.then_some((a, b))
+}
+
+impl ElementArray {
+    /// Work out which lattice the elements sit on
+    ///
+    /// Every element has to sit on the lattice, though the lattice doesn't
+    /// have to be full. Coincident elements or non-finite positions make the
+    /// array `Irregular`.
+    ///
+    pub fn detect_lattice(&self) -> Lattice {
+        let positions: Vec<&Point> = self.elements.iter().map(|n| n.position()).collect();
+        if positions.len() < 2 || positions.iter().any(|p| !(p.x.is_finite() && p.y.is_finite() && p.z.is_finite())) {
+            return Lattice::Irregular;
+        }
+
+        let distance = |a: &Point, b: &Point| {
+            ((a.x - b.x).powi(2) + (a.y - b.y).powi(2) + (a.z - b.z).powi(2)).sqrt()
+        };
+        let mut min_distance = f64::INFINITY;
+        for (idx, a) in positions.iter().enumerate() {
+            for b in positions.iter().skip(idx + 1) {
+                min_distance = min_distance.min(distance(a, b));
+            }
+        }
+        if min_distance == 0.0 {
+            return Lattice::Irregular;
+        }
+        let tol = TOLERANCE * min_distance;
+
+        // collinear: every element lies on the line through the first two
+        let origin = positions[0];
+        let (ux, uy, uz) = (
+            positions[1].x - origin.x,
+            positions[1].y - origin.y,
+            positions[1].z - origin.z,
+        );
+        let norm = (ux * ux + uy * uy + uz * uz).sqrt();
+        let (ux, uy, uz) = (ux / norm, uy / norm, uz / norm);
+        let along: Vec<f64> = positions
+            .iter()
+            .map(|p| (p.x - origin.x) * ux + (p.y - origin.y) * uy + (p.z - origin.z) * uz)
+            .collect();
+        let collinear = positions.iter().zip(along.iter()).all(|(p, t)| {
+            let offset = Point { x: origin.x + t * ux, y: origin.y + t * uy, z: origin.z + t * uz };
+            distance(p, &offset) < tol
+        });
+        if collinear {
+            let along = distinct(along, tol);
+            return match even_step(&along, tol) {
+                Some(spacing) if along.len() == positions.len() => Lattice::Linear { spacing },
+                _ => Lattice::Irregular,
+            };
+        }
+
+        // everything else has to be in the XY plane
+        if positions.iter().any(|p| (p.z - origin.z).abs() > tol) {
+            return Lattice::Irregular;
+        }
+
+        let Some((a, b)) = planar_basis(&positions, min_distance, tol) else {
+            return Lattice::Irregular;
+        };
+        let (len_a, len_b) = ((a[0] * a[0] + a[1] * a[1]).sqrt(), (b[0] * b[0] + b[1] * b[1]).sqrt());
+        let cos_angle = (a[0] * b[0] + a[1] * b[1]) / (len_a * len_b);
+        if (len_a - len_b).abs() < tol && (cos_angle.abs() - 0.5).abs() < TOLERANCE {
+            return Lattice::Hexagonal { spacing: len_a };
+        }
+        if cos_angle.abs() < TOLERANCE {
+            // columns are spaced by whichever basis vector points more along x
+            return if a[0].abs() >= b[0].abs() {
+                Lattice::Rectangular { dx: len_a, dy: len_b }
+            } else {
+                Lattice::Rectangular { dx: len_b, dy: len_a }
+            };
+        }
+
+        Lattice::Irregular
+    }
+}
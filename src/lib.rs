@@ -3,24 +3,148 @@
 //! This library provides tools for the user to create standard and custom
 //! antenna patterns.
 //!
+//! With the `no_std` feature and `--no-default-features` the crate builds
+//! without the standard library, needing only `alloc`. What is left is the
+//! element/array gain math: the element types, `ElementArray` and the
+//! traits to evaluate them.
 //!
 
 #![warn(missing_docs)]
 #![warn(rustdoc::missing_doc_code_examples)]
+#![cfg_attr(all(feature = "no_std", not(feature = "std")), no_std)]
+
+extern crate alloc;
 
 #[macro_use]
 extern crate derive_builder;
 
+use alloc::{boxed::Box, vec, vec::Vec};
+
 use num::complex::Complex;
 
+/// Items that need the standard library, left out of `no_std` builds
+macro_rules! with_std {
+    ($($item:item)*) => {
+        $( #[cfg(not(all(feature = "no_std", not(feature = "std"))))] $item )*
+    };
+}
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "io")]
+pub mod io;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+mod calibration;
+mod composite;
+mod convention;
+mod field;
+mod float;
+mod gain_table;
+mod gaussian;
+mod linear;
+mod subarray;
+
+with_std! {
+    mod aperture;
+    mod azel;
+    mod beamformer;
+    mod beamwidth;
+    mod compare;
+    #[cfg(feature = "ndarray")]
+    mod correlation;
+    mod coupling;
+    mod cut;
+    mod data_element;
+    mod data_files;
+    mod dataset;
+    mod dual_pol;
+    mod eirp;
+    mod endfire;
+    mod failure;
+    mod fit;
+    mod group_delay;
+    mod lattice;
+    mod math;
+    mod monopulse;
+    mod nearfield;
+    mod overlap;
+    mod pattern;
+    mod pattern_db;
+    mod planar;
+    mod rng;
+    mod sampling;
+    mod scan_table;
+    mod sidelobe;
+    #[cfg(feature = "simd")]
+    mod simd;
+    mod spatial_correlation;
+    mod steering;
+    mod superdirective;
+    mod symmetry;
+    mod synthesis;
+    mod taper;
+    mod thinning;
+    mod tolerance;
+    mod touchstone;
+    mod track;
+    mod units;
+    mod uv;
+}
+
+pub use composite::CompositeArray;
+pub use convention::{phase_convention, set_phase_convention, PhaseConvention};
+pub use field::{FarField, GainResult};
+pub use gain_table::GainTableElement;
+pub use gaussian::{GaussianBeamElement, GaussianBeamElementBuilder};
+pub use subarray::SubarrayElement;
+
+with_std! {
+    pub use aperture::{aperture_far_field, sample_aperture_distribution};
+    pub use azel::{AzElConvention, AzElGrid};
+    pub use beamformer::Beamformer;
+    pub use compare::FitReport;
+    pub use coupling::mutual_impedance_dipoles;
+    pub use cut::PatternCut;
+    pub use data_element::{DataElement, PhaseModel};
+    pub use data_files::DataFileError;
+    pub use dataset::PatternDataset;
+    pub use dual_pol::DualPolElement;
+    pub use failure::FailureStats;
+    pub use fit::fit_cos_n;
+    pub use lattice::{max_spacing_for_scan, HexArrayBuilder, Lattice};
+    pub use monopulse::Axis;
+    pub use nearfield::{FieldPlane, GainError};
+    pub use overlap::pattern_coupling;
+    pub use pattern::{PatternError, PatternGrid};
+    pub use pattern_db::PatternGridDb;
+    pub use planar::PlanarArray;
+    pub use scan_table::{ScanTable, ScanTableError};
+    pub use sampling::{max_directivity_bound, recommended_integration_step, swe_truncation_order};
+    pub use steering::bits_for_pointing_budget;
+    pub use symmetry::SymmetryReport;
+    pub use taper::taylor_linear;
+    pub use units::{
+        antenna_factor_to_gain, dbi_to_dbd, dbi_to_dbic, effective_height_to_gain, gain_to_antenna_factor,
+        gain_to_effective_height, Polarization, DIPOLE_GAIN_DBI,
+    };
+    pub use uv::UvGrid;
+}
+
 /// Speed of Light (m/s)
 pub const SPEED_OF_LIGHT: f64 = 299792458.0;
 
 /// An imaginary number
+#[cfg(not(all(feature = "no_std", not(feature = "std"))))]
 const I: Complex<f64> = Complex::new(0.0, 1.0);
 
 /// Pi
-pub const PI: f64 = std::f64::consts::PI;
+pub const PI: f64 = core::f64::consts::PI;
+
+/// Wave impedance of free space (ohms), the 120 * PI approximation used by the textbook tables
+#[cfg(not(all(feature = "no_std", not(feature = "std"))))]
+const FREE_SPACE_IMPEDANCE: f64 = 120.0 * PI;
 
 /// Interface for all objects that have gain
 ///
@@ -34,6 +158,316 @@ pub trait GainIface {
     /// gain for certain frequencies and/or aspect angles.
     ///
     fn get_gain(&self, frequency: f64, theta: f64, phi: f64) -> Option<Complex<f64>>;
+
+    /// Sample the gain over the whole sphere
+    ///
+    /// Directions where `get_gain` returns `None` are stored as NaN so that
+    /// they can't be mistaken for a null.
+    ///
+    #[cfg(not(all(feature = "no_std", not(feature = "std"))))]
+    fn sample_sphere(&self, frequency: f64, theta_step: f64, phi_step: f64) -> PatternGrid {
+        PatternGrid::from_fn(theta_step, phi_step, |theta, phi| {
+            self.get_gain(frequency, theta, phi)
+                .unwrap_or_else(|| Complex::new(f64::NAN, f64::NAN))
+        })
+    }
+
+    /// Sample the gain over the whole sphere one phi row at a time
+    ///
+    /// Same samples as `sample_sphere`, but instead of building a grid each
+    /// row is handed to `f(phi_idx, row)` as soon as it's computed, so a
+    /// fine grid can be written out or reduced without holding all of it.
+    /// Only one row is ever allocated.
+    ///
+    fn sample_sphere_streaming(&self, frequency: f64, theta_step: f64, phi_step: f64, mut f: impl FnMut(usize, &[Complex<f64>]))
+    where
+        Self: Sized,
+    {
+        let num_theta = float::round(PI / theta_step) as usize + 1;
+        let num_phi = (float::round(2.0 * PI / phi_step) as usize).max(1);
+
+        let mut row = vec![Complex::new(0.0, 0.0); num_theta];
+        for phi_idx in 0..num_phi {
+            let phi = phi_idx as f64 * phi_step;
+            for (theta_idx, value) in row.iter_mut().enumerate() {
+                *value = self.get_gain(frequency, theta_idx as f64 * theta_step, phi)
+                    .unwrap_or_else(|| Complex::new(f64::NAN, f64::NAN));
+            }
+            f(phi_idx, &row);
+        }
+    }
+
+    /// Sample the gain over the whole sphere into an ndarray, indexed `[phi_idx, theta_idx]`
+    ///
+    /// Same samples and layout as `sample_sphere`.
+    ///
+    #[cfg(feature = "ndarray")]
+    fn sample_to_ndarray(&self, frequency: f64, theta_step: f64, phi_step: f64) -> ndarray::Array2<Complex<f64>> {
+        let grid = self.sample_sphere(frequency, theta_step, phi_step);
+        ndarray::Array2::from_shape_fn((grid.num_phi(), grid.num_theta()), |(phi_idx, theta_idx)| {
+            grid.get(phi_idx, theta_idx)
+        })
+    }
+
+    /// Sample the gain along the plane at `phi`, theta from -PI to PI
+    #[cfg(not(all(feature = "no_std", not(feature = "std"))))]
+    fn sample_cut(&self, frequency: f64, phi: f64, theta_step: f64) -> PatternCut {
+        PatternCut::from_fn(phi, theta_step, |theta, phi| {
+            self.get_gain(frequency, theta, phi)
+                .unwrap_or_else(|| Complex::new(f64::NAN, f64::NAN))
+        })
+    }
+
+    /// Sample the gain over an azimuth/elevation grid (azimuth over elevation)
+    #[cfg(not(all(feature = "no_std", not(feature = "std"))))]
+    fn sample_azel(&self, frequency: f64, az_step: f64, el_step: f64) -> AzElGrid {
+        AzElGrid::from_fn(AzElConvention::AzOverEl, az_step, el_step, |theta, phi| {
+            self.get_gain(frequency, theta, phi)
+                .unwrap_or_else(|| Complex::new(f64::NAN, f64::NAN))
+        })
+    }
+
+    /// Sample the gain over an azimuth/elevation grid (elevation over azimuth)
+    #[cfg(not(all(feature = "no_std", not(feature = "std"))))]
+    fn sample_elaz(&self, frequency: f64, az_step: f64, el_step: f64) -> AzElGrid {
+        AzElGrid::from_fn(AzElConvention::ElOverAz, az_step, el_step, |theta, phi| {
+            self.get_gain(frequency, theta, phi)
+                .unwrap_or_else(|| Complex::new(f64::NAN, f64::NAN))
+        })
+    }
+}
+
+/// Interface for single elements that can be placed in an array
+///
+/// On top of its gain, an array needs to know where each element sits and
+/// how it is weighted so that it can split, steer and taper its elements.
+///
+pub trait ElementIface: GainIface + ElementClone {
+    /// Position of the phase center of the element
+    fn position(&self) -> &Point;
+
+    /// Move the phase center of the element
+    fn set_position(&mut self, position: Point);
+
+    /// Weight applied to the element pattern
+    fn weight(&self) -> Complex<f64>;
+
+    /// Replace the weight applied to the element pattern
+    fn set_weight(&mut self, weight: Complex<f64>);
+
+    /// Time delay in the feed of the element (seconds)
+    fn delay(&self) -> f64;
+
+    /// Replace the time delay in the feed of the element (seconds)
+    ///
+    /// The delay adds a phase of `2 * PI * frequency * delay` on top of the
+    /// weight, so it models cable lengths and true time delay steering.
+    ///
+    fn set_delay(&mut self, delay: f64);
+
+    /// Pattern of the element at its own phase center
+    ///
+    /// This is the gain without the positional phase and without the weight.
+    ///
+    fn pattern(&self, frequency: f64, theta: f64, phi: f64) -> Option<Complex<f64>>;
+
+    /// Polarized pattern of the element at its own phase center
+    ///
+    /// Like `pattern`, this leaves out the positional phase and the weight.
+    /// Elements without polarization data are taken to be purely co-polar
+    /// (Ludwig-3).
+    ///
+    fn pattern_field(&self, frequency: f64, theta: f64, phi: f64) -> Option<FarField> {
+        Some( FarField::from_ludwig3(self.pattern(frequency, theta, phi)?, Complex::new(0.0, 0.0), phi) )
+    }
+}
+
+/// Cloning for boxed elements
+///
+/// Implemented automatically for every element type that is `Clone`, so
+/// arrays of `Box<dyn ElementIface>` can be copied.
+///
+pub trait ElementClone {
+    /// Copy the element into a new box
+    fn box_clone(&self) -> Box<dyn ElementIface>;
+}
+
+impl<T: ElementIface + Clone + 'static> ElementClone for T {
+    fn box_clone(&self) -> Box<dyn ElementIface> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn ElementIface> {
+    fn clone(&self) -> Self {
+        self.box_clone()
+    }
+}
+
+/// Interface for groups of elements
+///
+/// Anything built out of elements, including arrays of sub-arrays, can be
+/// combined into a bigger array through this interface.
+///
+pub trait ArrayIface: GainIface {
+    /// Total number of radiating elements
+    fn num_elements(&self) -> usize;
+
+    /// Copies of every radiating element, with all the weights above it folded into its own
+    fn flat_elements(&self) -> Vec<Box<dyn ElementIface>>;
+
+    /// Gain at every combination of `thetas` and `phis` in one flat buffer
+    ///
+    /// The buffer is row-major with one row per phi, the same order as
+    /// `PatternGrid`: the gain at `(thetas[t], phis[p])` is at index
+    /// `p * thetas.len() + t`. The returned dimensions are `(rows, columns)`,
+    /// i.e. `(phis.len(), thetas.len())`. Directions with no gain are NaN.
+    ///
+    fn gain_flat(&self, frequency: f64, thetas: &[f64], phis: &[f64]) -> (Vec<Complex<f64>>, usize, usize) {
+        let values = phis
+            .iter()
+            .flat_map(|phi| thetas.iter().map(move |theta| (*theta, *phi)))
+            .map(|(theta, phi)| {
+                self.get_gain(frequency, theta, phi)
+                    .unwrap_or_else(|| Complex::new(f64::NAN, f64::NAN))
+            })
+            .collect();
+        (values, phis.len(), thetas.len())
+    }
+
+    /// Peak power over the sphere-averaged power (dB)
+    ///
+    /// This is the directivity of the sampled pattern, so an isotropic
+    /// pattern gives 0 dB.
+    ///
+    #[cfg(not(all(feature = "no_std", not(feature = "std"))))]
+    fn peak_to_average(&self, frequency: f64, theta_step: f64, phi_step: f64) -> f64 {
+        self.sample_sphere(frequency, theta_step, phi_step).peak_gain_dbi()
+    }
+
+    /// Phase (radians) of the gain at every combination of `thetas` and `phis`
+    ///
+    /// Indexed `[phi_idx, theta_idx]`, the same layout as `gain_flat`. With
+    /// `PhaseWrapping::Unwrapped` the first column is unwrapped along phi and
+    /// then each row along theta from there, which is right as long as the
+    /// phase moves by less than PI between neighbouring samples. Directions
+    /// with no gain are NaN.
+    ///
+    #[cfg(feature = "ndarray")]
+    fn phase_grid(&self, frequency: f64, thetas: &[f64], phis: &[f64], wrapping: PhaseWrapping) -> ndarray::Array2<f64> {
+        let (values, rows, cols) = self.gain_flat(frequency, thetas, phis);
+        let mut phases: Vec<f64> = values.iter().map(|value| value.arg()).collect();
+        if wrapping == PhaseWrapping::Unwrapped && cols > 0 {
+            let mut first: Vec<f64> = phases.iter().step_by(cols).cloned().collect();
+            math::unwrap_phase(&mut first);
+            for (row, start) in phases.chunks_mut(cols).zip(first) {
+                row[0] = start;
+                math::unwrap_phase(row);
+            }
+        }
+        ndarray::Array2::from_shape_vec((rows, cols), phases).unwrap()
+    }
+
+    /// Complex gain at every combination of direction cosines `us` and `vs`
+    ///
+    /// Indexed `[v_idx, u_idx]`, the same layout as `UvGrid`. Each `(u, v)`
+    /// is the front hemisphere direction `theta = asin(sqrt(u^2 + v^2))`,
+    /// `phi = atan2(v, u)`. Points outside the unit circle aren't real
+    /// directions and, like directions with no gain, are NaN.
+    ///
+    #[cfg(feature = "ndarray")]
+    fn gain_uv(&self, frequency: f64, us: &[f64], vs: &[f64]) -> ndarray::Array2<Complex<f64>> {
+        let nan = Complex::new(f64::NAN, f64::NAN);
+        ndarray::Array2::from_shape_fn((vs.len(), us.len()), |(v_idx, u_idx)| {
+            let (u, v) = (us[u_idx], vs[v_idx]);
+            // a little slack so points computed on the horizon aren't lost to rounding
+            if u * u + v * v > 1.0 + 1e-12 {
+                return nan;
+            }
+            let theta = (u * u + v * v).sqrt().min(1.0).asin();
+            let phi = v.atan2(u).rem_euclid(2.0 * PI);
+            self.get_gain(frequency, theta, phi).unwrap_or(nan)
+        })
+    }
+
+    /// Complex gain along with its magnitude, power and dB in one call
+    fn evaluate(&self, frequency: f64, theta: f64, phi: f64) -> Option<GainResult> {
+        Some( GainResult::new(self.get_gain(frequency, theta, phi)?) )
+    }
+
+    /// Polarized far field of the array, the sum of the element fields
+    fn get_field(&self, frequency: f64, theta: f64, phi: f64) -> Option<FarField> {
+        sum_fields(&self.flat_elements(), frequency, theta, phi)
+    }
+
+    /// Power-weighted average cross-polar discrimination over `region` (dB)
+    ///
+    /// The Ludwig-3 co and cross-polar power of the array field are each
+    /// summed over the `(theta, phi)` directions in `region`, which weights
+    /// every direction's cross/co ratio by its co-polar power, and the
+    /// result is `10 * log10(co / cross)`. A purely co-polar array gives
+    /// infinity. Directions with no field are skipped.
+    ///
+    fn average_xpd(&self, frequency: f64, region: &[(f64, f64)]) -> f64 {
+        let elements = self.flat_elements();
+        let (co, cross) = region
+            .iter()
+            .filter_map(|(theta, phi)| Some( sum_fields(&elements, frequency, *theta, *phi)?.ludwig3(*phi) ))
+            .fold((0.0, 0.0), |(co, cross), field| (co + field.0.norm_sqr(), cross + field.1.norm_sqr()));
+        10.0 * float::log10(co / cross)
+    }
+}
+
+/// Sum of the polarized far fields of `elements`, or `None` if any has no field
+fn sum_fields(elements: &[Box<dyn ElementIface>], frequency: f64, theta: f64, phi: f64) -> Option<FarField> {
+    elements.iter().try_fold(FarField::default(), |sum, n| {
+        let field = element_field(n.as_ref(), frequency, theta, phi)?;
+        Some( FarField { e_theta: sum.e_theta + field.e_theta, e_phi: sum.e_phi + field.e_phi } )
+    })
+}
+
+/// Polarized far field of an element once it is moved to its position and weighted
+fn element_field<E: ElementIface + ?Sized>(element: &E, frequency: f64, theta: f64, phi: f64) -> Option<FarField> {
+    let scale = calc_phase(element.position(), frequency, theta, phi) * delay_phase(element.delay(), frequency) * element.weight();
+    let field = element.pattern_field(frequency, theta, phi)?;
+    Some( FarField { e_theta: field.e_theta * scale, e_phi: field.e_phi * scale } )
+}
+
+/// Whether phases are left in (-PI, PI] or unwrapped
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PhaseWrapping {
+    /// Every phase in (-PI, PI]
+    #[default]
+    Wrapped,
+    /// 2*PI jumps between neighbouring samples removed
+    Unwrapped,
+}
+
+/// Gain of an element once it is moved to its position and weighted
+fn element_gain<E: ElementIface + ?Sized>(element: &E, frequency: f64, theta: f64, phi: f64) -> Option<Complex<f64>> {
+    Some( element_response(element, frequency, theta, phi)? * element.weight() )
+}
+
+/// Gain of an element at its position, including its delay but not its weight
+fn element_response<E: ElementIface + ?Sized>(element: &E, frequency: f64, theta: f64, phi: f64) -> Option<Complex<f64>> {
+    Some( calc_phase(element.position(), frequency, theta, phi) * element.pattern(frequency, theta, phi)? * delay_phase(element.delay(), frequency) )
+}
+
+/// Weight element `idx` of `array` radiates with when it is given `weight`
+///
+/// This is `weight` times the element's `set_calibration` correction, if
+/// there is one. Everything that evaluates an array from its weights goes
+/// through here instead of using the element weights directly.
+pub(crate) fn effective_weight(array: &ElementArray, idx: usize, weight: Complex<f64>) -> Complex<f64> {
+    match &array.calibration {
+        Some(calibration) => weight * calibration[idx],
+        None => weight,
+    }
+}
+
+/// Phase of a time delay at `frequency`, lagging under either phase convention
+fn delay_phase(delay: f64, frequency: f64) -> Complex<f64> {
+    float::cis(-phase_convention().sign() * 2.0 * PI * frequency * delay)
 }
 
 /// Translates element patterns in space
@@ -41,16 +475,24 @@ pub trait GainIface {
 /// Antenna patterns are normally created at the phase center of the antenna
 /// element. To create an array of elements, each element needs to be shifted
 /// to a different position so that their independent patterns can combine into
-/// a more focused pattern.
+/// a more focused pattern. The sign of the exponent follows `phase_convention`.
 ///
 fn calc_phase(pnt: &Point, frequency: f64, theta: f64, phi: f64) -> Complex<f64> {
-    let k = 2.0 * PI * frequency / SPEED_OF_LIGHT;
+    let k = phase_convention().sign() * 2.0 * PI * frequency / SPEED_OF_LIGHT;
+    let (sin_theta, cos_theta) = (float::sin(theta), float::cos(theta));
+
+    let dx = k * pnt.x * float::cos(phi) * sin_theta;
+    let dy = k * pnt.y * float::sin(phi) * sin_theta;
+    let dz = k * pnt.z * cos_theta;
 
-    let dx = I * k * pnt.x * phi.cos() * theta.sin();
-    let dy = I * k * pnt.y * phi.sin() * theta.sin();
-    let dz = I * k * pnt.z * theta.cos();
+    float::cis(dx + dy + dz)
+}
 
-    dx.exp() * dy.exp() * dz.exp()
+/// Angle between two (theta, phi) directions
+#[cfg(not(all(feature = "no_std", not(feature = "std"))))]
+fn separation(a: (f64, f64), b: (f64, f64)) -> f64 {
+    let cos = a.0.cos() * b.0.cos() + a.0.sin() * b.0.sin() * (a.1 - b.1).cos();
+    cos.clamp(-1.0, 1.0).acos()
 }
 
 /// An omni-directional element is the most generic type of element
@@ -58,14 +500,18 @@ fn calc_phase(pnt: &Point, frequency: f64, theta: f64, phi: f64) -> Complex<f64>
 /// On initialization, the user can set the position, gain, and weight
 /// of this element.
 #[derive(Builder,Clone,Default)]
+#[cfg_attr(all(feature = "no_std", not(feature = "std")), builder(no_std))]
 pub struct OmniElement {
-    // position of omni in space
+    /// position of omni in space
     position: Point,
-    // Omni elements usually have a gain of 1 (0dBi) but the user can set this manually
+    /// Omni elements usually have a gain of 1 (0dBi) but the user can set this manually
     gain: f64,
-    // Weight applied to element pattern
+    /// Weight applied to element pattern
     #[builder(default = "Complex::new(1.0,0.0)")]
     weight: Complex<f64>,
+    /// Time delay in the feed (seconds)
+    #[builder(default)]
+    delay: f64,
 }
 
 /// Satisfy required interface for OmniElement
@@ -73,22 +519,91 @@ pub struct OmniElement {
 ///
 impl GainIface for OmniElement {
     fn get_gain(&self, frequency: f64, theta: f64, phi: f64) -> Option<Complex<f64>> {
-        Some( calc_phase(&self.position, frequency, theta, phi) * self.gain * self.weight )
+        element_gain(self, frequency, theta, phi)
+    }
+}
+
+impl ElementIface for OmniElement {
+    fn position(&self) -> &Point {
+        &self.position
+    }
+
+    fn set_position(&mut self, position: Point) {
+        self.position = position;
+    }
+
+    fn weight(&self) -> Complex<f64> {
+        self.weight
+    }
+
+    fn set_weight(&mut self, weight: Complex<f64>) {
+        self.weight = weight;
+    }
+
+    fn delay(&self) -> f64 {
+        self.delay
+    }
+
+    fn set_delay(&mut self, delay: f64) {
+        self.delay = delay;
+    }
+
+    fn pattern(&self, _frequency: f64, _theta: f64, _phi: f64) -> Option<Complex<f64>> {
+        Some( Complex::new(self.gain, 0.0) )
     }
 }
 
 /// A patch is a PCB based antenna that has a hemispherically directional pattern
 ///
-///
+/// The patch sits in the XY plane radiating towards +z, over a ground plane
+/// that decides how much leaks into the back hemisphere.
+#[derive(Builder,Clone,Default)]
+#[cfg_attr(all(feature = "no_std", not(feature = "std")), builder(no_std))]
 pub struct PatchElement {
-    // position of patch in space
+    /// position of patch in space
     position: Point,
-    // side of patch parallel with feed (meters)
+    /// side of patch parallel with feed (meters)
     length: f64,
-    // side of patch normal to feed (meters)
+    /// side of patch normal to feed (meters)
     width: f64,
-    // Weight applied to element pattern
+    /// Weight applied to element pattern
+    #[builder(default = "Complex::new(1.0,0.0)")]
     weight: Complex<f64>,
+    /// Ground plane behind the patch
+    #[builder(default)]
+    ground: GroundPlane,
+    /// Time delay in the feed (seconds)
+    #[builder(default)]
+    delay: f64,
+}
+
+/// Ground plane model for elements that radiate off a board
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum GroundPlane {
+    /// Infinite ground plane, nothing is radiated behind the board
+    #[default]
+    Ideal,
+    /// Finite ground plane, the gain rolls off smoothly behind the board
+    Finite {
+        /// gain straight behind the board relative to the unshielded pattern (dB, e.g. -20.0)
+        back_level_db: f64,
+    },
+}
+
+impl GroundPlane {
+    /// Amplitude factor applied to the pattern at `theta`
+    fn factor(&self, theta: f64) -> f64 {
+        if theta <= PI / 2.0 {
+            return 1.0;
+        }
+        match self {
+            GroundPlane::Ideal => 0.0,
+            GroundPlane::Finite { back_level_db } => {
+                // falls linearly in dB from 1 at the horizon to back_level_db at theta = PI
+                float::powf(10.0, back_level_db / 20.0 * (theta - PI / 2.0) / (PI / 2.0))
+            }
+        }
+    }
 }
 
 /// Canonical formula to calculate gain of patch antenna
@@ -97,21 +612,21 @@ pub struct PatchElement {
 /// can benefit from the memoization that is here.
 fn patch_gain(length: f64, width: f64, frequency: f64, theta: f64, phi: f64) -> Complex<f64> {
     let k = 2.0 * PI * frequency / SPEED_OF_LIGHT;
-    let sin_theta = theta.sin();
-    let cos_theta = theta.cos();
-    let sin_phi = phi.sin();
-    let cos_phi = phi.cos();
+    let sin_theta = float::sin(theta);
+    let cos_theta = float::cos(theta);
+    let sin_phi = float::sin(phi);
+    let cos_phi = float::cos(phi);
 
     let inside0 = k * width * sin_theta * sin_phi / 2.0;
-    let value0 = inside0.sin() / inside0;
-    let value1 = (k * length * sin_theta * cos_phi).cos();
+    let value0 = if inside0 == 0.0 { 1.0 } else { float::sin(inside0) / inside0 };
+    let value1 = float::cos(k * length * sin_theta * cos_phi);
     let value2 = value0 * value1;
 
     let e_field_theta = value2 * cos_phi;
     let e_field_phi = -value2 * cos_theta * sin_phi;
 
     Complex::new(
-        (e_field_theta.powf(2.0) + e_field_phi.powf(2.0)).powf(0.5),
+        float::sqrt(float::powf(e_field_theta, 2.0) + float::powf(e_field_phi, 2.0)),
         0.0,
     )
 }
@@ -121,45 +636,200 @@ fn patch_gain(length: f64, width: f64, frequency: f64, theta: f64, phi: f64) ->
 ///
 impl GainIface for PatchElement {
     fn get_gain(&self, frequency: f64, theta: f64, phi: f64) -> Option<Complex<f64>> {
-        Some( patch_gain(self.length, self.width, frequency, theta, phi) )
+        element_gain(self, frequency, theta, phi)
     }
 }
 
-// Reads and interpolates a data table for the antenna pattern with optional
-// positional offset
+impl ElementIface for PatchElement {
+    fn position(&self) -> &Point {
+        &self.position
+    }
 
-/// A special element that relies on a table of data
-///
-///
-struct DataElement {
-    position: Option<Point>,
-    data: Vec<Vec<Complex<f64>>>,
+    fn set_position(&mut self, position: Point) {
+        self.position = position;
+    }
+
+    fn weight(&self) -> Complex<f64> {
+        self.weight
+    }
+
+    fn set_weight(&mut self, weight: Complex<f64>) {
+        self.weight = weight;
+    }
+
+    fn delay(&self) -> f64 {
+        self.delay
+    }
+
+    fn set_delay(&mut self, delay: f64) {
+        self.delay = delay;
+    }
+
+    fn pattern(&self, frequency: f64, theta: f64, phi: f64) -> Option<Complex<f64>> {
+        Some( patch_gain(self.length, self.width, frequency, theta, phi) * self.ground.factor(theta) )
+    }
 }
 
 /// A position in 3D cartesian space
-#[derive(Builder,Clone,Default)]
+#[derive(Builder,Clone,Debug,Default,PartialEq)]
+#[cfg_attr(all(feature = "no_std", not(feature = "std")), builder(no_std))]
 pub struct Point {
     // all values are distance from origin (meters)
+    /// Distance along the x axis
     #[builder( default = "0.0") ]
     x: f64,
+    /// Distance along the y axis
     #[builder( default = "0.0") ]
     y: f64,
+    /// Distance along the z axis
     #[builder( default = "0.0") ]
     z: f64,
 }
 
+impl Point {
+    /// Distance along the x axis (meters)
+    pub fn x(&self) -> f64 {
+        self.x
+    }
+
+    /// Distance along the y axis (meters)
+    pub fn y(&self) -> f64 {
+        self.y
+    }
+
+    /// Distance along the z axis (meters)
+    pub fn z(&self) -> f64 {
+        self.z
+    }
+}
+
 /// This object represents an array of elements
 ///
 /// Antenna arrays take many shapes, this can handle all of them as long as
-/// each element satisfies the GainIface trait.
-pub struct ElementArray ( pub Vec<Box<dyn GainIface>> );
+/// each element satisfies the ElementIface trait.
+#[derive(Clone)]
+pub struct ElementArray {
+    elements: Vec<Box<dyn ElementIface>>,
+    // loss of the feed network (dB), only applied to realized gain
+    feed_loss_db: f64,
+    // per-element channel correction applied on top of the weights
+    calibration: Option<Vec<Complex<f64>>>,
+}
 
-impl GainIface for ElementArray {
-    fn get_gain(&self, frequency: f64, phi: f64, theta: f64) -> Option<Complex<f64>> {
-        let gains: Vec<Complex<f64>> = self.0
+impl ElementArray {
+    /// Create an array from its elements
+    pub fn new(elements: Vec<Box<dyn ElementIface>>) -> ElementArray {
+        ElementArray { elements, feed_loss_db: 0.0, calibration: None }
+    }
+
+    /// Elements of the array
+    pub fn elements(&self) -> &[Box<dyn ElementIface>] {
+        &self.elements
+    }
+
+    /// Mutable access to the elements of the array
+    pub fn elements_mut(&mut self) -> &mut [Box<dyn ElementIface>] {
+        &mut self.elements
+    }
+
+    /// Complex gain of each element at a direction, before they are summed
+    ///
+    /// Each contribution includes the element's positional phase and weight,
+    /// so the sum is the array gain. Useful for finding which elements
+    /// dominate a lobe.
+    ///
+    pub fn element_contributions(&self, frequency: f64, theta: f64, phi: f64) -> Vec<Complex<f64>> {
+        #[cfg(feature = "simd")]
+        let gains = simd::element_gains(self, frequency, theta, phi);
+
+        #[cfg(not(feature = "simd"))]
+        let gains: Vec<Complex<f64>> = self.elements
             .iter()
-            .map(|n| n.get_gain(frequency, phi, theta).unwrap())
+            .enumerate()
+            .map(|(idx, n)| element_response(n.as_ref(), frequency, theta, phi).unwrap() * effective_weight(self, idx, n.weight()))
             .collect();
+
+        gains
+    }
+
+    /// Loss of the feed network (dB)
+    pub fn feed_loss_db(&self) -> f64 {
+        self.feed_loss_db
+    }
+
+    /// Set the loss of the combiner/phase shifter network (dB, positive is loss)
+    ///
+    /// The loss is the same in every direction. It only affects
+    /// `realized_gain`; `get_gain` stays lossless.
+    ///
+    pub fn set_feed_loss_db(&mut self, feed_loss_db: f64) {
+        self.feed_loss_db = feed_loss_db;
+    }
+
+    /// Complex gain including the feed network loss
+    pub fn realized_gain(&self, frequency: f64, theta: f64, phi: f64) -> Option<Complex<f64>> {
+        let scale = float::powf(10.0, -self.feed_loss_db / 20.0);
+        Some( self.get_gain(frequency, theta, phi)? * scale )
+    }
+}
+
+/// Weight of element `idx` out of `count`
+type TaperFn = Box<dyn Fn(usize, usize) -> Complex<f64>>;
+
+/// Builds an `ElementArray` from its elements, optionally weighting them on the way
+#[derive(Default)]
+pub struct ElementArrayBuilder {
+    elements: Vec<Box<dyn ElementIface>>,
+    taper: Option<TaperFn>,
+}
+
+impl ElementArrayBuilder {
+    /// Elements of the array
+    pub fn elements(&mut self, elements: Vec<Box<dyn ElementIface>>) -> &mut Self {
+        self.elements = elements;
+        self
+    }
+
+    /// Weight element `idx` of `count` with `taper(idx, count)` when the array is built
+    ///
+    /// The taper replaces the weight each element was created with.
+    ///
+    pub fn with_taper(&mut self, taper: impl Fn(usize, usize) -> Complex<f64> + 'static) -> &mut Self {
+        self.taper = Some(Box::new(taper));
+        self
+    }
+
+    /// Build the array
+    pub fn build(&self) -> ElementArray {
+        let mut elements = self.elements.clone();
+        if let Some(taper) = &self.taper {
+            let count = elements.len();
+            for (idx, n) in elements.iter_mut().enumerate() {
+                n.set_weight(taper(idx, count));
+            }
+        }
+        ElementArray::new(elements)
+    }
+}
+
+impl GainIface for ElementArray {
+    fn get_gain(&self, frequency: f64, theta: f64, phi: f64) -> Option<Complex<f64>> {
+        let gains = self.element_contributions(frequency, theta, phi);
         Some( gains.iter().sum() )
     }
 }
+
+impl ArrayIface for ElementArray {
+    fn num_elements(&self) -> usize {
+        self.elements.len()
+    }
+
+    fn flat_elements(&self) -> Vec<Box<dyn ElementIface>> {
+        let mut elements = self.elements.clone();
+        for (idx, n) in elements.iter_mut().enumerate() {
+            let weight = effective_weight(self, idx, n.weight());
+            n.set_weight(weight);
+        }
+        elements
+    }
+}
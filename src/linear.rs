@@ -0,0 +1,36 @@
+//! Linear array factories
+//!
+//! Elements are placed along the x axis starting at the origin, which is the
+//! layout used throughout the tests.
+
+use alloc::boxed::Box;
+
+use crate::{ElementArray, ElementIface, Point, SPEED_OF_LIGHT};
+
+impl ElementArray {
+    /// Evenly spaced linear array along the x axis
+    ///
+    /// `element` creates the element at each position and `spacing` is in
+    /// meters.
+    ///
+    pub fn uniform_linear<F>(num: usize, spacing: f64, element: F) -> ElementArray
+    where
+        F: Fn(Point) -> Box<dyn ElementIface>,
+    {
+        ElementArray::new( (0..num)
+            .map(|idx| element(Point { x: idx as f64 * spacing, y: 0.0, z: 0.0 }))
+            .collect() )
+    }
+
+    /// Evenly spaced linear array with the spacing given in wavelengths
+    ///
+    /// The spacing is converted to meters at `design_frequency`.
+    ///
+    pub fn uniform_linear_wl<F>(num: usize, spacing_wl: f64, design_frequency: f64, element: F) -> ElementArray
+    where
+        F: Fn(Point) -> Box<dyn ElementIface>,
+    {
+        let wavelength = SPEED_OF_LIGHT / design_frequency;
+        ElementArray::uniform_linear(num, spacing_wl * wavelength, element)
+    }
+}
@@ -0,0 +1,187 @@
+//! Special functions that aren't in std
+//!
+//! The Bessel function approximations are the rational/asymptotic fits from
+//! Numerical Recipes, good to about 1e-8 which is plenty for tapers. The sine
+//! and cosine integrals follow the Numerical Recipes series/continued fraction.
+//! There is also a small dense linear solver for the optimizers.
+
+use num::complex::Complex;
+
+use crate::PI;
+
+/// Bessel function of the first kind, order 0
+pub(crate) fn bessel_j0(x: f64) -> f64 {
+    let ax = x.abs();
+    if ax < 8.0 {
+        let y = x * x;
+        let num = 57568490574.0
+            + y * (-13362590354.0 + y * (651619640.7 + y * (-11214424.18 + y * (77392.33017 + y * (-184.9052456)))));
+        let den = 57568490411.0
+            + y * (1029532985.0 + y * (9494680.718 + y * (59272.64853 + y * (267.8532712 + y))));
+        num / den
+    } else {
+        let z = 8.0 / ax;
+        let y = z * z;
+        let xx = ax - std::f64::consts::FRAC_PI_4;
+        let p = 1.0 + y * (-0.1098628627e-2 + y * (0.2734510407e-4 + y * (-0.2073370639e-5 + y * 0.2093887211e-6)));
+        let q = -0.1562499995e-1
+            + y * (0.1430488765e-3 + y * (-0.6911147651e-5 + y * (0.7621095161e-6 - y * 0.934935152e-7)));
+        (std::f64::consts::FRAC_2_PI / ax).sqrt() * (xx.cos() * p - z * xx.sin() * q)
+    }
+}
+
+/// Bessel function of the first kind, order 1
+pub(crate) fn bessel_j1(x: f64) -> f64 {
+    let ax = x.abs();
+    if ax < 8.0 {
+        let y = x * x;
+        let num = x
+            * (72362614232.0
+                + y * (-7895059235.0 + y * (242396853.1 + y * (-2972611.439 + y * (15704.48260 + y * (-30.16036606))))));
+        let den = 144725228442.0
+            + y * (2300535178.0 + y * (18583304.74 + y * (99447.43394 + y * (376.9991397 + y))));
+        num / den
+    } else {
+        let z = 8.0 / ax;
+        let y = z * z;
+        let xx = ax - 3.0 * std::f64::consts::FRAC_PI_4;
+        let p = 1.0 + y * (0.183105e-2 + y * (-0.3516396496e-4 + y * (0.2457520174e-5 + y * (-0.240337019e-6))));
+        let q = 0.04687499995
+            + y * (-0.2002690873e-3 + y * (0.8449199096e-5 + y * (-0.88228987e-6 + y * 0.105787412e-6)));
+        let value = (std::f64::consts::FRAC_2_PI / ax).sqrt() * (xx.cos() * p - z * xx.sin() * q);
+        if x < 0.0 {
+            -value
+        } else {
+            value
+        }
+    }
+}
+
+/// The `m`th positive zero of J1 (m starts at 1)
+pub(crate) fn bessel_j1_zero(m: usize) -> f64 {
+    // McMahon's expansion gets close enough for Newton to finish the job
+    let beta = (m as f64 + 0.25) * PI;
+    let mut x = beta - 3.0 / (8.0 * beta);
+    for _ in 0..20 {
+        // J1'(x) = J0(x) - J1(x) / x
+        let step = bessel_j1(x) / (bessel_j0(x) - bessel_j1(x) / x);
+        x -= step;
+        if step.abs() < 1e-12 {
+            break;
+        }
+    }
+    x
+}
+
+/// Sine and cosine integrals, (Si(x), Ci(x)), for x > 0
+pub(crate) fn sine_cosine_integrals(x: f64) -> (f64, f64) {
+    const EULER: f64 = 0.577_215_664_901_532_9;
+    const MAX_ITER: usize = 100;
+    let eps = f64::EPSILON;
+
+    if x > 2.0 {
+        // continued fraction for E1(ix) with the modified Lentz method
+        let mut b = Complex::new(1.0, x);
+        let mut c = Complex::new(1.0 / f64::MIN_POSITIVE, 0.0);
+        let mut d = 1.0 / b;
+        let mut h = d;
+        for i in 2..MAX_ITER {
+            let a = -((i - 1) as f64).powi(2);
+            b += 2.0;
+            d = 1.0 / (a * d + b);
+            c = b + a / c;
+            let delta = c * d;
+            h *= delta;
+            if (delta.re - 1.0).abs() + delta.im.abs() < eps {
+                break;
+            }
+        }
+        let h = Complex::new(x.cos(), -x.sin()) * h;
+        (std::f64::consts::FRAC_PI_2 + h.im, -h.re)
+    } else {
+        // power series, alternating between the odd (Si) and even (Ci) terms
+        let (mut sum, mut sum_s, mut sum_c) = (0.0, 0.0, 0.0);
+        let mut sign = 1.0;
+        let mut fact = 1.0;
+        let mut odd = true;
+        for k in 1..MAX_ITER {
+            fact *= x / k as f64;
+            let term = fact / k as f64;
+            sum += sign * term;
+            let err = term / sum.abs();
+            if odd {
+                sign = -sign;
+                sum_s = sum;
+                sum = sum_c;
+            } else {
+                sum_c = sum;
+                sum = sum_s;
+            }
+            if err < eps {
+                break;
+            }
+            odd = !odd;
+        }
+        (sum_s, sum_c + x.ln() + EULER)
+    }
+}
+
+/// Solve `matrix * x = rhs` by Gaussian elimination with partial pivoting
+///
+/// `matrix` is square, one row per entry of `rhs`. Returns `None` if the
+/// matrix is singular.
+pub(crate) fn solve_linear(mut matrix: Vec<Vec<f64>>, mut rhs: Vec<f64>) -> Option<Vec<f64>> {
+    let size = rhs.len();
+    for col in 0..size {
+        let pivot = (col..size).max_by(|a, b| matrix[*a][col].abs().total_cmp(&matrix[*b][col].abs()))?;
+        if matrix[pivot][col] == 0.0 {
+            return None;
+        }
+        matrix.swap(col, pivot);
+        rhs.swap(col, pivot);
+
+        let pivot_row = matrix[col].clone();
+        for row in col + 1..size {
+            let factor = matrix[row][col] / pivot_row[col];
+            for (entry, p) in matrix[row].iter_mut().zip(&pivot_row).skip(col) {
+                *entry -= factor * p;
+            }
+            rhs[row] -= factor * rhs[col];
+        }
+    }
+
+    let mut x = vec![0.0; size];
+    for row in (0..size).rev() {
+        let sum: f64 = (row + 1..size).map(|idx| matrix[row][idx] * x[idx]).sum();
+        x[row] = (rhs[row] - sum) / matrix[row][row];
+    }
+    Some(x)
+}
+
+/// Solve the complex system `matrix * x = rhs`
+///
+/// Done as the real system `[re -im; im re] [x_re; x_im] = [b_re; b_im]`,
+/// twice the size. Returns `None` if the matrix is singular.
+pub(crate) fn solve_complex(matrix: &[Vec<Complex<f64>>], rhs: &[Complex<f64>]) -> Option<Vec<Complex<f64>>> {
+    let count = rhs.len();
+    let mut real = vec![vec![0.0; 2 * count]; 2 * count];
+    for (m, row) in matrix.iter().enumerate() {
+        for (n, entry) in row.iter().enumerate() {
+            real[m][n] = entry.re;
+            real[m][n + count] = -entry.im;
+            real[m + count][n] = entry.im;
+            real[m + count][n + count] = entry.re;
+        }
+    }
+    let rhs: Vec<f64> = rhs.iter().map(|b| b.re).chain(rhs.iter().map(|b| b.im)).collect();
+    let x = solve_linear(real, rhs)?;
+    Some( (0..count).map(|idx| Complex::new(x[idx], x[idx + count])).collect() )
+}
+
+/// Remove the 2*PI jumps between consecutive phases (radians), in place
+pub(crate) fn unwrap_phase(phases: &mut [f64]) {
+    for idx in 1..phases.len() {
+        let step = phases[idx] - phases[idx - 1];
+        phases[idx] -= 2.0 * PI * ((step + PI) / (2.0 * PI)).floor();
+    }
+}
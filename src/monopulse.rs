@@ -0,0 +1,91 @@
+//! Monopulse (sum/difference) processing
+//!
+//! A monopulse tracker splits the array into two halves and compares them.
+//! The sum channel is the normal array pattern, the difference channel has a
+//! null at boresight that changes sign as a target moves across it.
+
+use num::complex::Complex;
+
+use crate::{effective_weight, element_response, ElementArray, GainIface, PatternGrid, Point, PI};
+
+/// Cartesian axis used to split an array
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Axis {
+    /// Split on the sign of x
+    X,
+    /// Split on the sign of y
+    Y,
+    /// Split on the sign of z
+    Z,
+}
+
+impl Axis {
+    fn coordinate(&self, pnt: &Point) -> f64 {
+        match self {
+            Axis::X => pnt.x,
+            Axis::Y => pnt.y,
+            Axis::Z => pnt.z,
+        }
+    }
+}
+
+impl ElementArray {
+    /// Difference channel for a single direction
+    ///
+    /// Elements are split about the centroid of the array along `axis`.
+    /// Elements sitting exactly on the centroid belong to neither half.
+    ///
+    fn difference_gain(&self, frequency: f64, axis: Axis, theta: f64, phi: f64) -> Complex<f64> {
+        let center = self.elements
+            .iter()
+            .map(|n| axis.coordinate(n.position()))
+            .sum::<f64>() / self.elements.len() as f64;
+
+        self.elements
+            .iter()
+            .enumerate()
+            .map(|(idx, n)| {
+                let offset = axis.coordinate(n.position()) - center;
+                let gain = element_response(n.as_ref(), frequency, theta, phi).unwrap() * effective_weight(self, idx, n.weight());
+                if offset > 0.0 {
+                    gain
+                } else if offset < 0.0 {
+                    -gain
+                } else {
+                    Complex::new(0.0, 0.0)
+                }
+            })
+            .sum()
+    }
+
+    /// Build the monopulse difference pattern
+    ///
+    /// The array is split in two about `axis` and the sum pattern of one half
+    /// is subtracted from the other. The result has a null at boresight that
+    /// is used alongside the normal sum pattern for tracking.
+    ///
+    pub fn monopulse_difference(&self, frequency: f64, axis: Axis, theta_step: f64, phi_step: f64) -> PatternGrid {
+        PatternGrid::from_fn(theta_step, phi_step, |theta, phi| {
+            self.difference_gain(frequency, axis, theta, phi)
+        })
+    }
+
+    /// Normalized difference-over-sum slope at boresight (per radian)
+    ///
+    /// This sets the tracking sensitivity of the monopulse channel. Boresight
+    /// is +z when splitting about X or Y, and +x when splitting about Z. The
+    /// slope is taken by tilting away from boresight towards `axis`.
+    ///
+    pub fn monopulse_slope(&self, frequency: f64, axis: Axis) -> f64 {
+        let delta = 1e-4;
+        let (theta, phi) = match axis {
+            Axis::X => (delta, 0.0),
+            Axis::Y => (delta, PI / 2.0),
+            Axis::Z => (PI / 2.0 - delta, 0.0),
+        };
+
+        let sum = self.get_gain(frequency, theta, phi).unwrap();
+        let diff = self.difference_gain(frequency, axis, theta, phi);
+        (diff / sum).norm() / delta
+    }
+}
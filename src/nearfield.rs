@@ -0,0 +1,142 @@
+//! Near-field evaluation
+//!
+//! Instead of a direction, the field is evaluated at a point in space. Every
+//! element contributes its own pattern, looked up in the direction from the
+//! element to the point, with a spherical wave `exp(-jkr)/r` on top. Under
+//! the negative phase convention the spherical wave is `exp(+jkr)/r`, so the
+//! near field still tends to the far-field phase.
+//!
+//! This is only valid outside the reactive near field of each element: the
+//! element patterns are still far-field patterns, so results are trustworthy
+//! a few wavelengths away from every element. Closer than that the numbers
+//! are an approximation, and at the element itself the `1/r` term is
+//! singular, which is reported as an error instead of returning inf/NaN.
+
+use std::fmt;
+
+use num::complex::Complex;
+
+use crate::{delay_phase, effective_weight, phase_convention, ElementArray, Point, I, PI, SPEED_OF_LIGHT};
+
+/// Errors from evaluating gain
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GainError {
+    /// The calculation is singular at the element at this index, e.g. the
+    /// observation point or another element sits on top of it
+    Singular(usize),
+    /// The element at this index can't provide a gain for this direction
+    NoGain(usize),
+}
+
+impl fmt::Display for GainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GainError::Singular(idx) => write!(f, "singular at element {}", idx),
+            GainError::NoGain(idx) => write!(f, "element {} has no gain in this direction", idx),
+        }
+    }
+}
+
+impl std::error::Error for GainError {}
+
+/// Points closer than this fraction of a wavelength to an element are singular
+const MIN_DISTANCE_WAVELENGTHS: f64 = 1e-6;
+
+impl ElementArray {
+    /// Complex field at a point in space
+    ///
+    /// See the module documentation for where this is valid. Points within a
+    /// millionth of a wavelength of an element return `GainError::Singular`.
+    ///
+    pub fn get_gain_at_point(&self, frequency: f64, point: &Point) -> Result<Complex<f64>, GainError> {
+        let wavelength = SPEED_OF_LIGHT / frequency;
+        let k = phase_convention().sign() * 2.0 * PI / wavelength;
+
+        let mut total = Complex::new(0.0, 0.0);
+        for (idx, n) in self.elements.iter().enumerate() {
+            let dx = point.x - n.position().x;
+            let dy = point.y - n.position().y;
+            let dz = point.z - n.position().z;
+            let r = (dx * dx + dy * dy + dz * dz).sqrt();
+            if r < MIN_DISTANCE_WAVELENGTHS * wavelength {
+                return Err(GainError::Singular(idx));
+            }
+
+            let theta = (dz / r).clamp(-1.0, 1.0).acos();
+            let phi = dy.atan2(dx).rem_euclid(2.0 * PI);
+            let pattern = n.pattern(frequency, theta, phi).ok_or(GainError::NoGain(idx))?;
+
+            total += pattern * effective_weight(self, idx, n.weight()) * delay_phase(n.delay(), frequency) * (-I * k * r).exp() / r;
+        }
+        Ok(total)
+    }
+}
+
+/// Complex near field sampled on a plane of constant z
+///
+/// The plane is centered on the z axis; x and y run from `-extent / 2` to
+/// `extent / 2` in each direction. Samples are stored one y row at a time.
+#[derive(Clone, Debug)]
+pub struct FieldPlane {
+    z: f64,
+    x: Vec<f64>,
+    y: Vec<f64>,
+    // values[y_idx][x_idx]
+    values: Vec<Vec<Complex<f64>>>,
+}
+
+impl FieldPlane {
+    /// Distance of the plane from the origin along z (meters)
+    pub fn z(&self) -> f64 {
+        self.z
+    }
+
+    /// x of every column (meters)
+    pub fn x(&self) -> &[f64] {
+        &self.x
+    }
+
+    /// y of every row (meters)
+    pub fn y(&self) -> &[f64] {
+        &self.y
+    }
+
+    /// Complex field at the given sample
+    pub fn get(&self, y_idx: usize, x_idx: usize) -> Complex<f64> {
+        self.values[y_idx][x_idx]
+    }
+
+    /// All samples, one y row at a time
+    pub fn values(&self) -> &[Vec<Complex<f64>>] {
+        &self.values
+    }
+}
+
+/// Evenly spaced samples covering `-extent / 2..=extent / 2`
+fn plane_axis(extent: f64, step: f64) -> Vec<f64> {
+    let count = (extent / step).round() as usize;
+    (0..=count).map(|idx| -extent / 2.0 + idx as f64 * step).collect()
+}
+
+impl ElementArray {
+    /// Near field on a planar scan grid `z_distance` meters along z
+    ///
+    /// `extent` is the `(x, y)` size of the plane (meters) and `step` the
+    /// spacing between samples, as used for a planar near-field range. Each
+    /// sample is `get_gain_at_point`, so the first error from any sample is
+    /// returned.
+    ///
+    pub fn near_field_plane(&self, frequency: f64, z_distance: f64, extent: (f64, f64), step: f64) -> Result<FieldPlane, GainError> {
+        let x = plane_axis(extent.0, step);
+        let y = plane_axis(extent.1, step);
+        let values = y
+            .iter()
+            .map(|y| {
+                x.iter()
+                    .map(|x| self.get_gain_at_point(frequency, &Point { x: *x, y: *y, z: z_distance }))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(FieldPlane { z: z_distance, x, y, values })
+    }
+}
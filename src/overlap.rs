@@ -0,0 +1,37 @@
+//! Pattern overlap between two antennas
+//!
+//! How much two antennas "see" the same directions, e.g. for co-site
+//! interference, is measured by the normalized overlap integral of their
+//! complex far fields over the sphere.
+
+use num::complex::Complex;
+
+use crate::{GainIface, PatternGrid};
+
+/// Normalized overlap of two far-field patterns, from 0 to 1
+///
+/// `|∮ a conj(b) dΩ| / sqrt(∮ |a|^2 dΩ ∮ |b|^2 dΩ)`, integrated on a sphere
+/// grid with the given steps (radians). Identical patterns give 1 and
+/// patterns that don't overlap give 0. Directions where either antenna has
+/// no gain count as zero.
+///
+pub fn pattern_coupling(a: &dyn GainIface, b: &dyn GainIface, frequency: f64, theta_step: f64, phi_step: f64) -> f64 {
+    let grid_a = sample(a, frequency, theta_step, phi_step);
+    let grid_b = sample(b, frequency, theta_step, phi_step);
+
+    let (mut cross, mut power_a, mut power_b) = (Complex::new(0.0, 0.0), 0.0, 0.0);
+    for (row_a, row_b) in grid_a.values().iter().zip(grid_b.values()) {
+        for (theta_idx, (ga, gb)) in row_a.iter().zip(row_b).enumerate() {
+            let d_omega = grid_a.theta(theta_idx).sin();
+            cross += ga * gb.conj() * d_omega;
+            power_a += ga.norm_sqr() * d_omega;
+            power_b += gb.norm_sqr() * d_omega;
+        }
+    }
+    cross.norm() / (power_a * power_b).sqrt()
+}
+
+/// Sample a gain over the sphere, with zero where there is none
+fn sample(gain: &dyn GainIface, frequency: f64, theta_step: f64, phi_step: f64) -> PatternGrid {
+    PatternGrid::from_fn(theta_step, phi_step, |theta, phi| gain.get_gain(frequency, theta, phi).unwrap_or_default())
+}
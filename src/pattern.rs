@@ -0,0 +1,300 @@
+//! Sampled antenna patterns
+//!
+//! Most of the analysis in this crate is done on a pattern that has been
+//! sampled over the whole sphere rather than on the gain function itself.
+
+use std::fmt;
+
+use num::complex::Complex;
+
+use crate::{PI, SPEED_OF_LIGHT};
+
+/// Problems found in a sampled pattern
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatternError {
+    /// Samples that are NaN or infinite, as `(phi_idx, theta_idx)` like `PatternGrid::get`
+    NonFinite(Vec<(usize, usize)>),
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatternError::NonFinite(samples) => {
+                let (phi_idx, theta_idx) = samples[0];
+                write!(f, "{} non-finite samples, the first at phi_idx {}, theta_idx {}", samples.len(), phi_idx, theta_idx)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PatternError {}
+
+/// Complex gain sampled on a regular theta/phi grid
+///
+/// Theta runs from 0 to PI (both ends included) and phi runs from 0 up to,
+/// but not including, 2*PI. Samples are stored one phi row at a time, which
+/// is the same layout the H5 writer in the tests uses.
+#[derive(Clone, Debug)]
+pub struct PatternGrid {
+    theta_step: f64,
+    phi_step: f64,
+    // values[phi_idx][theta_idx]
+    values: Vec<Vec<Complex<f64>>>,
+}
+
+impl PatternGrid {
+    /// Build a grid by evaluating `f(theta, phi)` at every sample
+    pub fn from_fn<F>(theta_step: f64, phi_step: f64, mut f: F) -> PatternGrid
+    where
+        F: FnMut(f64, f64) -> Complex<f64>,
+    {
+        let num_theta = (PI / theta_step).round() as usize + 1;
+        let num_phi = ((2.0 * PI / phi_step).round() as usize).max(1);
+
+        let values = (0..num_phi)
+            .map(|phi_idx| {
+                let phi = phi_idx as f64 * phi_step;
+                (0..num_theta)
+                    .map(|theta_idx| f(theta_idx as f64 * theta_step, phi))
+                    .collect()
+            })
+            .collect();
+
+        PatternGrid { theta_step, phi_step, values }
+    }
+
+    /// Grid from samples already laid out one phi row at a time
+    pub(crate) fn from_values(theta_step: f64, phi_step: f64, values: Vec<Vec<Complex<f64>>>) -> PatternGrid {
+        PatternGrid { theta_step, phi_step, values }
+    }
+
+    /// Spacing between theta samples (radians)
+    pub fn theta_step(&self) -> f64 {
+        self.theta_step
+    }
+
+    /// Spacing between phi samples (radians)
+    pub fn phi_step(&self) -> f64 {
+        self.phi_step
+    }
+
+    /// Number of theta samples in each phi row
+    pub fn num_theta(&self) -> usize {
+        self.values[0].len()
+    }
+
+    /// Number of phi rows
+    pub fn num_phi(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Theta of the given sample index (radians)
+    pub fn theta(&self, theta_idx: usize) -> f64 {
+        theta_idx as f64 * self.theta_step
+    }
+
+    /// Phi of the given sample index (radians)
+    pub fn phi(&self, phi_idx: usize) -> f64 {
+        phi_idx as f64 * self.phi_step
+    }
+
+    /// Complex gain at the given sample
+    pub fn get(&self, phi_idx: usize, theta_idx: usize) -> Complex<f64> {
+        self.values[phi_idx][theta_idx]
+    }
+
+    /// All samples, one phi row at a time
+    pub fn values(&self) -> &[Vec<Complex<f64>>] {
+        &self.values
+    }
+
+    /// Check that every sample is finite
+    ///
+    /// A NaN from an unguarded singularity (a sinc at zero, `1 / sin(theta)`
+    /// at the poles) would otherwise spread silently through every integral
+    /// of the pattern. All the offending samples are reported.
+    ///
+    pub fn validate(&self) -> Result<(), PatternError> {
+        let bad: Vec<(usize, usize)> = self.values
+            .iter()
+            .enumerate()
+            .flat_map(|(phi_idx, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(|(_, value)| !value.is_finite())
+                    .map(move |(theta_idx, _)| (phi_idx, theta_idx))
+            })
+            .collect();
+        if bad.is_empty() {
+            Ok(())
+        } else {
+            Err(PatternError::NonFinite(bad))
+        }
+    }
+
+    /// Bilinearly interpolate the complex gain at any direction
+    ///
+    /// Phi wraps around and theta is clamped to the sampled range.
+    ///
+    pub fn interpolate(&self, theta: f64, phi: f64) -> Complex<f64> {
+        let num_theta = self.num_theta();
+        let num_phi = self.num_phi();
+
+        let theta_pos = (theta / self.theta_step).clamp(0.0, (num_theta - 1) as f64);
+        let theta_idx = (theta_pos.floor() as usize).min(num_theta.saturating_sub(2));
+        let theta_frac = theta_pos - theta_idx as f64;
+        let next_theta_idx = (theta_idx + 1).min(num_theta - 1);
+
+        let phi_pos = phi.rem_euclid(2.0 * PI) / self.phi_step;
+        let phi_idx = phi_pos.floor() as usize % num_phi;
+        let phi_frac = phi_pos - phi_pos.floor();
+        let next_phi_idx = (phi_idx + 1) % num_phi;
+
+        let row = |idx: usize| {
+            self.values[idx][theta_idx] * (1.0 - theta_frac) + self.values[idx][next_theta_idx] * theta_frac
+        };
+        row(phi_idx) * (1.0 - phi_frac) + row(next_phi_idx) * phi_frac
+    }
+
+    /// Integrate `f(theta, phi, value)` over the sphere
+    fn integrate<F>(&self, f: F) -> f64
+    where
+        F: Fn(f64, f64, Complex<f64>) -> f64,
+    {
+        let mut total = 0.0;
+        for (phi_idx, row) in self.values.iter().enumerate() {
+            let phi = self.phi(phi_idx);
+            for (theta_idx, value) in row.iter().enumerate() {
+                let theta = self.theta(theta_idx);
+                total += f(theta, phi, *value) * theta.sin();
+            }
+        }
+        total * self.theta_step * self.phi_step
+    }
+
+    /// Total power radiated, the integral of |gain|^2 over the sphere
+    ///
+    /// An isotropic pattern with unit gain radiates 4*PI.
+    ///
+    pub fn radiated_power(&self) -> f64 {
+        self.integrate(|_, _, value| value.norm_sqr())
+    }
+
+    /// Antenna temperature seen through this pattern (Kelvin)
+    ///
+    /// The brightness temperature `brightness(theta, phi)` of the sky/ground
+    /// is weighted by the normalized power pattern and integrated over the
+    /// sphere.
+    ///
+    pub fn antenna_temperature(&self, brightness: impl Fn(f64, f64) -> f64) -> f64 {
+        let weighted = self.integrate(|theta, phi, value| brightness(theta, phi) * value.norm_sqr());
+        weighted / self.integrate(|_, _, value| value.norm_sqr())
+    }
+
+    /// Largest power sample in the grid
+    pub(crate) fn peak_power(&self) -> f64 {
+        self.values
+            .iter()
+            .flatten()
+            .map(|value| value.norm_sqr())
+            .fold(0.0, f64::max)
+    }
+
+    /// (theta, phi) points where the gain, relative to the peak, crosses `level_db`
+    ///
+    /// Same as `normalize_db(...).contour(level_db)`; see `PatternGridDb::contour`.
+    ///
+    pub fn contour(&self, level_db: f64) -> Vec<(f64, f64)> {
+        // a floor far below the level so nulls don't pull the crossings around
+        self.normalize_db(level_db.min(0.0) - 300.0).contour(level_db)
+    }
+
+    /// (phi_idx, theta_idx) of the largest power sample
+    pub(crate) fn peak_index(&self) -> (usize, usize) {
+        let mut best = (0, 0);
+        for (phi_idx, row) in self.values.iter().enumerate() {
+            for (theta_idx, value) in row.iter().enumerate() {
+                if value.norm_sqr() > self.values[best.0][best.1].norm_sqr() {
+                    best = (phi_idx, theta_idx);
+                }
+            }
+        }
+        best
+    }
+
+    /// Which samples belong to the main lobe, `[phi_idx][theta_idx]`
+    ///
+    /// The main lobe is everything reachable from the peak by stepping to
+    /// neighbouring samples without the power going back up. Phi wraps.
+    pub(crate) fn main_lobe_mask(&self) -> Vec<Vec<bool>> {
+        let (num_phi, num_theta) = (self.num_phi(), self.num_theta());
+        let mut mask = vec![vec![false; num_theta]; num_phi];
+        let peak = self.peak_index();
+        mask[peak.0][peak.1] = true;
+        let mut stack = vec![peak];
+        while let Some((phi_idx, theta_idx)) = stack.pop() {
+            let power = self.values[phi_idx][theta_idx].norm_sqr();
+            let mut neighbours = vec![((phi_idx + 1) % num_phi, theta_idx), ((phi_idx + num_phi - 1) % num_phi, theta_idx)];
+            if theta_idx > 0 {
+                neighbours.push((phi_idx, theta_idx - 1));
+            }
+            if theta_idx + 1 < num_theta {
+                neighbours.push((phi_idx, theta_idx + 1));
+            }
+            for (p, t) in neighbours {
+                if !mask[p][t] && self.values[p][t].norm_sqr() <= power {
+                    mask[p][t] = true;
+                    stack.push((p, t));
+                }
+            }
+        }
+        mask
+    }
+
+    /// Largest sample outside the main lobe, relative to the peak (dB)
+    pub(crate) fn peak_sidelobe_db(&self) -> f64 {
+        let mask = self.main_lobe_mask();
+        let sidelobe = self.values
+            .iter()
+            .zip(&mask)
+            .flat_map(|(row, mask_row)| row.iter().zip(mask_row))
+            .filter(|(_, in_lobe)| !**in_lobe)
+            .map(|(value, _)| value.norm_sqr())
+            .fold(0.0, f64::max);
+        10.0 * (sidelobe / self.peak_power()).log10()
+    }
+
+    /// Full cone angle (radians) with the same solid angle as the main lobe above half power
+    ///
+    /// Works the same wherever the beam points, and equals the usual
+    /// half-power beamwidth for a circular pencil beam.
+    pub(crate) fn equivalent_hpbw(&self) -> f64 {
+        let half = self.peak_power() / 2.0;
+        let mask = self.main_lobe_mask();
+        let mut solid_angle = 0.0;
+        for (phi_idx, row) in self.values.iter().enumerate() {
+            for (theta_idx, value) in row.iter().enumerate() {
+                if mask[phi_idx][theta_idx] && value.norm_sqr() >= half {
+                    solid_angle += self.theta(theta_idx).sin() * self.theta_step * self.phi_step;
+                }
+            }
+        }
+        2.0 * (1.0 - solid_angle / (2.0 * PI)).clamp(-1.0, 1.0).acos()
+    }
+
+    /// Beam solid angle (steradians)
+    ///
+    /// The integral of the power pattern, normalized to its peak, over the
+    /// sphere. An isotropic pattern gives 4*PI.
+    ///
+    pub fn beam_solid_angle(&self) -> f64 {
+        let peak = self.peak_power();
+        self.integrate(|_, _, value| value.norm_sqr() / peak)
+    }
+
+    /// Effective aperture (square meters), `wavelength^2 / beam_solid_angle`
+    pub fn effective_aperture(&self, frequency: f64) -> f64 {
+        let wavelength = SPEED_OF_LIGHT / frequency;
+        wavelength.powi(2) / self.beam_solid_angle()
+    }
+}
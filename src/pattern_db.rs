@@ -0,0 +1,164 @@
+//! Patterns in normalized decibels
+//!
+//! Plots, contours and beamwidths all work on the normalized power pattern in
+//! dB. Converting once into a `PatternGridDb` saves redoing the conversion
+//! for every query.
+
+use std::ops::Index;
+
+use crate::PatternGrid;
+
+/// Power pattern (dB) on the same grid as the `PatternGrid` it came from
+///
+/// From `PatternGrid::normalize_db` the peak is 0 dB; maps of absolute
+/// levels such as `ElementArray::eirp_map` are shifted. Nothing is below the
+/// floor.
+#[derive(Clone, Debug)]
+pub struct PatternGridDb {
+    theta_step: f64,
+    phi_step: f64,
+    floor_db: f64,
+    // values[phi_idx][theta_idx]
+    values: Vec<Vec<f64>>,
+}
+
+impl PatternGrid {
+    /// Power pattern normalized to its peak, in dB
+    ///
+    /// Samples below `floor_db` (e.g. -60.0), including nulls, are clamped to
+    /// the floor.
+    ///
+    pub fn normalize_db(&self, floor_db: f64) -> PatternGridDb {
+        let peak = self.peak_power();
+        let values = self.values()
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|value| (10.0 * (value.norm_sqr() / peak).log10()).max(floor_db))
+                    .collect()
+            })
+            .collect();
+        PatternGridDb {
+            theta_step: self.theta_step(),
+            phi_step: self.phi_step(),
+            floor_db,
+            values,
+        }
+    }
+}
+
+impl PatternGridDb {
+    /// The same pattern with every sample (and the floor) raised by `offset_db`
+    pub(crate) fn offset(mut self, offset_db: f64) -> PatternGridDb {
+        for row in self.values.iter_mut() {
+            for value in row.iter_mut() {
+                *value += offset_db;
+            }
+        }
+        self.floor_db += offset_db;
+        self
+    }
+
+    /// Spacing between theta samples (radians)
+    pub fn theta_step(&self) -> f64 {
+        self.theta_step
+    }
+
+    /// Spacing between phi samples (radians)
+    pub fn phi_step(&self) -> f64 {
+        self.phi_step
+    }
+
+    /// Lowest value in the grid (dB)
+    pub fn floor_db(&self) -> f64 {
+        self.floor_db
+    }
+
+    /// Number of theta samples in each phi row
+    pub fn num_theta(&self) -> usize {
+        self.values[0].len()
+    }
+
+    /// Number of phi rows
+    pub fn num_phi(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Theta of the given sample index (radians)
+    pub fn theta(&self, theta_idx: usize) -> f64 {
+        theta_idx as f64 * self.theta_step
+    }
+
+    /// Phi of the given sample index (radians)
+    pub fn phi(&self, phi_idx: usize) -> f64 {
+        phi_idx as f64 * self.phi_step
+    }
+
+    /// All samples (dB), one phi row at a time
+    pub fn values(&self) -> &[Vec<f64>] {
+        &self.values
+    }
+
+    /// (theta, phi) of the peak sample (radians)
+    pub fn peak_angle(&self) -> (f64, f64) {
+        let mut best = (0, 0);
+        for (phi_idx, row) in self.values.iter().enumerate() {
+            for (theta_idx, value) in row.iter().enumerate() {
+                if *value > self.values[best.0][best.1] {
+                    best = (phi_idx, theta_idx);
+                }
+            }
+        }
+        (self.theta(best.1), self.phi(best.0))
+    }
+
+    /// (theta, phi) points where the pattern crosses `level_db`
+    ///
+    /// These are the contour vertices marching squares would find: every
+    /// edge between neighbouring samples (along theta, and along phi with
+    /// wrap-around) that straddles the level contributes one point, linearly
+    /// interpolated between the two samples. The points are not ordered
+    /// along the contour.
+    ///
+    pub fn contour(&self, level_db: f64) -> Vec<(f64, f64)> {
+        let num_phi = self.num_phi();
+        let mut points = Vec::new();
+        for (phi_idx, row) in self.values.iter().enumerate() {
+            let phi = self.phi(phi_idx);
+            for (theta_idx, value) in row.iter().enumerate() {
+                let theta = self.theta(theta_idx);
+                let a = value - level_db;
+                if a == 0.0 {
+                    points.push((theta, phi));
+                    continue;
+                }
+
+                // edge to the next theta sample
+                if let Some(next) = row.get(theta_idx + 1) {
+                    let b = next - level_db;
+                    if a * b < 0.0 {
+                        points.push((theta + a / (a - b) * self.theta_step, phi));
+                    }
+                }
+
+                // edge to the next phi row; the poles are single points so skip them
+                if num_phi > 1 && theta_idx > 0 && theta_idx + 1 < row.len() {
+                    let b = self.values[(phi_idx + 1) % num_phi][theta_idx] - level_db;
+                    if a * b < 0.0 {
+                        points.push((theta, phi + a / (a - b) * self.phi_step));
+                    }
+                }
+            }
+        }
+        points
+    }
+}
+
+impl Index<(usize, usize)> for PatternGridDb {
+    type Output = f64;
+
+    /// Sample (dB) at `(phi_idx, theta_idx)`, the same order as `PatternGrid::get`
+    fn index(&self, (phi_idx, theta_idx): (usize, usize)) -> &f64 {
+        &self.values[phi_idx][theta_idx]
+    }
+}
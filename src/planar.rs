@@ -0,0 +1,132 @@
+//! Rectangular planar arrays
+//!
+//! Elements sit on a rectangular grid in the XY plane. Keeping track of the
+//! rows and columns makes separable (row times column) weightings easy.
+
+use std::collections::BTreeMap;
+
+use num::complex::Complex;
+
+use crate::{ArrayIface, ElementArray, ElementIface, GainIface, Point, SubarrayElement};
+
+/// Array on a rectangular grid in the XY plane
+///
+/// Element `row * cols + col` sits at `(col * dx, row * dy, 0)`, so rows run
+/// along x and columns along y.
+#[derive(Clone)]
+pub struct PlanarArray {
+    array: ElementArray,
+    rows: usize,
+    cols: usize,
+}
+
+impl PlanarArray {
+    /// Evenly spaced `rows` by `cols` grid
+    ///
+    /// `element` creates the element at each position and `dx`, `dy` are in
+    /// meters.
+    ///
+    pub fn uniform<F>(rows: usize, cols: usize, dx: f64, dy: f64, element: F) -> PlanarArray
+    where
+        F: Fn(Point) -> Box<dyn ElementIface>,
+    {
+        let elements = (0..rows)
+            .flat_map(|row| (0..cols).map(move |col| (row, col)))
+            .map(|(row, col)| element(Point { x: col as f64 * dx, y: row as f64 * dy, z: 0.0 }))
+            .collect();
+        PlanarArray { array: ElementArray::new(elements), rows, cols }
+    }
+
+    /// Number of rows (along y)
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Number of elements in each row (along x)
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// The underlying array
+    pub fn array(&self) -> &ElementArray {
+        &self.array
+    }
+
+    /// Mutable access to the underlying array
+    pub fn array_mut(&mut self) -> &mut ElementArray {
+        &mut self.array
+    }
+
+    /// Multiply the element weights by a separable taper
+    ///
+    /// `row_taper` runs along each row (one value per column, along x) and
+    /// `col_taper` runs along each column (one value per row, along y). The
+    /// element at `(row, col)` is scaled by `row_taper[col] * col_taper[row]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the taper lengths don't match the grid.
+    ///
+    pub fn apply_separable_taper(&mut self, row_taper: &[f64], col_taper: &[f64]) {
+        assert_eq!(row_taper.len(), self.cols, "row taper needs one value per column");
+        assert_eq!(col_taper.len(), self.rows, "column taper needs one value per row");
+
+        for (idx, n) in self.array.elements_mut().iter_mut().enumerate() {
+            let (row, col) = (idx / self.cols, idx % self.cols);
+            let weight = n.weight() * (row_taper[col] * col_taper[row]);
+            n.set_weight(weight);
+        }
+    }
+
+    /// Equivalent linear array for the cut plane at `azimuth_of_cut` (phi, radians)
+    ///
+    /// In that plane only the distance of each element along the line
+    /// `(cos(phi), sin(phi), 0)` changes the phase, so elements with the same
+    /// projection (to the nearest nanometer) act as one. Each such group
+    /// becomes a `SubarrayElement` on the line, holding the original
+    /// elements with their weights and delays at their offsets from it.
+    /// Along the cut (phi and phi + PI) the linear array has exactly the
+    /// pattern of the planar one; off the cut it doesn't.
+    ///
+    pub fn project_to_line(&self, azimuth_of_cut: f64) -> ElementArray {
+        let (cos_phi, sin_phi) = (azimuth_of_cut.cos(), azimuth_of_cut.sin());
+        let mut groups: BTreeMap<i64, Vec<Box<dyn ElementIface>>> = BTreeMap::new();
+        for n in self.array.elements() {
+            let along = n.position().x * cos_phi + n.position().y * sin_phi;
+            groups.entry((along * 1e9).round() as i64).or_default().push(n.clone());
+        }
+
+        ElementArray::new( groups
+            .into_iter()
+            .map(|(key, members)| {
+                let along = key as f64 * 1e-9;
+                let center = Point { x: along * cos_phi, y: along * sin_phi, z: 0.0 };
+                let members = members
+                    .into_iter()
+                    .map(|mut n| {
+                        let p = n.position().clone();
+                        n.set_position(Point { x: p.x - center.x, y: p.y - center.y, z: p.z });
+                        n
+                    })
+                    .collect();
+                Box::new(SubarrayElement::new(ElementArray::new(members), center)) as Box<dyn ElementIface>
+            })
+            .collect() )
+    }
+}
+
+impl GainIface for PlanarArray {
+    fn get_gain(&self, frequency: f64, theta: f64, phi: f64) -> Option<Complex<f64>> {
+        self.array.get_gain(frequency, theta, phi)
+    }
+}
+
+impl ArrayIface for PlanarArray {
+    fn num_elements(&self) -> usize {
+        self.array.num_elements()
+    }
+
+    fn flat_elements(&self) -> Vec<Box<dyn ElementIface>> {
+        self.array.flat_elements()
+    }
+}
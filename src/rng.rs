@@ -0,0 +1,40 @@
+//! Small seedable random number generator
+//!
+//! Monte Carlo studies need repeatable random numbers but not cryptographic
+//! ones, so a SplitMix64 generator is plenty and saves a dependency.
+
+use crate::PI;
+
+/// SplitMix64 generator
+pub(crate) struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Generator that always produces the same sequence for the same seed
+    pub(crate) fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    /// Next raw 64 bit value
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform sample in [0, 1)
+    pub(crate) fn uniform(&mut self) -> f64 {
+        // top 53 bits fill the mantissa exactly
+        (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64
+    }
+
+    /// Standard normal sample (Box-Muller)
+    pub(crate) fn gaussian(&mut self) -> f64 {
+        // 1 - uniform is in (0, 1], so the log is finite
+        let radius = (-2.0 * (1.0 - self.uniform()).ln()).sqrt();
+        radius * (2.0 * PI * self.uniform()).cos()
+    }
+}
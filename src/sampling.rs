@@ -0,0 +1,61 @@
+//! Choosing sphere sampling steps
+//!
+//! Too coarse a grid misses the beam peak and aliases the integral of the
+//! power pattern; too fine a grid wastes time. The step needed follows from
+//! the size of the array in wavelengths.
+
+use crate::{ElementArray, PI, SPEED_OF_LIGHT};
+
+/// Suggested `(theta_step, phi_step)` (radians) for a directivity within `target_rel_error`
+///
+/// Two limits are combined. The power pattern of an array that reaches a
+/// distance `R` from the origin has no angular detail finer than
+/// `PI / (2 * k * R)`, so sampling at that rate integrates it accurately
+/// (Nyquist). Separately the peak may fall between samples; for a beam
+/// `wavelength / D` wide the step is shrunk with `sqrt(target_rel_error)`
+/// so the peak is missed by less than the target. Phi only needs the extent
+/// in the XY plane.
+///
+pub fn recommended_integration_step(array: &ElementArray, frequency: f64, target_rel_error: f64) -> (f64, f64) {
+    let wavelength = SPEED_OF_LIGHT / frequency;
+    let k = 2.0 * PI / wavelength;
+    let positions: Vec<_> = array.elements().iter().map(|n| n.position().clone()).collect();
+
+    let radius = positions.iter().map(|p| (p.x * p.x + p.y * p.y + p.z * p.z).sqrt()).fold(0.0, f64::max);
+    let radius_xy = positions.iter().map(|p| (p.x * p.x + p.y * p.y).sqrt()).fold(0.0, f64::max);
+    let size = positions
+        .iter()
+        .flat_map(|a| positions.iter().map(move |b| ((a.x - b.x).powi(2) + (a.y - b.y).powi(2) + (a.z - b.z).powi(2)).sqrt()))
+        .fold(0.0, f64::max);
+
+    // the +2 keeps a few samples per lobe for small arrays
+    let nyquist = |r: f64| PI / (2.0 * k * r + 2.0);
+    let beam = if size > 0.0 {
+        0.75 * target_rel_error.sqrt() * wavelength / size
+    } else {
+        f64::INFINITY
+    };
+
+    (nyquist(radius).min(beam), nyquist(radius_xy).min(beam))
+}
+
+/// Spherical wave expansion truncation order for a source inside `max_radius` meters
+///
+/// Uses the common rule `N = ceil(k * r) + 10`; modes above that carry
+/// next to no power outside the minimum sphere.
+///
+pub fn swe_truncation_order(max_radius: f64, frequency: f64) -> usize {
+    let k = 2.0 * PI * frequency / SPEED_OF_LIGHT;
+    (k * max_radius).ceil() as usize + 10
+}
+
+/// Harrington's limit on the directivity (linear) of an antenna inside `aperture_radius` meters
+///
+/// `(k * a)^2 + 2 * k * a`, where `a` is the radius of the smallest sphere
+/// enclosing the antenna. Getting more than this takes superdirective
+/// excitations, with their narrow bandwidth and high loss.
+///
+pub fn max_directivity_bound(aperture_radius: f64, frequency: f64) -> f64 {
+    let ka = 2.0 * PI * frequency / SPEED_OF_LIGHT * aperture_radius;
+    ka * ka + 2.0 * ka
+}
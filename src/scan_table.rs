@@ -0,0 +1,173 @@
+//! Interpolated beam steering
+//!
+//! Real-time steering loops can't afford to evaluate an exponential per
+//! element every time the beam moves. Instead the steering weights are
+//! computed once for a grid of direction cosines `(u, v)` over the front
+//! hemisphere and each new beam position is bilinearly interpolated from the
+//! four surrounding grid points, which only takes multiplies, adds and a
+//! couple of square roots per element.
+//!
+//! In `(u, v)` the steering phase of an element in the XY plane is linear,
+//! so interpolating the phase itself would be exact. The table keeps the
+//! complex weights rather than the phases to avoid the sine and cosine, and
+//! interpolating unit phasors pulls the phase towards the nearer grid point
+//! by at most `delta^3 / 60`, where `delta` is the phase change of an element
+//! across one table cell. That grows with the table step and the size of the
+//! aperture in wavelengths, and shows up as a small pointing error; keeping
+//! the step to a fraction of the beamwidth (in `u`) keeps it negligible.
+//! Elements off the XY plane also see the curvature of `cos(theta)`, which is
+//! worst towards the horizon.
+
+use std::fmt;
+
+use num::complex::Complex;
+
+use crate::{calc_phase, phase_convention, ElementArray, Point, PI};
+
+/// Errors from steering with a `ScanTable`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScanTableError {
+    /// The table was built for this many elements but the array has a different count
+    ElementCount(usize),
+    /// The element at this index has moved since the table was built
+    ElementMoved(usize),
+    /// The phase convention has changed since the table was built
+    PhaseConvention,
+    /// The direction is behind the XY plane (theta > PI/2), outside the table
+    OutsideTable,
+}
+
+impl fmt::Display for ScanTableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScanTableError::ElementCount(count) => write!(f, "scan table was built for {} elements", count),
+            ScanTableError::ElementMoved(idx) => write!(f, "element {} has moved since the scan table was built", idx),
+            ScanTableError::PhaseConvention => write!(f, "phase convention has changed since the scan table was built"),
+            ScanTableError::OutsideTable => write!(f, "direction is outside the front hemisphere covered by the scan table"),
+        }
+    }
+}
+
+impl std::error::Error for ScanTableError {}
+
+/// Steering weights for a grid of direction cosines over the front hemisphere
+///
+/// The table is built from an array at one frequency and remembers where
+/// each element was, so steering an array whose elements have moved (or a
+/// different array) is reported as an error instead of silently applying
+/// the wrong phases. Changing weights, delays or element patterns doesn't
+/// invalidate it; changing frequency does, and isn't checked.
+///
+#[derive(Clone, Debug)]
+pub struct ScanTable {
+    // spacing of the grid in u and in v
+    step: f64,
+    // grid points along each of u and v, both running from -1 to 1
+    num: usize,
+    // positions of the elements the table was built for
+    positions: Vec<Point>,
+    // sign of the phase convention the table was built under
+    sign: f64,
+    // weights[(v_idx * num + u_idx) * num_elements + element_idx], unit magnitude
+    weights: Vec<Complex<f64>>,
+}
+
+impl ScanTable {
+    /// Precompute the steering weights of `array` at `frequency` every `step` in u and v
+    ///
+    /// The grid covers the whole square `-1 <= u, v <= 1`, so it holds
+    /// `(2 / step + 1)^2` weights per element.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` isn't positive.
+    ///
+    pub fn new(array: &ElementArray, frequency: f64, step: f64) -> ScanTable {
+        assert!(step > 0.0, "scan table step has to be positive");
+        let num = (2.0 / step).ceil() as usize + 1;
+        let positions: Vec<Point> = array.elements.iter().map(|n| n.position().clone()).collect();
+
+        let mut weights = Vec::with_capacity(num * num * positions.len());
+        for v_idx in 0..num {
+            let v = -1.0 + v_idx as f64 * step;
+            for u_idx in 0..num {
+                let u = -1.0 + u_idx as f64 * step;
+                // points past the horizon are steered along it so the cells around it interpolate smoothly
+                let (theta, phi) = ((u * u + v * v).sqrt().min(1.0).asin(), v.atan2(u));
+                weights.extend(positions.iter().map(|pnt| calc_phase(pnt, frequency, theta, phi).conj()));
+            }
+        }
+
+        ScanTable { step, num, positions, sign: phase_convention().sign(), weights }
+    }
+
+    /// Spacing of the table in u and in v
+    pub fn step(&self) -> f64 {
+        self.step
+    }
+
+    /// Number of elements the table was built for
+    pub fn num_elements(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Check that `array` still matches what the table was built from
+    fn check(&self, array: &ElementArray) -> Result<(), ScanTableError> {
+        if array.elements.len() != self.positions.len() {
+            return Err(ScanTableError::ElementCount(self.positions.len()));
+        }
+        if let Some(idx) = array.elements.iter().zip(&self.positions).position(|(n, pnt)| n.position() != pnt) {
+            return Err(ScanTableError::ElementMoved(idx));
+        }
+        if phase_convention().sign() != self.sign {
+            return Err(ScanTableError::PhaseConvention);
+        }
+        Ok(())
+    }
+}
+
+impl ElementArray {
+    /// Steer the main beam towards `(theta0, phi0)` using a precomputed `table`
+    ///
+    /// Like `steer`, the weight amplitudes are kept and only the phases
+    /// change. See the module documentation for the accuracy.
+    /// The weights aren't touched if the table doesn't match the array or
+    /// the direction is behind the XY plane.
+    ///
+    pub fn steer_interpolated(&mut self, table: &ScanTable, theta0: f64, phi0: f64) -> Result<(), ScanTableError> {
+        table.check(self)?;
+        if !(0.0..=PI / 2.0).contains(&theta0) {
+            return Err(ScanTableError::OutsideTable);
+        }
+
+        let last = (table.num - 2) as f64;
+        let u_pos = ((theta0.sin() * phi0.cos() + 1.0) / table.step).clamp(0.0, last + 1.0);
+        let v_pos = ((theta0.sin() * phi0.sin() + 1.0) / table.step).clamp(0.0, last + 1.0);
+        let (u_idx, v_idx) = (u_pos.floor().min(last) as usize, v_pos.floor().min(last) as usize);
+        let (u_frac, v_frac) = (u_pos - u_idx as f64, v_pos - v_idx as f64);
+
+        let count = table.positions.len();
+        let cell = |v_idx: usize, u_idx: usize| {
+            let start = (v_idx * table.num + u_idx) * count;
+            &table.weights[start..start + count]
+        };
+        let corners = [
+            (cell(v_idx, u_idx), (1.0 - v_frac) * (1.0 - u_frac)),
+            (cell(v_idx, u_idx + 1), (1.0 - v_frac) * u_frac),
+            (cell(v_idx + 1, u_idx), v_frac * (1.0 - u_frac)),
+            (cell(v_idx + 1, u_idx + 1), v_frac * u_frac),
+        ];
+
+        for (idx, n) in self.elements.iter_mut().enumerate() {
+            let phasor: Complex<f64> = corners.iter().map(|(weights, w)| weights[idx] * w).sum();
+            // the corners only cancel if the table is far too coarse, fall back to the nearest one
+            let phasor = match phasor.norm() {
+                norm if norm > 0.0 => phasor / norm,
+                _ => corners.iter().max_by(|a, b| a.1.total_cmp(&b.1)).unwrap().0[idx],
+            };
+            let weight = n.weight().norm() * phasor;
+            n.set_weight(weight);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,121 @@
+//! Minimum peak sidelobe synthesis
+//!
+//! With the element phases fixed, the array gain is linear in the element
+//! amplitudes, so the largest sidelobe is a convex function of them. Lawson's
+//! algorithm finds the min-max (Chebyshev) solution as the limit of a
+//! sequence of weighted least squares problems: directions where the pattern
+//! is high get more weight in the next iteration until the sidelobes are
+//! equiripple.
+
+use num::complex::Complex;
+
+use crate::math::solve_linear;
+use crate::{effective_weight, element_response, ElementArray, PI};
+
+/// Sphere sampling of the sidelobe region (radians)
+const SIDELOBE_STEP: f64 = 2.0 * PI / 180.0;
+
+/// Number of Lawson iterations
+const LAWSON_ITERATIONS: usize = 200;
+
+/// Directions with a Lawson weight below this fraction of the largest are left out of the least squares
+const PRUNE: f64 = 1e-6;
+
+impl ElementArray {
+    /// Set the element amplitudes that minimize the peak sidelobe
+    ///
+    /// The sidelobe region is everything more than `main_beam_cone_deg` from
+    /// boresight (theta = 0), sampled on a 2 degree grid. The phase of each
+    /// existing weight is kept and the amplitudes are chosen so that the
+    /// gain at boresight is exactly 1 and the largest gain in the sidelobe
+    /// region is as small as possible. The amplitudes are real but may come
+    /// out negative, which flips the element's phase.
+    ///
+    /// A cone only describes the main beam of a planar array. A linear
+    /// array has a fan beam that leaves the cone, so it can only reach low
+    /// "sidelobes" by superdirective weights. Nothing changes if the least
+    /// squares problem is singular.
+    ///
+    pub fn minimize_peak_sidelobe(&mut self, main_beam_cone_deg: f64, frequency: f64) {
+        let count = self.elements.len();
+        let phases: Vec<Complex<f64>> = self.elements
+            .iter()
+            .map(|n| {
+                let weight = n.weight();
+                if weight.norm() > 0.0 { weight / weight.norm() } else { Complex::new(1.0, 0.0) }
+            })
+            .collect();
+        let contributions = |theta: f64, phi: f64| -> Vec<Complex<f64>> {
+            self.elements
+                .iter()
+                .zip(&phases)
+                .enumerate()
+                .map(|(idx, (n, phase))| {
+                    element_response(n.as_ref(), frequency, theta, phi).unwrap_or_default() * effective_weight(self, idx, *phase)
+                })
+                .collect()
+        };
+
+        let boresight: Vec<f64> = contributions(0.0, 0.0).iter().map(|c| c.re).collect();
+        let cone = main_beam_cone_deg.to_radians();
+        let num_theta = (PI / SIDELOBE_STEP).round() as usize + 1;
+        let num_phi = (2.0 * PI / SIDELOBE_STEP).round() as usize;
+        let samples: Vec<Vec<Complex<f64>>> = (0..num_theta)
+            .map(|t| t as f64 * SIDELOBE_STEP)
+            .filter(|theta| *theta > cone)
+            .flat_map(|theta| (0..num_phi).map(move |p| (theta, p as f64 * SIDELOBE_STEP)))
+            .map(|(theta, phi)| contributions(theta, phi))
+            .filter(|sample| sample.iter().any(|c| c.norm() > 0.0))
+            .collect();
+        if samples.is_empty() || count == 0 {
+            return;
+        }
+
+        let mut lawson = vec![1.0 / samples.len() as f64; samples.len()];
+        let mut best: Option<(f64, Vec<f64>)> = None;
+        for _ in 0..LAWSON_ITERATIONS {
+            // weighted least squares with the boresight constraint:
+            // amplitudes = R^-1 b / (b' R^-1 b)
+            // directions with negligible weight are skipped, but keep their weight
+            let largest = lawson.iter().cloned().fold(0.0, f64::max);
+            let mut matrix = vec![vec![0.0; count]; count];
+            for (sample, weight) in samples.iter().zip(&lawson).filter(|(_, w)| **w > PRUNE * largest) {
+                for (row, cm) in matrix.iter_mut().zip(sample) {
+                    for (entry, cn) in row.iter_mut().zip(sample) {
+                        *entry += weight * (cm * cn.conj()).re;
+                    }
+                }
+            }
+            let Some(x) = solve_linear(matrix, boresight.clone()) else { break };
+            let scale: f64 = boresight.iter().zip(&x).map(|(b, x)| b * x).sum();
+            if scale == 0.0 {
+                break;
+            }
+            let amplitudes: Vec<f64> = x.iter().map(|x| x / scale).collect();
+
+            let errors: Vec<f64> = samples
+                .iter()
+                .map(|sample| sample.iter().zip(&amplitudes).map(|(c, a)| c * a).sum::<Complex<f64>>().norm())
+                .collect();
+            let peak = errors.iter().cloned().fold(0.0, f64::max);
+            if best.as_ref().is_none_or(|(level, _)| peak < *level) {
+                best = Some((peak, amplitudes));
+            }
+
+            // Lawson update
+            let total: f64 = lawson.iter().zip(&errors).map(|(w, e)| w * e).sum();
+            if total == 0.0 {
+                break;
+            }
+            for (w, e) in lawson.iter_mut().zip(&errors) {
+                *w *= e / total;
+            }
+        }
+
+        if let Some((_, amplitudes)) = best {
+            for ((n, phase), amplitude) in self.elements.iter_mut().zip(phases).zip(amplitudes) {
+                n.set_weight(phase * amplitude);
+            }
+        }
+    }
+}
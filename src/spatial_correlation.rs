@@ -0,0 +1,64 @@
+//! Spatial correlation for channel models
+//!
+//! The spatial correlation matrix of an array in a channel with angular
+//! power spectrum `P` is `R[m][n] = ∮ P a_m conj(a_n) dΩ / ∮ P dΩ`, where
+//! `a` is the steering vector (element pattern, positional phase and delay).
+//! It is what a MIMO channel model uses to correlate the fading at each
+//! element.
+
+use num::complex::Complex;
+
+use crate::{element_response, ElementArray, PI};
+
+/// Sphere sampling used for the correlation integral (radians)
+const CORRELATION_STEP: f64 = PI / 180.0;
+
+impl ElementArray {
+    /// Spatial correlation matrix under an angular power spectrum
+    ///
+    /// `aps(theta, phi)` is the power arriving from each direction and need
+    /// not be normalized. The steering vector holds each element's pattern,
+    /// positional phase and delay, but not its weight. The sphere is sampled on a
+    /// 1 degree grid. Isotropic elements give a unit diagonal; an APS that is
+    /// zero everywhere gives a zero matrix.
+    ///
+    pub fn spatial_correlation_matrix(&self, frequency: f64, aps: impl Fn(f64, f64) -> f64) -> Vec<Vec<Complex<f64>>> {
+        let count = self.elements.len();
+        let num_theta = (PI / CORRELATION_STEP).round() as usize + 1;
+        let num_phi = (2.0 * PI / CORRELATION_STEP).round() as usize;
+
+        let mut matrix = vec![vec![Complex::new(0.0, 0.0); count]; count];
+        let mut total = 0.0;
+        for phi_idx in 0..num_phi {
+            let phi = phi_idx as f64 * CORRELATION_STEP;
+            for theta_idx in 0..num_theta {
+                let theta = theta_idx as f64 * CORRELATION_STEP;
+                let power = aps(theta, phi) * theta.sin() * CORRELATION_STEP * CORRELATION_STEP;
+                if power == 0.0 {
+                    continue;
+                }
+                total += power;
+
+                let steering: Vec<Complex<f64>> = self.elements
+                    .iter()
+                    .map(|n| element_response(n.as_ref(), frequency, theta, phi).unwrap_or_default())
+                    .collect();
+                for (m, a_m) in steering.iter().enumerate() {
+                    for (n, a_n) in steering.iter().enumerate().skip(m) {
+                        matrix[m][n] += a_m * a_n.conj() * power;
+                    }
+                }
+            }
+        }
+
+        // only the upper triangle was accumulated; the matrix is Hermitian
+        let scale = if total > 0.0 { 1.0 / total } else { 0.0 };
+        (0..count)
+            .map(|m| {
+                (0..count)
+                    .map(|n| if n >= m { matrix[m][n] * scale } else { matrix[n][m].conj() * scale })
+                    .collect()
+            })
+            .collect()
+    }
+}
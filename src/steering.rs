@@ -0,0 +1,371 @@
+//! Electronic beam steering
+//!
+//! Steering rewrites the phase of every element weight so that the element
+//! contributions add up coherently in the requested direction. The amplitude
+//! of each weight (the taper) is left alone.
+
+use num::complex::Complex;
+
+use crate::{calc_phase, delay_phase, effective_weight, element_response, recommended_integration_step, separation, ElementArray, GainIface, PatternCut, PatternGrid, PI, SPEED_OF_LIGHT};
+
+/// Scan grid used by `scan_volume` (radians)
+const SCAN_VOLUME_STEP: f64 = 2.0 * PI / 180.0;
+
+/// Most phase shifter bits tried by `bits_for_pointing_budget`
+const MAX_PHASE_BITS: usize = 16;
+
+impl ElementArray {
+    /// Steer the main beam towards `(theta0, phi0)`
+    pub fn steer(&mut self, frequency: f64, theta0: f64, phi0: f64) {
+        for n in self.elements.iter_mut() {
+            let phase = calc_phase(n.position(), frequency, theta0, phi0).conj();
+            let weight = n.weight().norm() * phase;
+            n.set_weight(weight);
+        }
+    }
+
+    /// Round every weight phase to the nearest level of a `bits` phase shifter
+    pub fn quantize_phases(&mut self, bits: u32) {
+        for n in self.elements.iter_mut() {
+            let weight = quantize(n.weight(), bits);
+            n.set_weight(weight);
+        }
+    }
+
+    /// Angular offset of the actual beam peak from the intended direction
+    ///
+    /// Starting from `intended = (theta, phi)` the peak is found by climbing
+    /// the pattern in direction cosine space, which behaves at boresight
+    /// where phi is undefined. The result is `(theta, phi)` of the peak minus
+    /// the intended direction, with the phi offset wrapped to +/- PI.
+    ///
+    pub fn pointing_error(&self, frequency: f64, intended: (f64, f64)) -> (f64, f64) {
+        let (theta0, phi0) = intended;
+        let (theta, phi) = climb_to_peak(|theta, phi| self.get_gain(frequency, theta, phi).unwrap().norm(), intended);
+        let dphi = (phi - phi0 + PI).rem_euclid(2.0 * PI) - PI;
+        (theta - theta0, dphi)
+    }
+
+    /// Full pattern of the array steered to `(theta0, phi0)`, sampled over the sphere
+    ///
+    /// The weights are steered the same way as `steer` (keeping their
+    /// amplitudes) without changing the array, and the pattern includes the
+    /// element patterns, so it shows the scan loss from the element roll-off
+    /// as well as from the array factor.
+    ///
+    pub fn scan_pattern(&self, frequency: f64, theta0: f64, phi0: f64, theta_step: f64, phi_step: f64) -> PatternGrid {
+        let weights: Vec<Complex<f64>> = self.elements
+            .iter()
+            .enumerate()
+            .map(|(idx, n)| effective_weight(self, idx, n.weight().norm() * calc_phase(n.position(), frequency, theta0, phi0).conj()))
+            .collect();
+
+        PatternGrid::from_fn(theta_step, phi_step, |theta, phi| {
+            self.elements
+                .iter()
+                .zip(&weights)
+                .map(|(n, weight)| {
+                    element_response(n.as_ref(), frequency, theta, phi).unwrap() * weight
+                })
+                .sum()
+        })
+    }
+
+    /// Spatial ambiguity function for a target at `(theta0, phi0)`
+    ///
+    /// The sample at `(theta, phi)` is the gain towards the target with the
+    /// beam steered to `(theta, phi)` the same way as `steer` (keeping the
+    /// weight amplitudes), i.e. what a receive beam looking there would see
+    /// of the target. It peaks at the target, and every other lobe, grating
+    /// lobes included, is a direction the target could be mistaken for.
+    /// The weights of the array aren't changed.
+    ///
+    pub fn angular_ambiguity(&self, frequency: f64, theta0: f64, phi0: f64, theta_step: f64, phi_step: f64) -> PatternGrid {
+        let target: Vec<Complex<f64>> = self.elements
+            .iter()
+            .enumerate()
+            .map(|(idx, n)| effective_weight(self, idx, Complex::from(n.weight().norm())) * element_response(n.as_ref(), frequency, theta0, phi0).unwrap())
+            .collect();
+
+        PatternGrid::from_fn(theta_step, phi_step, |theta, phi| {
+            self.elements
+                .iter()
+                .zip(&target)
+                .map(|(n, response)| calc_phase(n.position(), frequency, theta, phi).conj() * response)
+                .sum()
+        })
+    }
+
+    /// Solid angle (steradians) of scan directions that lose at most `max_scan_loss_db` against broadside
+    ///
+    /// The array is steered (keeping the weight amplitudes) to every
+    /// direction on a 2 degree grid and the gain in that direction is
+    /// compared with the gain of the beam steered to broadside (theta = 0),
+    /// so e.g. 3 dB gives the volume the array can scan over before its
+    /// beam drops to half power. Gives 0 if the array has no gain at
+    /// broadside. The weights of the array aren't changed.
+    ///
+    pub fn scan_volume(&self, frequency: f64, max_scan_loss_db: f64) -> f64 {
+        // steering cancels the positional phase, leaving the weighted element patterns
+        let steered = PatternGrid::from_fn(SCAN_VOLUME_STEP, SCAN_VOLUME_STEP, |theta, phi| {
+            self.elements
+                .iter()
+                .enumerate()
+                .map(|(idx, n)| {
+                    let weight = effective_weight(self, idx, Complex::from(n.weight().norm()));
+                    n.pattern(frequency, theta, phi).unwrap_or_default() * delay_phase(n.delay(), frequency) * weight
+                })
+                .sum()
+        });
+
+        let broadside = steered.get(0, 0).norm_sqr();
+        if broadside == 0.0 {
+            return 0.0;
+        }
+
+        steered.values()
+            .iter()
+            .flat_map(|row| row.iter().enumerate())
+            .filter(|(_, value)| 10.0 * (value.norm_sqr() / broadside).log10() >= -max_scan_loss_db)
+            .map(|(theta_idx, _)| steered.theta(theta_idx).sin() * SCAN_VOLUME_STEP * SCAN_VOLUME_STEP)
+            .sum()
+    }
+
+    /// Peak sidelobe level (dB relative to the beam) with the beam scanned to each of `scan_angles`
+    ///
+    /// Each angle is a theta (radians) in the XZ plane, negative angles
+    /// scanning towards phi = PI. The array is steered like `steer` and the
+    /// pattern is sampled along the scan plane (a `PatternCut` at phi = 0)
+    /// with the theta step from `recommended_integration_step`. The main
+    /// lobe is the peak nearest the scan angle down to the nulls on either
+    /// side, and everything else along the cut counts as a sidelobe, so
+    /// grating lobes coming into visible space show up too. Working on the
+    /// cut treats the pencil beam of a planar array and the fan beam of a
+    /// linear array along x the same way. The element patterns are included:
+    /// their roll-off lowers the scanned beam but not the sidelobes near
+    /// broadside. The weights are put back afterwards.
+    ///
+    pub fn peak_sidelobe_vs_scan(&mut self, frequency: f64, scan_angles: &[f64]) -> Vec<f64> {
+        let weights: Vec<Complex<f64>> = self.elements.iter().map(|n| n.weight()).collect();
+        let (theta_step, _) = recommended_integration_step(self, frequency, 0.05);
+
+        let levels = scan_angles
+            .iter()
+            .map(|theta0| {
+                self.steer(frequency, *theta0, 0.0);
+                cut_peak_sidelobe_db(&self.sample_cut(frequency, 0.0, theta_step), *theta0)
+            })
+            .collect();
+
+        for (n, weight) in self.elements.iter_mut().zip(weights) {
+            n.set_weight(weight);
+        }
+        levels
+    }
+
+    /// Complex gain with hybrid analog/digital beamforming
+    ///
+    /// The elements are split, in order, into consecutive subarrays:
+    /// `analog[s]` holds the analog weights of the `analog[s].len()` elements
+    /// of subarray `s`. Each subarray output is then weighted by
+    /// `digital[s]` and the outputs are summed. These weights replace the
+    /// element weights, which are ignored. One element per subarray is fully
+    /// digital beamforming; a single subarray is fully analog.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the subarrays don't cover every element exactly once or if
+    /// there isn't one digital weight per subarray.
+    ///
+    pub fn hybrid_gain(&self, frequency: f64, theta: f64, phi: f64, analog: &[Vec<Complex<f64>>], digital: &[Complex<f64>]) -> Complex<f64> {
+        assert_eq!(analog.iter().map(|weights| weights.len()).sum::<usize>(), self.elements.len(), "subarrays must cover every element");
+        assert_eq!(analog.len(), digital.len(), "one digital weight per subarray");
+
+        let mut elements = self.elements.iter().enumerate();
+        analog
+            .iter()
+            .zip(digital)
+            .map(|(weights, digital)| {
+                let output: Complex<f64> = weights
+                    .iter()
+                    .zip(elements.by_ref())
+                    .map(|(weight, (idx, n))| element_response(n.as_ref(), frequency, theta, phi).unwrap_or_default() * effective_weight(self, idx, *weight))
+                    .sum();
+                output * digital
+            })
+            .sum()
+    }
+
+    /// Peak-to-peak variation (dB) of the beam peak gain while scanning over `sector`
+    ///
+    /// The array is steered (keeping the weight amplitudes) to each
+    /// `(theta, phi)` in `sector`, and the peak of the resulting beam is found
+    /// by climbing from the steered direction, so beam squint from the
+    /// element patterns is accounted for. Returns the largest minus the
+    /// smallest peak gain. The weights of the array aren't changed.
+    ///
+    pub fn scan_ripple_db(&self, frequency: f64, sector: &[(f64, f64)]) -> f64 {
+        let peaks: Vec<f64> = sector
+            .iter()
+            .map(|(theta0, phi0)| {
+                let weights: Vec<Complex<f64>> = self.elements
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, n)| effective_weight(self, idx, n.weight().norm() * calc_phase(n.position(), frequency, *theta0, *phi0).conj()))
+                    .collect();
+                let gain = |theta: f64, phi: f64| -> f64 {
+                    self.elements
+                        .iter()
+                        .zip(&weights)
+                        .map(|(n, weight)| element_response(n.as_ref(), frequency, theta, phi).unwrap_or_default() * weight)
+                        .sum::<Complex<f64>>()
+                        .norm()
+                };
+                let (theta, phi) = climb_to_peak(gain, (*theta0, *phi0));
+                20.0 * gain(theta, phi).log10()
+            })
+            .collect();
+
+        let max = peaks.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let min = peaks.iter().cloned().fold(f64::INFINITY, f64::min);
+        max - min
+    }
+
+    /// Instantaneous bandwidth (Hz) allowed by the aperture fill time, `c / (D * sin(theta0))`
+    ///
+    /// Phase steering is only right at one frequency; scanned to `theta0`,
+    /// the far edge of the aperture lags the near edge by `D * sin(theta0) / c`.
+    /// `D` is the extent of the array in the XY plane along the scan plane
+    /// `phi0`. At broadside there's no limit and the result is infinite.
+    ///
+    pub fn aperture_fill_bandwidth(&self, theta0: f64, phi0: f64) -> f64 {
+        let along: Vec<f64> = self.elements
+            .iter()
+            .map(|n| n.position().x * phi0.cos() + n.position().y * phi0.sin())
+            .collect();
+        let extent = along.iter().cloned().fold(f64::NEG_INFINITY, f64::max)
+            - along.iter().cloned().fold(f64::INFINITY, f64::min);
+        SPEED_OF_LIGHT / (extent * theta0.sin().abs())
+    }
+}
+
+/// Fewest phase shifter bits that keep the beam within a pointing and sidelobe budget
+///
+/// The array's current weights set the intended beam: its peak is found on
+/// the unquantized pattern. The weight phases are then quantized with 1, 2,
+/// ... bits (as `quantize_phases` would) until the peak moves by no more
+/// than `max_pointing_error_deg` and the peak sidelobe, relative to the
+/// beam, is at or below `max_sll_db`. The pattern is sampled with
+/// `recommended_integration_step`. Returns `None` if 16 bits aren't
+/// enough, e.g. when the taper itself can't meet the sidelobe level. The
+/// array isn't changed.
+///
+pub fn bits_for_pointing_budget(array: &ElementArray, max_pointing_error_deg: f64, max_sll_db: f64, frequency: f64) -> Option<usize> {
+    let gain = |weights: &[Complex<f64>], theta: f64, phi: f64| -> Complex<f64> {
+        array.elements
+            .iter()
+            .zip(weights)
+            .enumerate()
+            .map(|(idx, (n, weight))| element_response(n.as_ref(), frequency, theta, phi).unwrap() * effective_weight(array, idx, *weight))
+            .sum()
+    };
+    let (theta_step, phi_step) = recommended_integration_step(array, frequency, 0.05);
+
+    let weights: Vec<Complex<f64>> = array.elements.iter().map(|n| n.weight()).collect();
+    let ideal = PatternGrid::from_fn(theta_step, phi_step, |theta, phi| gain(&weights, theta, phi));
+    let (phi_idx, theta_idx) = ideal.peak_index();
+    let intended = climb_to_peak(|theta, phi| gain(&weights, theta, phi).norm(), (ideal.theta(theta_idx), ideal.phi(phi_idx)));
+
+    (1..=MAX_PHASE_BITS).find(|bits| {
+        let quantized: Vec<Complex<f64>> = weights.iter().map(|weight| quantize(*weight, *bits as u32)).collect();
+        let peak = climb_to_peak(|theta, phi| gain(&quantized, theta, phi).norm(), intended);
+        separation(peak, intended).to_degrees() <= max_pointing_error_deg
+            && PatternGrid::from_fn(theta_step, phi_step, |theta, phi| gain(&quantized, theta, phi)).peak_sidelobe_db() <= max_sll_db
+    })
+}
+
+/// Largest power outside the main lobe of `cut` relative to its peak (dB)
+///
+/// The main lobe is found by climbing from the sample nearest `theta0` to
+/// the top of the beam, then walking down both sides for as long as the
+/// power keeps falling.
+fn cut_peak_sidelobe_db(cut: &PatternCut, theta0: f64) -> f64 {
+    let power: Vec<f64> = cut.values().iter().map(|value| value.norm_sqr()).collect();
+    let last = power.len() - 1;
+    let step = 2.0 * PI / last as f64;
+
+    let mut peak = (((theta0 + PI) / step).round() as usize).min(last);
+    loop {
+        if peak > 0 && power[peak - 1] > power[peak] {
+            peak -= 1;
+        } else if peak < last && power[peak + 1] > power[peak] {
+            peak += 1;
+        } else {
+            break;
+        }
+    }
+
+    let mut start = peak;
+    while start > 0 && power[start - 1] <= power[start] {
+        start -= 1;
+    }
+    let mut end = peak;
+    while end < last && power[end + 1] <= power[end] {
+        end += 1;
+    }
+
+    let sidelobe = power[..start].iter().chain(&power[end + 1..]).cloned().fold(0.0, f64::max);
+    10.0 * (sidelobe / power[peak]).log10()
+}
+
+/// Round the phase of a weight to the nearest level of a `bits` phase shifter
+fn quantize(weight: Complex<f64>, bits: u32) -> Complex<f64> {
+    let lsb = 2.0 * PI / 2_f64.powi(bits as i32);
+    let (amplitude, phase) = weight.to_polar();
+    Complex::from_polar(amplitude, (phase / lsb).round() * lsb)
+}
+
+/// Climb `gain(theta, phi)` from `start` to the top of the beam in direction cosine space
+fn climb_to_peak<F>(gain: F, start: (f64, f64)) -> (f64, f64)
+where
+    F: Fn(f64, f64) -> f64,
+{
+    let (theta0, phi0) = start;
+    let gain = |u: f64, v: f64| {
+        let (theta, phi) = uv_to_theta_phi(u, v);
+        gain(theta, phi)
+    };
+
+    let mut u = theta0.sin() * phi0.cos();
+    let mut v = theta0.sin() * phi0.sin();
+    let mut best = gain(u, v);
+    let mut step = 1e-2;
+    while step > 1e-10 {
+        let mut moved = false;
+        for (du, dv) in [(step, 0.0), (-step, 0.0), (0.0, step), (0.0, -step)] {
+            let (u1, v1) = (u + du, v + dv);
+            if u1 * u1 + v1 * v1 > 1.0 {
+                continue;
+            }
+            let candidate = gain(u1, v1);
+            if candidate > best {
+                best = candidate;
+                u = u1;
+                v = v1;
+                moved = true;
+            }
+        }
+        if !moved {
+            step /= 2.0;
+        }
+    }
+
+    uv_to_theta_phi(u, v)
+}
+
+/// Direction cosines in the front hemisphere to theta/phi
+fn uv_to_theta_phi(u: f64, v: f64) -> (f64, f64) {
+    let theta = (u * u + v * v).sqrt().min(1.0).asin();
+    let phi = v.atan2(u).rem_euclid(2.0 * PI);
+    (theta, phi)
+}
@@ -0,0 +1,84 @@
+//! Sub-arrays as elements
+//!
+//! A small array can stand in for a single "super element" of a larger one.
+//! The sub-array's elements are positioned relative to the sub-array's own
+//! origin, and the sub-array as a whole is placed like any other element.
+
+use num::complex::Complex;
+
+use crate::{element_gain, ElementArray, ElementIface, GainIface, Point};
+
+/// An `ElementArray` used as a single element
+///
+/// The element pattern is the gain of the wrapped array about its own
+/// origin, so the element weights of the sub-array shape the pattern and the
+/// sub-array weight scales the whole thing.
+#[derive(Clone)]
+pub struct SubarrayElement {
+    // position of the sub-array origin in space
+    position: Point,
+    // Weight applied to the sub-array pattern
+    weight: Complex<f64>,
+    // time delay in the feed (seconds)
+    delay: f64,
+    // elements relative to the sub-array origin
+    array: ElementArray,
+}
+
+impl SubarrayElement {
+    /// Place `array` with its origin at `position`
+    pub fn new(array: ElementArray, position: Point) -> SubarrayElement {
+        SubarrayElement {
+            position,
+            weight: Complex::new(1.0, 0.0),
+            delay: 0.0,
+            array,
+        }
+    }
+
+    /// The wrapped sub-array
+    pub fn array(&self) -> &ElementArray {
+        &self.array
+    }
+
+    /// Mutable access to the wrapped sub-array
+    pub fn array_mut(&mut self) -> &mut ElementArray {
+        &mut self.array
+    }
+}
+
+impl GainIface for SubarrayElement {
+    fn get_gain(&self, frequency: f64, theta: f64, phi: f64) -> Option<Complex<f64>> {
+        element_gain(self, frequency, theta, phi)
+    }
+}
+
+impl ElementIface for SubarrayElement {
+    fn position(&self) -> &Point {
+        &self.position
+    }
+
+    fn set_position(&mut self, position: Point) {
+        self.position = position;
+    }
+
+    fn weight(&self) -> Complex<f64> {
+        self.weight
+    }
+
+    fn set_weight(&mut self, weight: Complex<f64>) {
+        self.weight = weight;
+    }
+
+    fn delay(&self) -> f64 {
+        self.delay
+    }
+
+    fn set_delay(&mut self, delay: f64) {
+        self.delay = delay;
+    }
+
+    fn pattern(&self, frequency: f64, theta: f64, phi: f64) -> Option<Complex<f64>> {
+        self.array.get_gain(frequency, theta, phi)
+    }
+}
@@ -0,0 +1,80 @@
+//! Maximum directivity excitation
+//!
+//! Directivity is the ratio of two quadratic forms in the weights: the power
+//! in the look direction over the power averaged over the sphere. The
+//! maximum comes from the pattern-overlap matrix of the elements. For
+//! elements half a wavelength apart it is close to the conventional phased
+//! excitation; packed closer together the optimum becomes superdirective,
+//! with large weights of alternating sign that nearly cancel. The
+//! sensitivity factor measures how much that cancellation costs.
+
+use num::complex::Complex;
+
+use crate::math::solve_complex;
+use crate::{effective_weight, element_response, ElementArray, GainError};
+
+impl ElementArray {
+    /// Weights that maximize the directivity towards `(theta0, phi0)`
+    ///
+    /// With `a` the element responses towards the look direction and `R` the
+    /// sphere-averaged overlap of the element responses (see
+    /// `spatial_correlation_matrix` with a flat spectrum), the optimum is
+    /// `w = conj(R^-1 a)`, the solution of the generalized eigenvalue
+    /// problem `a a^H x = lambda R x` with `x = conj(w)`. The weights are
+    /// scaled so that the sum of their squared magnitudes is the number of
+    /// elements and the gain towards the look direction is real and
+    /// positive, the same as the weights from `steer` for a uniform taper.
+    ///
+    /// For elements much closer than half a wavelength the result is
+    /// superdirective: it only reaches its directivity if the weights are
+    /// realized almost exactly, and it radiates little for the power put in.
+    /// Check it with `excitation_sensitivity` before use.
+    ///
+    /// If the overlap matrix is singular, e.g. with two elements at the same
+    /// position, the first element whose pattern adds nothing to the ones
+    /// before it is returned as `GainError::Singular`.
+    ///
+    pub fn max_directivity_weights(&self, frequency: f64, theta0: f64, phi0: f64) -> Result<Vec<Complex<f64>>, GainError> {
+        let overlap = self.spatial_correlation_matrix(frequency, |_, _| 1.0);
+        let look: Vec<Complex<f64>> = self.elements
+            .iter()
+            .map(|n| element_response(n.as_ref(), frequency, theta0, phi0).unwrap_or_default())
+            .collect();
+
+        let solution = solve_complex(&overlap, &look).ok_or_else(|| GainError::Singular(dependent_element(&overlap)))?;
+        let weights: Vec<Complex<f64>> = solution.iter().map(|x| x.conj()).collect();
+
+        let gain: Complex<f64> = weights.iter().zip(&look).map(|(w, a)| w * a).sum();
+        let power: f64 = weights.iter().map(|w| w.norm_sqr()).sum();
+        let scale = (self.elements.len() as f64 / power).sqrt() * gain.conj() / gain.norm();
+        Ok( weights.iter().map(|w| w * scale).collect() )
+    }
+
+    /// Sensitivity (tolerance) factor of the current weights towards `(theta0, phi0)`
+    ///
+    /// `sum |w|^2 / |AF|^2`, with `AF` the array gain in the look direction.
+    /// Random weight errors with variance `sigma^2` raise the average
+    /// sidelobe level to about `sigma^2` times this relative to the beam, so
+    /// it shows how accurately the weights have to be realized. A uniform
+    /// excitation of `N` isotropic elements gives `1 / N`; superdirective
+    /// weights give orders of magnitude more, and anything above 1 is
+    /// rarely practical. Infinite if the look direction is a null.
+    ///
+    pub fn excitation_sensitivity(&self, frequency: f64, theta0: f64, phi0: f64) -> f64 {
+        let power: f64 = self.elements.iter().enumerate().map(|(idx, n)| effective_weight(self, idx, n.weight()).norm_sqr()).sum();
+        let gain: Complex<f64> = self.element_contributions(frequency, theta0, phi0).iter().sum();
+        power / gain.norm_sqr()
+    }
+}
+
+/// Index of the first element whose row of `overlap` depends on the rows before it
+///
+/// That is the first leading submatrix that is singular.
+fn dependent_element(overlap: &[Vec<Complex<f64>>]) -> usize {
+    (1..=overlap.len())
+        .find(|&size| {
+            let leading: Vec<Vec<Complex<f64>>> = overlap[..size].iter().map(|row| row[..size].to_vec()).collect();
+            solve_complex(&leading, &vec![Complex::new(0.0, 0.0); size]).is_none()
+        })
+        .map_or(overlap.len().saturating_sub(1), |size| size - 1)
+}
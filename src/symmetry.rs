@@ -0,0 +1,66 @@
+//! Symmetry checks for tabulated patterns
+//!
+//! Measured patterns of symmetric antennas should be symmetric too, within
+//! the measurement error. Checking which symmetries hold is a quick sanity
+//! check on the data, and tells you which symmetries are safe to assume.
+
+use crate::{DataElement, PatternGrid, PI};
+
+/// Symmetries of a tabulated pattern, as found by `DataElement::check_symmetry`
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SymmetryReport {
+    /// Unchanged by any rotation about the z axis (a body of revolution)
+    pub rotational: bool,
+    /// Largest `n` such that rotating by `2 * PI / n` about z leaves the
+    /// pattern unchanged; 1 if there is no rotational symmetry
+    pub rotation_order: usize,
+    /// Mirror symmetric about the XZ plane, `g(theta, phi) == g(theta, -phi)`
+    pub mirror_xz: bool,
+    /// Mirror symmetric about the YZ plane, `g(theta, phi) == g(theta, PI - phi)`
+    pub mirror_yz: bool,
+    /// Mirror symmetric about the XY plane, `g(theta, phi) == g(PI - theta, phi)`
+    pub mirror_xy: bool,
+}
+
+/// Whether every sample of `grid` matches its image under `map` within `tolerance`
+fn holds(grid: &PatternGrid, tolerance: f64, map: impl Fn(f64, f64) -> (f64, f64)) -> bool {
+    grid.values().iter().enumerate().all(|(phi_idx, row)| {
+        let phi = grid.phi(phi_idx);
+        row.iter().enumerate().all(|(theta_idx, value)| {
+            let (theta, phi) = map(grid.theta(theta_idx), phi);
+            (value.norm() - grid.interpolate(theta, phi).norm()).abs() <= tolerance
+        })
+    })
+}
+
+impl DataElement {
+    /// Find which rotational and mirror symmetries the tables have
+    ///
+    /// Only magnitudes are compared, and a symmetry holds if every sample is
+    /// within `tolerance` (relative to the peak magnitude, e.g. 0.01) of its
+    /// image. Images that fall between samples are interpolated. With a
+    /// cross-polar table both tables have to be symmetric.
+    ///
+    pub fn check_symmetry(&self, tolerance: f64) -> SymmetryReport {
+        let grids: Vec<&PatternGrid> = std::iter::once(self.data()).chain(self.cross()).collect();
+        let peak = self.data().peak_power().sqrt();
+        let tolerance = tolerance * peak;
+        let all = |map: &dyn Fn(f64, f64) -> (f64, f64)| grids.iter().all(|grid| holds(grid, tolerance, map));
+
+        // rotations by a whole number of phi samples
+        let num_phi = self.data().num_phi();
+        let rotation_order = (1..=num_phi)
+            .rev()
+            .filter(|n| num_phi.is_multiple_of(*n))
+            .find(|n| *n == 1 || all(&|theta, phi| (theta, phi + 2.0 * PI / *n as f64)))
+            .unwrap_or(1);
+
+        SymmetryReport {
+            rotational: num_phi > 1 && rotation_order == num_phi,
+            rotation_order,
+            mirror_xz: all(&|theta, phi| (theta, -phi)),
+            mirror_yz: all(&|theta, phi| (theta, PI - phi)),
+            mirror_xy: all(&|theta, phi| (PI - theta, phi)),
+        }
+    }
+}
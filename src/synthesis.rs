@@ -0,0 +1,340 @@
+//! Phase-only pattern synthesis
+//!
+//! Contoured (footprint) beams are shaped with the intersection approach:
+//! the pattern alternates between the set of patterns that meet the
+//! coverage/sidelobe mask and the set of patterns the array can actually
+//! make with its fixed amplitude taper. Only the weight phases change.
+//!
+//! Flat-top sector beams use the same alternation, but with amplitude and
+//! phase free: each step is a least squares fit of the weights to the
+//! masked pattern.
+//!
+//! A phase-only approximation of an amplitude taper can't get there by
+//! alternating: the tapered pattern has the same phase as the uniform one,
+//! so the projections go straight back to uniform phase. Instead the phases
+//! are nudged one element at a time (a coordinate search) to shrink the
+//! largest amount by which the pattern rises above the tapered one.
+
+use num::complex::Complex;
+
+use crate::math::solve_complex;
+use crate::{effective_weight, element_response, separation, ElementArray, PI, SPEED_OF_LIGHT};
+
+/// Sphere sampling for the directions outside the coverage (radians)
+const OUTSIDE_STEP: f64 = 3.0 * PI / 180.0;
+
+/// Highest allowed gain outside the coverage, relative to the mean coverage gain (dB)
+const OUTSIDE_LEVEL_DB: f64 = -20.0;
+
+/// Sampling of the flat-top cut (radians)
+const FLATTOP_STEP: f64 = 0.5 * PI / 180.0;
+
+/// Number of least squares iterations for a flat-top beam
+const FLATTOP_ITERATIONS: usize = 200;
+
+/// Largest extent of the array (meters)
+fn array_size(array: &ElementArray) -> f64 {
+    array.elements
+        .iter()
+        .flat_map(|a| array.elements.iter().map(move |b| {
+            let (pa, pb) = (a.position(), b.position());
+            ((pa.x - pb.x).powi(2) + (pa.y - pb.y).powi(2) + (pa.z - pb.z).powi(2)).sqrt()
+        }))
+        .fold(0.0, f64::max)
+}
+
+/// Least squares weights `w` minimizing `sum |rows * w - desired|^2`
+///
+/// The complex normal equations are solved as a real system twice the size,
+/// with a little diagonal loading so that redundant directions can't make
+/// them singular.
+fn least_squares(rows: &[Vec<Complex<f64>>], desired: &[Complex<f64>]) -> Option<Vec<Complex<f64>>> {
+    let count = rows.first().map_or(0, |row| row.len());
+    let mut normal = vec![vec![Complex::new(0.0, 0.0); count]; count];
+    let mut rhs = vec![Complex::new(0.0, 0.0); count];
+    for (row, d) in rows.iter().zip(desired) {
+        for ((normal_row, rhs), a) in normal.iter_mut().zip(rhs.iter_mut()).zip(row) {
+            for (entry, b) in normal_row.iter_mut().zip(row) {
+                *entry += a.conj() * b;
+            }
+            *rhs += a.conj() * d;
+        }
+    }
+    let loading = 1e-9 * normal.iter().enumerate().map(|(idx, row)| row[idx].re).sum::<f64>() / count.max(1) as f64;
+    for (idx, row) in normal.iter_mut().enumerate() {
+        row[idx] += loading;
+    }
+    solve_complex(&normal, &rhs)
+}
+
+impl ElementArray {
+    /// Shape the beam to cover the `(theta, phi)` directions in `coverage`
+    ///
+    /// Each iteration pushes the gain inside the coverage up to a flat level
+    /// and clips it to 20 dB below that level outside, then picks the weight
+    /// phases that best match the result. Directions within one beamwidth
+    /// (wavelength / array size) of the coverage are left free as the
+    /// transition region. The weights with the highest minimum gain over
+    /// the coverage are kept, so the coverage never gets worse than the
+    /// starting weights.
+    ///
+    pub fn synthesize_footprint(&mut self, coverage: &[(f64, f64)], frequency: f64, iterations: usize) {
+        if coverage.is_empty() || self.elements.is_empty() {
+            return;
+        }
+
+        let wavelength = SPEED_OF_LIGHT / frequency;
+        let size = array_size(self);
+        let margin = if size > 0.0 { wavelength / size } else { PI };
+
+        let num_theta = (PI / OUTSIDE_STEP).round() as usize + 1;
+        let num_phi = (2.0 * PI / OUTSIDE_STEP).round() as usize;
+        let outside: Vec<(f64, f64)> = (0..num_phi)
+            .flat_map(|p| (0..num_theta).map(move |t| (t as f64 * OUTSIDE_STEP, p as f64 * OUTSIDE_STEP)))
+            .filter(|dir| coverage.iter().all(|c| separation(*dir, *c) > margin))
+            .collect();
+
+        // unit-weight contribution of every element in every direction
+        let directions: Vec<(f64, f64)> = coverage.iter().chain(&outside).cloned().collect();
+        let steering: Vec<Vec<Complex<f64>>> = directions
+            .iter()
+            .map(|&(theta, phi)| {
+                self.elements
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, n)| element_response(n.as_ref(), frequency, theta, phi).unwrap_or_default() * effective_weight(self, idx, Complex::new(1.0, 0.0)))
+                    .collect()
+            })
+            .collect();
+
+        let amplitudes: Vec<f64> = self.elements.iter().map(|n| n.weight().norm()).collect();
+        let mut weights: Vec<Complex<f64>> = self.elements.iter().map(|n| n.weight()).collect();
+        let field = |weights: &[Complex<f64>]| -> Vec<Complex<f64>> {
+            steering.iter().map(|row| row.iter().zip(weights).map(|(a, w)| a * w).sum()).collect()
+        };
+        let worst_coverage = |field: &[Complex<f64>]| {
+            field[..coverage.len()].iter().map(|f| f.norm()).fold(f64::INFINITY, f64::min)
+        };
+
+        let mut current = field(&weights);
+        let mut best = (worst_coverage(&current), weights.clone());
+        let outside_ratio = 10_f64.powf(OUTSIDE_LEVEL_DB / 20.0);
+        for _ in 0..iterations {
+            // project onto the patterns that meet the mask
+            let level = current[..coverage.len()].iter().map(|f| f.norm()).sum::<f64>() / coverage.len() as f64;
+            let desired: Vec<Complex<f64>> = current
+                .iter()
+                .enumerate()
+                .map(|(idx, f)| {
+                    let phase = if f.norm() > 0.0 { f / f.norm() } else { Complex::new(1.0, 0.0) };
+                    if idx < coverage.len() {
+                        phase * level.max(f.norm())
+                    } else {
+                        phase * f.norm().min(outside_ratio * level)
+                    }
+                })
+                .collect();
+
+            // back onto the patterns the array can make: matched phases, fixed amplitudes
+            for (idx, (weight, amplitude)) in weights.iter_mut().zip(&amplitudes).enumerate() {
+                let projection: Complex<f64> = steering.iter().zip(&desired).map(|(row, d)| row[idx].conj() * d).sum();
+                *weight = *amplitude * Complex::from_polar(1.0, projection.arg());
+            }
+
+            current = field(&weights);
+            let worst = worst_coverage(&current);
+            if worst > best.0 {
+                best = (worst, weights.clone());
+            }
+        }
+
+        for (n, weight) in self.elements.iter_mut().zip(best.1) {
+            n.set_weight(weight);
+        }
+    }
+
+    /// Shape a flat-top sector beam in the XZ plane
+    ///
+    /// The sector is `sector_width` (radians) wide, centered on boresight,
+    /// in the plane phi = 0/PI, which suits a linear array along x. Each
+    /// iteration clamps the pattern inside the sector to within `ripple_db`
+    /// (peak to peak) of its mean, clips it 20 dB below the mean outside,
+    /// and fits the weights (amplitude and phase) to the result by least
+    /// squares. Directions within one beamwidth (wavelength / array size) of
+    /// the sector edges are left free. The cut is sampled every half degree;
+    /// the weights with the smallest ripple are kept and scaled so the
+    /// largest is 1. Small arrays may not reach the ripple.
+    ///
+    pub fn synthesize_flattop(&mut self, frequency: f64, sector_width: f64, ripple_db: f64) {
+        if self.elements.is_empty() {
+            return;
+        }
+        let size = array_size(self);
+        let margin = if size > 0.0 { SPEED_OF_LIGHT / frequency / size } else { PI };
+        let half_width = sector_width / 2.0;
+
+        // signed angle from boresight in the XZ plane, and whether it's in the sector
+        let num = (PI / 2.0 / FLATTOP_STEP).round() as i64;
+        let cut: Vec<(f64, bool)> = (-num..=num)
+            .map(|idx| idx as f64 * FLATTOP_STEP)
+            .filter(|angle| angle.abs() <= half_width || angle.abs() > half_width + margin)
+            .map(|angle| (angle, angle.abs() <= half_width))
+            .collect();
+        let rows: Vec<Vec<Complex<f64>>> = cut
+            .iter()
+            .map(|(angle, _)| {
+                let (theta, phi) = if *angle < 0.0 { (-angle, PI) } else { (*angle, 0.0) };
+                self.elements
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, n)| element_response(n.as_ref(), frequency, theta, phi).unwrap_or_default() * effective_weight(self, idx, Complex::new(1.0, 0.0)))
+                    .collect()
+            })
+            .collect();
+        let field = |weights: &[Complex<f64>]| -> Vec<Complex<f64>> {
+            rows.iter().map(|row| row.iter().zip(weights).map(|(a, w)| a * w).sum()).collect()
+        };
+        let ripple = |field: &[Complex<f64>]| {
+            let inside = || cut.iter().zip(field).filter(|((_, inside), _)| *inside).map(|(_, f)| f.norm());
+            let high = inside().fold(0.0, f64::max);
+            let low = inside().fold(f64::INFINITY, f64::min);
+            20.0 * (high / low).log10()
+        };
+
+        // start from the ideal sector: flat inside, nothing outside
+        let ideal: Vec<Complex<f64>> = cut
+            .iter()
+            .map(|(_, inside)| Complex::new(if *inside { 1.0 } else { 0.0 }, 0.0))
+            .collect();
+        let Some(mut weights) = least_squares(&rows, &ideal) else { return };
+        let mut current = field(&weights);
+        let mut best = (ripple(&current), weights.clone());
+
+        let band = 10_f64.powf(ripple_db / 40.0);
+        let outside_ratio = 10_f64.powf(OUTSIDE_LEVEL_DB / 20.0);
+        for _ in 0..FLATTOP_ITERATIONS {
+            let inside: Vec<f64> = cut.iter().zip(&current).filter(|((_, inside), _)| *inside).map(|(_, f)| f.norm()).collect();
+            let level = inside.iter().sum::<f64>() / inside.len().max(1) as f64;
+            let desired: Vec<Complex<f64>> = current
+                .iter()
+                .zip(&cut)
+                .map(|(f, (_, inside))| {
+                    let phase = if f.norm() > 0.0 { f / f.norm() } else { Complex::new(1.0, 0.0) };
+                    if *inside {
+                        phase * f.norm().clamp(level / band, level * band)
+                    } else {
+                        phase * f.norm().min(outside_ratio * level)
+                    }
+                })
+                .collect();
+
+            let Some(next) = least_squares(&rows, &desired) else { break };
+            weights = next;
+            current = field(&weights);
+            let worst = ripple(&current);
+            if worst < best.0 {
+                best = (worst, weights.clone());
+            }
+        }
+
+        let peak = best.1.iter().map(|w| w.norm()).fold(0.0, f64::max);
+        for (n, weight) in self.elements.iter_mut().zip(best.1) {
+            n.set_weight(if peak > 0.0 { weight / peak } else { weight });
+        }
+    }
+
+    /// Phase-only weights that approximate the pattern of an amplitude taper
+    ///
+    /// The target is the pattern the array would have with its weight
+    /// amplitudes replaced by `target_taper` (one value per element, phases
+    /// kept). Only the weight phases change: they're chosen to minimize the
+    /// largest amount, over the sphere, by which the realized pattern rises
+    /// above the target, both normalized to their peaks. Since a taper
+    /// lowers the sidelobes by widening the main beam, that mostly pushes
+    /// down the sidelobes. Each of the `iterations` passes nudges each phase
+    /// in turn, halving the nudge after a pass that doesn't improve. The
+    /// sphere is sampled at a quarter of a beamwidth (wavelength / array
+    /// size), at most 3 degrees. The result is never further from the
+    /// target than the starting weights.
+    ///
+    /// Phase-only weights can't match a taper: expect a few dB lower
+    /// sidelobes than uniform, not the taper's.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there isn't one taper value per element.
+    ///
+    pub fn phase_only_taper_approx(&mut self, target_taper: &[f64], frequency: f64, iterations: usize) {
+        assert_eq!(target_taper.len(), self.elements.len(), "one taper value per element");
+        if self.elements.is_empty() {
+            return;
+        }
+
+        let size = array_size(self);
+        let step = if size > 0.0 { (SPEED_OF_LIGHT / frequency / size / 4.0).min(OUTSIDE_STEP) } else { OUTSIDE_STEP };
+        let num_theta = (PI / step).round() as usize + 1;
+        let num_phi = (2.0 * PI / step).round() as usize;
+        let theta_step = PI / (num_theta - 1) as f64;
+        let phi_step = 2.0 * PI / num_phi as f64;
+        let steering: Vec<Vec<Complex<f64>>> = (0..num_phi)
+            .flat_map(|p| (0..num_theta).map(move |t| (t as f64 * theta_step, p as f64 * phi_step)))
+            .map(|(theta, phi)| {
+                self.elements
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, n)| element_response(n.as_ref(), frequency, theta, phi).unwrap_or_default() * effective_weight(self, idx, Complex::new(1.0, 0.0)))
+                    .collect()
+            })
+            .collect();
+        let field = |weights: &[Complex<f64>]| -> Vec<Complex<f64>> {
+            steering.iter().map(|row| row.iter().zip(weights).map(|(a, w)| a * w).sum()).collect()
+        };
+        let peak = |field: &[Complex<f64>]| field.iter().map(|f| f.norm()).fold(0.0, f64::max);
+
+        let mut weights: Vec<Complex<f64>> = self.elements.iter().map(|n| n.weight()).collect();
+        let tapered: Vec<Complex<f64>> = weights
+            .iter()
+            .zip(target_taper)
+            .map(|(w, t)| if w.norm() > 0.0 { w / w.norm() * *t } else { Complex::new(*t, 0.0) })
+            .collect();
+        let target = field(&tapered);
+        let target_peak = peak(&target);
+        if target_peak <= 0.0 {
+            return;
+        }
+        let target: Vec<f64> = target.iter().map(|t| t.norm() / target_peak).collect();
+        let excess = |field: &[Complex<f64>]| -> f64 {
+            let peak = peak(field);
+            field.iter().zip(&target).map(|(f, t)| f.norm() / peak - t).fold(0.0, f64::max)
+        };
+
+        let mut current = field(&weights);
+        let mut best = excess(&current);
+        let mut nudge = PI / 2.0;
+        for _ in 0..iterations {
+            let mut improved = false;
+            for idx in 0..weights.len() {
+                for sign in [1.0, -1.0] {
+                    let candidate = weights[idx] * Complex::from_polar(1.0, sign * nudge);
+                    let change = candidate - weights[idx];
+                    let trial: Vec<Complex<f64>> = current.iter().zip(&steering).map(|(f, row)| f + row[idx] * change).collect();
+                    let trial_excess = excess(&trial);
+                    if trial_excess < best {
+                        best = trial_excess;
+                        weights[idx] = candidate;
+                        current = trial;
+                        improved = true;
+                        break;
+                    }
+                }
+            }
+            if !improved {
+                nudge /= 2.0;
+            }
+        }
+
+        for (n, weight) in self.elements.iter_mut().zip(weights) {
+            n.set_weight(weight);
+        }
+    }
+}
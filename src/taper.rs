@@ -0,0 +1,130 @@
+//! Amplitude tapers
+//!
+//! Tapers trade a little gain and beamwidth for lower sidelobes. A taper
+//! multiplies the existing element weights, so it can be combined with
+//! steering and with other tapers.
+
+use crate::math::{bessel_j0, bessel_j1_zero};
+use crate::{ElementArray, PI};
+
+/// Terms `(mu, coefficient)` of the radially symmetric Taylor (Hansen) taper
+///
+/// The taper at radial position `p` is the sum of `coefficient * J0(PI * mu * p)`,
+/// see `taylor_circular`. Needs `n_bar >= 2`; with fewer the taper is uniform
+/// and there is a single term.
+fn taylor_circular_terms(n_bar: usize, sll_db: f64) -> Vec<(f64, f64)> {
+    if n_bar < 2 {
+        return vec![(0.0, 1.0)];
+    }
+    let ratio = 10_f64.powf(sll_db.abs() / 20.0);
+    let a = ratio.acosh() / PI;
+
+    // zeros of J1(PI * mu)
+    let mu: Vec<f64> = (0..=n_bar)
+        .map(|m| if m == 0 { 0.0 } else { bessel_j1_zero(m) / PI })
+        .collect();
+
+    let sigma = mu[n_bar] / (a * a + (n_bar as f64 - 0.5).powi(2)).sqrt();
+    let u: Vec<f64> = (1..n_bar)
+        .map(|n| sigma * (a * a + (n as f64 - 0.5).powi(2)).sqrt())
+        .collect();
+
+    (0..n_bar)
+        .map(|m| {
+            let coefficient = if m == 0 {
+                1.0
+            } else {
+                let num: f64 = u.iter().map(|un| 1.0 - mu[m].powi(2) / un.powi(2)).product();
+                let den: f64 = (1..n_bar)
+                    .filter(|n| *n != m)
+                    .map(|n| 1.0 - mu[m].powi(2) / mu[n].powi(2))
+                    .product();
+                -bessel_j0(PI * mu[m]) * num / den
+            };
+            (mu[m], coefficient / bessel_j0(PI * mu[m]).powi(2))
+        })
+        .collect()
+}
+
+/// Radially symmetric Taylor (Hansen) weight for a circular aperture
+///
+/// `terms` come from `taylor_circular_terms` and `p` is the radial position
+/// normalized to the aperture radius. The result is not normalized.
+fn taylor_circular(terms: &[(f64, f64)], p: f64) -> f64 {
+    terms.iter().map(|(mu, coefficient)| coefficient * bessel_j0(PI * mu * p)).sum()
+}
+
+/// Taylor weights for an evenly spaced linear array of `num` elements
+///
+/// `n_bar` is the number of near-in sidelobes held at `sll_db` (e.g. -30.0).
+/// The weights are symmetric about the center of the array and scaled so the
+/// largest is 1.
+///
+pub fn taylor_linear(num: usize, n_bar: usize, sll_db: f64) -> Vec<f64> {
+    let ratio = 10_f64.powf(sll_db.abs() / 20.0);
+    let a = ratio.acosh() / PI;
+    let sigma2 = (n_bar as f64).powi(2) / (a * a + (n_bar as f64 - 0.5).powi(2));
+
+    // coefficients of the cosine series for m = 1..n_bar
+    let coefficients: Vec<f64> = (1..n_bar)
+        .map(|m| {
+            let m = m as f64;
+            let num: f64 = (1..n_bar)
+                .map(|n| 1.0 - m * m / (sigma2 * (a * a + (n as f64 - 0.5).powi(2))))
+                .product();
+            let den: f64 = (1..n_bar)
+                .filter(|n| *n as f64 != m)
+                .map(|n| 1.0 - m * m / (n as f64).powi(2))
+                .product();
+            -(-1_f64).powf(m) * num / (2.0 * den)
+        })
+        .collect();
+
+    let taper: Vec<f64> = (0..num)
+        .map(|idx| {
+            let x = (idx as f64 - (num as f64 - 1.0) / 2.0) / num as f64;
+            1.0 + 2.0 * coefficients
+                .iter()
+                .enumerate()
+                .map(|(m, f)| f * (2.0 * PI * (m + 1) as f64 * x).cos())
+                .sum::<f64>()
+        })
+        .collect();
+    let peak = taper.iter().cloned().fold(0.0, f64::max);
+    taper.iter().map(|t| t / peak).collect()
+}
+
+impl ElementArray {
+    /// Apply a circular Taylor taper to a planar array in the XY plane
+    ///
+    /// Each element is weighted by its radial distance from the centroid of
+    /// the array, with the outermost element defining the aperture radius.
+    /// `n_bar` is the number of near-in sidelobes held at `sll_db` (e.g.
+    /// -30.0); the weights are scaled so the largest is 1. With `n_bar` below
+    /// 2 there are no sidelobes to hold and the taper is uniform, the same as
+    /// `taylor_linear`.
+    ///
+    pub fn apply_taylor_circular(&mut self, n_bar: usize, sll_db: f64) {
+        let count = self.elements.len() as f64;
+        let cx = self.elements.iter().map(|n| n.position().x).sum::<f64>() / count;
+        let cy = self.elements.iter().map(|n| n.position().y).sum::<f64>() / count;
+
+        let radii: Vec<f64> = self.elements
+            .iter()
+            .map(|n| ((n.position().x - cx).powi(2) + (n.position().y - cy).powi(2)).sqrt())
+            .collect();
+        let radius = radii.iter().cloned().fold(0.0, f64::max);
+
+        let terms = taylor_circular_terms(n_bar, sll_db);
+        let taper: Vec<f64> = radii
+            .iter()
+            .map(|r| if radius > 0.0 { taylor_circular(&terms, r / radius) } else { 1.0 })
+            .collect();
+        let peak = taper.iter().cloned().fold(0.0, f64::max);
+
+        for (n, t) in self.elements.iter_mut().zip(taper) {
+            let weight = n.weight() * (t / peak);
+            n.set_weight(weight);
+        }
+    }
+}
@@ -0,0 +1,68 @@
+//! Thinned array metrics
+//!
+//! A thinned array switches off some of the elements of a filled lattice
+//! (zero weight) to save cost while keeping the full aperture, and so the
+//! full beamwidth. These measure how far an array has been thinned.
+
+use crate::{effective_weight, ElementArray, ElementIface};
+
+impl ElementArray {
+    /// Fraction of elements that are switched off (zero weight)
+    ///
+    /// 0 for a filled array, 1 when every element is off. An empty array
+    /// gives 0.
+    ///
+    pub fn sparsity(&self) -> f64 {
+        if self.elements.is_empty() {
+            return 0.0;
+        }
+        let disabled = (0..self.elements.len()).filter(|idx| !is_active(self, *idx)).count();
+        disabled as f64 / self.elements.len() as f64
+    }
+
+    /// Size of the aperture spanned by the active elements relative to the whole array
+    ///
+    /// The extent of the element positions is taken along x, y and z, and
+    /// the product over the axes the whole array extends along is compared
+    /// for active (non-zero weight) and all elements. A thinned array that
+    /// keeps its edge elements gives 1; turning off the edges shrinks the
+    /// aperture and broadens the beam. Gives 0 if no element is active, and
+    /// 1 if the whole array sits at a single point.
+    ///
+    pub fn active_aperture_fill(&self) -> f64 {
+        let active: Vec<&dyn ElementIface> = self.elements
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| is_active(self, *idx))
+            .map(|(_, n)| n.as_ref())
+            .collect();
+        if active.is_empty() {
+            return 0.0;
+        }
+        let all: Vec<&dyn ElementIface> = self.elements.iter().map(|n| n.as_ref()).collect();
+
+        let (full, used) = (extents(&all), extents(&active));
+        full.iter()
+            .zip(used)
+            .filter(|(full, _)| **full > 0.0)
+            .map(|(full, used)| used / full)
+            .product()
+    }
+}
+
+/// Whether element `idx` of `array` radiates at all
+fn is_active(array: &ElementArray, idx: usize) -> bool {
+    effective_weight(array, idx, array.elements[idx].weight()).norm_sqr() > 0.0
+}
+
+/// Extent of the element positions along x, y and z (meters)
+fn extents(elements: &[&dyn ElementIface]) -> [f64; 3] {
+    let extent = |coordinate: fn(&dyn ElementIface) -> f64| {
+        let (min, max) = elements
+            .iter()
+            .map(|n| coordinate(*n))
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), x| (min.min(x), max.max(x)));
+        max - min
+    };
+    [extent(|n| n.position().x), extent(|n| n.position().y), extent(|n| n.position().z)]
+}
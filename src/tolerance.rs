@@ -0,0 +1,30 @@
+//! Manufacturing tolerances
+//!
+//! No array is built exactly as drawn. Jittering the element positions with
+//! random errors of the size the mechanical tolerances allow shows how
+//! sensitive the pattern is to them.
+
+use crate::rng::Rng;
+use crate::{ElementArray, Point};
+
+impl ElementArray {
+    /// Move every element by independent Gaussian errors in x, y and z
+    ///
+    /// Each coordinate gets an error with standard deviation `std_dev_m`
+    /// (meters). The same `seed` always gives the same errors, so a study can
+    /// be repeated. The weights aren't changed; they stay the ones designed
+    /// for the nominal positions.
+    ///
+    pub fn perturb_positions(&mut self, std_dev_m: f64, seed: u64) {
+        let mut rng = Rng::new(seed);
+        for n in self.elements.iter_mut() {
+            let position = n.position();
+            let perturbed = Point {
+                x: position.x + std_dev_m * rng.gaussian(),
+                y: position.y + std_dev_m * rng.gaussian(),
+                z: position.z + std_dev_m * rng.gaussian(),
+            };
+            n.set_position(perturbed);
+        }
+    }
+}
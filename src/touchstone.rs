@@ -0,0 +1,73 @@
+//! Touchstone network parameter files
+//!
+//! Only one-port files (`.s1p`) are read, for the input match of an
+//! element. The option line sets the frequency unit and the number format;
+//! anything after a `!` is a comment.
+
+use num::complex::Complex;
+
+use crate::data_files::parse_numbers;
+use crate::DataFileError;
+
+/// Parse the contents of a `.s1p` file into `(frequency (Hz), S11)` pairs
+///
+/// The option line defaults to `# GHz S MA R 50` as in the Touchstone spec;
+/// only S parameters are supported. Frequencies must be increasing.
+///
+pub(crate) fn parse_s1p(contents: &str) -> Result<Vec<(f64, Complex<f64>)>, DataFileError> {
+    let mut unit = 1e9;
+    let mut format = "MA".to_string();
+    let mut data = Vec::new();
+
+    for (idx, line) in contents.lines().enumerate() {
+        let line_number = idx + 1;
+        let line = line.split('!').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(options) = line.strip_prefix('#') {
+            let error = |message: String| DataFileError::Parse { line: line_number, message };
+            let mut tokens = options.split_whitespace().map(|token| token.to_uppercase());
+            while let Some(token) = tokens.next() {
+                match token.as_str() {
+                    "HZ" => unit = 1.0,
+                    "KHZ" => unit = 1e3,
+                    "MHZ" => unit = 1e6,
+                    "GHZ" => unit = 1e9,
+                    "S" => {}
+                    "Y" | "Z" | "H" | "G" => return Err(error(format!("unsupported parameter type {}", token))),
+                    "MA" | "DB" | "RI" => format = token,
+                    // reference impedance, the S parameters are already normalized to it
+                    "R" => {
+                        tokens.next();
+                    }
+                    _ => return Err(error(format!("unknown option '{}'", token))),
+                }
+            }
+            continue;
+        }
+
+        let values = parse_numbers(line, line_number)?;
+        if values.len() != 3 {
+            return Err(DataFileError::Parse {
+                line: line_number,
+                message: "expected frequency and one complex S11 (3 columns)".to_string(),
+            });
+        }
+        let s11 = match format.as_str() {
+            "MA" => Complex::from_polar(values[1], values[2].to_radians()),
+            "DB" => Complex::from_polar(10_f64.powf(values[1] / 20.0), values[2].to_radians()),
+            _ => Complex::new(values[1], values[2]),
+        };
+        let frequency = values[0] * unit;
+        if data.last().is_some_and(|(last, _)| frequency <= *last) {
+            return Err(DataFileError::Parse {
+                line: line_number,
+                message: "frequencies must be increasing".to_string(),
+            });
+        }
+        data.push((frequency, s11));
+    }
+    Ok(data)
+}
@@ -0,0 +1,85 @@
+//! Gain along a sequence of look directions
+//!
+//! Sweeping the array along a satellite ground track evaluates the same
+//! elements at many directions, so the per-element terms that don't depend on
+//! direction are worked out once up front. The array can also follow a
+//! moving target, steering one step ahead of it.
+
+use num::complex::Complex;
+
+use crate::{delay_phase, effective_weight, phase_convention, ElementArray, I, PI, SPEED_OF_LIGHT};
+
+impl ElementArray {
+    /// Gain magnitude at each (theta, phi) look direction, in order
+    ///
+    /// Gives the same result as calling `get_gain` for every direction. An
+    /// empty track gives an empty result.
+    ///
+    pub fn gain_along_track(&self, frequency: f64, directions: &[(f64, f64)]) -> Vec<f64> {
+        let k = phase_convention().sign() * 2.0 * PI * frequency / SPEED_OF_LIGHT;
+        // (k * position, weight with the delay) for each element
+        let terms: Vec<([f64; 3], Complex<f64>)> = self.elements
+            .iter()
+            .enumerate()
+            .map(|(idx, n)| {
+                let p = n.position();
+                ([k * p.x, k * p.y, k * p.z], effective_weight(self, idx, n.weight()) * delay_phase(n.delay(), frequency))
+            })
+            .collect();
+
+        directions
+            .iter()
+            .map(|&(theta, phi)| {
+                let u = [theta.sin() * phi.cos(), theta.sin() * phi.sin(), theta.cos()];
+                self.elements
+                    .iter()
+                    .zip(&terms)
+                    .map(|(n, (kp, weight))| {
+                        let phase = (I * (kp[0] * u[0] + kp[1] * u[1] + kp[2] * u[2])).exp();
+                        phase * n.pattern(frequency, theta, phi).unwrap() * weight
+                    })
+                    .sum::<Complex<f64>>()
+                    .norm()
+            })
+            .collect()
+    }
+
+    /// Follow a moving target, returning the realized gain magnitude towards it at each step
+    ///
+    /// `target_history[i]` is the `(theta, phi)` of the target at step `i`,
+    /// equally spaced in time. At each step the array only knows where the
+    /// target was before: it steers (like `steer`) to the linear
+    /// extrapolation of the last two positions, continued along the great
+    /// circle through them so it behaves across the pole, and the gain is
+    /// then evaluated towards where the target actually is. The first step
+    /// points at the first position and the second holds it. Runs for
+    /// `steps` steps, or the length of the history if that's shorter, and
+    /// leaves the array steered for the last one.
+    ///
+    pub fn track(&mut self, frequency: f64, target_history: &[(f64, f64)], steps: usize) -> Vec<f64> {
+        let steps = steps.min(target_history.len());
+        (0..steps)
+            .map(|idx| {
+                let (theta0, phi0) = match idx {
+                    0 | 1 => target_history[0],
+                    _ => extrapolate(target_history[idx - 2], target_history[idx - 1]),
+                };
+                self.steer(frequency, theta0, phi0);
+                let (theta, phi) = target_history[idx];
+                self.realized_gain(frequency, theta, phi).unwrap().norm()
+            })
+            .collect()
+    }
+}
+
+/// Direction one step on from `current`, continuing the move from `previous`
+///
+/// Reflecting `previous` through `current` keeps going along the same great
+/// circle by the same angle.
+fn extrapolate(previous: (f64, f64), current: (f64, f64)) -> (f64, f64) {
+    let unit = |(theta, phi): (f64, f64)| [theta.sin() * phi.cos(), theta.sin() * phi.sin(), theta.cos()];
+    let (a, b) = (unit(previous), unit(current));
+    let dot: f64 = (0..3).map(|idx| a[idx] * b[idx]).sum();
+    let next: Vec<f64> = (0..3).map(|idx| 2.0 * dot * b[idx] - a[idx]).collect();
+    (next[2].clamp(-1.0, 1.0).acos(), next[1].atan2(next[0]).rem_euclid(2.0 * PI))
+}
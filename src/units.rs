@@ -0,0 +1,116 @@
+//! Gain reference conversions
+//!
+//! Gains in this crate are relative to an isotropic radiator (dBi). Other
+//! references are common in data sheets: a half-wave dipole (dBd) or an
+//! isotropic circularly polarized radiator (dBic). EMC test engineers work
+//! in antenna factor and effective height instead.
+
+use crate::{PatternGrid, FREE_SPACE_IMPEDANCE, PI, SPEED_OF_LIGHT};
+
+/// Gain of a lossless half-wave dipole (dBi)
+pub const DIPOLE_GAIN_DBI: f64 = 2.15;
+
+/// Polarization of the antenna whose gain is being converted
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Polarization {
+    /// Linearly polarized, only half its power couples to a circular reference
+    Linear,
+    /// Circularly polarized, with the gain given in its own sense
+    Circular,
+}
+
+/// Reference impedance of the receiver that antenna factors are quoted for (ohms)
+const ANTENNA_FACTOR_IMPEDANCE: f64 = 50.0;
+
+/// Gain relative to a half-wave dipole (dBd) from dBi
+pub fn dbi_to_dbd(dbi: f64) -> f64 {
+    dbi - DIPOLE_GAIN_DBI
+}
+
+/// Gain relative to an isotropic circularly polarized radiator (dBic) from dBi
+///
+/// A circularly polarized gain is already matched to the reference. A
+/// linearly polarized antenna picks up half the power of a circular wave, so
+/// it is 3 dB down.
+///
+pub fn dbi_to_dbic(dbi: f64, polarization: Polarization) -> f64 {
+    match polarization {
+        Polarization::Linear => dbi - 10.0 * 2_f64.log10(),
+        Polarization::Circular => dbi,
+    }
+}
+
+/// Antenna factor (dB/m) of a matched antenna with this gain (dBi) in a 50 ohm system
+///
+/// The antenna factor is the ratio of the incident field to the voltage at
+/// the receiver, `AF = sqrt(4 * PI * eta / (50 * G)) / wavelength`, which is
+/// the familiar `20 log10(f_MHz) - G - 29.79` (29.77 without rounding the
+/// constants).
+///
+pub fn gain_to_antenna_factor(gain_dbi: f64, frequency: f64) -> f64 {
+    let wavelength = SPEED_OF_LIGHT / frequency;
+    let gain = 10_f64.powf(gain_dbi / 10.0);
+    let factor = (4.0 * PI * FREE_SPACE_IMPEDANCE / (ANTENNA_FACTOR_IMPEDANCE * gain)).sqrt() / wavelength;
+    20.0 * factor.log10()
+}
+
+/// Gain (dBi) from an antenna factor (dB/m) in a 50 ohm system
+pub fn antenna_factor_to_gain(antenna_factor_db: f64, frequency: f64) -> f64 {
+    gain_to_antenna_factor(0.0, frequency) - antenna_factor_db
+}
+
+/// Effective height (m) of a matched antenna with this gain (dBi)
+///
+/// `impedance` is the radiation resistance of the antenna (ohms). The open
+/// circuit voltage is the effective height times the incident field:
+/// `h = (wavelength / PI) * sqrt(G * R / 120)`, about `wavelength / PI` for a
+/// half-wave dipole.
+///
+pub fn gain_to_effective_height(gain_dbi: f64, frequency: f64, impedance: f64) -> f64 {
+    let wavelength = SPEED_OF_LIGHT / frequency;
+    let gain = 10_f64.powf(gain_dbi / 10.0);
+    2.0 * wavelength * (gain * impedance / (4.0 * PI * FREE_SPACE_IMPEDANCE)).sqrt()
+}
+
+/// Gain (dBi) from an effective height (m) and radiation resistance (ohms)
+pub fn effective_height_to_gain(height: f64, frequency: f64, impedance: f64) -> f64 {
+    let wavelength = SPEED_OF_LIGHT / frequency;
+    let gain = PI * FREE_SPACE_IMPEDANCE * (height / wavelength).powi(2) / impedance;
+    10.0 * gain.log10()
+}
+
+impl PatternGrid {
+    /// Peak gain of a lossless antenna with this pattern (dBi)
+    ///
+    /// This is the directivity: peak power over the power averaged over the
+    /// sphere.
+    ///
+    pub fn peak_gain_dbi(&self) -> f64 {
+        10.0 * (4.0 * PI * self.peak_power() / self.radiated_power()).log10()
+    }
+
+    /// Peak gain of a lossless antenna with this pattern (dBd)
+    pub fn peak_gain_dbd(&self) -> f64 {
+        dbi_to_dbd(self.peak_gain_dbi())
+    }
+
+    /// Highest gain (dBi) at elevations from `el_low` to `el_high` (radians), over all azimuths
+    ///
+    /// Elevation is measured up from the XY plane, `PI/2 - theta`, so a
+    /// band just above zero is the horizon that regulatory masks limit. The
+    /// gain is that of a lossless antenna, as in `peak_gain_dbi`. Returns
+    /// negative infinity if no sample falls in the band.
+    ///
+    pub fn max_gain_in_elevation_band(&self, el_low: f64, el_high: f64) -> f64 {
+        let band_power = self.values()
+            .iter()
+            .flat_map(|row| row.iter().enumerate())
+            .filter(|(theta_idx, _)| {
+                let el = PI / 2.0 - self.theta(*theta_idx);
+                el >= el_low && el <= el_high
+            })
+            .map(|(_, value)| value.norm_sqr())
+            .fold(0.0, f64::max);
+        10.0 * (4.0 * PI * band_power / self.radiated_power()).log10()
+    }
+}
@@ -0,0 +1,128 @@
+//! Direction-cosine (u-v) sampling
+//!
+//! Some simulators export far fields on a rectangular kx-ky grid instead of
+//! theta/phi. Dividing by k gives the direction cosines `u = sin(theta) *
+//! cos(phi)` and `v = sin(theta) * sin(phi)`, which only cover the
+//! hemisphere in front of the XY plane (z >= 0).
+
+use num::complex::Complex;
+
+use crate::{ElementArray, GainIface, PatternGrid, PI};
+
+/// Complex gain sampled on a rectangular u-v grid
+///
+/// Both axes must be sorted in increasing order but need not be evenly
+/// spaced. Samples are stored one v row at a time. For kx-ky data divide the
+/// axes by the wavenumber first.
+#[derive(Clone, Debug)]
+pub struct UvGrid {
+    u: Vec<f64>,
+    v: Vec<f64>,
+    // values[v_idx][u_idx]
+    values: Vec<Vec<Complex<f64>>>,
+}
+
+/// Index of the cell containing `x` and the fraction across it, or `None` outside the axis
+fn locate(axis: &[f64], x: f64) -> Option<(usize, f64)> {
+    if axis.len() < 2 || x < axis[0] || x > axis[axis.len() - 1] {
+        return None;
+    }
+    let idx = axis.partition_point(|a| *a <= x).clamp(1, axis.len() - 1) - 1;
+    Some( (idx, (x - axis[idx]) / (axis[idx + 1] - axis[idx])) )
+}
+
+impl UvGrid {
+    /// Create a grid from its axes and samples
+    ///
+    /// # Panics
+    ///
+    /// Panics if `values` isn't `v.len()` rows of `u.len()` samples.
+    ///
+    pub fn new(u: Vec<f64>, v: Vec<f64>, values: Vec<Vec<Complex<f64>>>) -> UvGrid {
+        assert_eq!(values.len(), v.len(), "one row of samples per v");
+        assert!(values.iter().all(|row| row.len() == u.len()), "one sample per u in every row");
+        UvGrid { u, v, values }
+    }
+
+    /// Build a grid by evaluating `f(u, v)` at every sample
+    pub fn from_fn<F>(u: Vec<f64>, v: Vec<f64>, mut f: F) -> UvGrid
+    where
+        F: FnMut(f64, f64) -> Complex<f64>,
+    {
+        let values = v.iter().map(|v| u.iter().map(|u| f(*u, *v)).collect()).collect();
+        UvGrid { u, v, values }
+    }
+
+    /// Sample the front hemisphere of a theta/phi pattern onto the given axes
+    ///
+    /// Samples outside the visible circle `u^2 + v^2 <= 1` are zero.
+    ///
+    pub fn from_pattern(pattern: &PatternGrid, u: Vec<f64>, v: Vec<f64>) -> UvGrid {
+        UvGrid::from_fn(u, v, |u, v| {
+            if u * u + v * v > 1.0 {
+                return Complex::new(0.0, 0.0);
+            }
+            pattern.interpolate((u * u + v * v).sqrt().asin(), v.atan2(u).rem_euclid(2.0 * PI))
+        })
+    }
+
+    /// The u axis
+    pub fn u(&self) -> &[f64] {
+        &self.u
+    }
+
+    /// The v axis
+    pub fn v(&self) -> &[f64] {
+        &self.v
+    }
+
+    /// All samples, one v row at a time
+    pub fn values(&self) -> &[Vec<Complex<f64>>] {
+        &self.values
+    }
+
+    /// Bilinearly interpolate the complex gain at `(u, v)`
+    ///
+    /// Returns `None` outside the sampled rectangle.
+    ///
+    pub fn interpolate(&self, u: f64, v: f64) -> Option<Complex<f64>> {
+        let (u_idx, u_frac) = locate(&self.u, u)?;
+        let (v_idx, v_frac) = locate(&self.v, v)?;
+        let row = |idx: usize| self.values[idx][u_idx] * (1.0 - u_frac) + self.values[idx][u_idx + 1] * u_frac;
+        Some( row(v_idx) * (1.0 - v_frac) + row(v_idx + 1) * v_frac )
+    }
+
+    /// Resample onto a theta/phi grid
+    ///
+    /// Directions in the front hemisphere are interpolated from the u-v
+    /// samples. The back hemisphere (theta > PI/2), and anything outside the
+    /// sampled rectangle, is zero.
+    ///
+    pub fn to_pattern(&self, theta_step: f64, phi_step: f64) -> PatternGrid {
+        PatternGrid::from_fn(theta_step, phi_step, |theta, phi| {
+            if theta > PI / 2.0 + 1e-12 {
+                return Complex::new(0.0, 0.0);
+            }
+            self.interpolate(theta.sin() * phi.cos(), theta.sin() * phi.sin())
+                .unwrap_or_default()
+        })
+    }
+}
+
+impl ElementArray {
+    /// Complex gain at `n` evenly spaced points on the edge of visible space
+    ///
+    /// The edge `u^2 + v^2 = 1` is the horizon (theta = PI/2), where grating
+    /// lobes enter visible space as the array is scanned or the frequency
+    /// goes up. Returns `(u, v, gain)` going counterclockwise from `(1, 0)`.
+    ///
+    pub fn visible_region_gain(&self, frequency: f64, n: usize) -> Vec<(f64, f64, Complex<f64>)> {
+        (0..n)
+            .map(|idx| {
+                let phi = 2.0 * PI * idx as f64 / n as f64;
+                let gain = self.get_gain(frequency, PI / 2.0, phi).unwrap_or_default();
+                (phi.cos(), phi.sin(), gain)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,51 @@
+//! JavaScript interface
+//!
+//! `wasm-bindgen` wrappers so the library can run in the browser, e.g. for
+//! interactive pattern plots. Complex numbers cross the boundary as
+//! `[re, im]` arrays. The core has no file I/O, so build with
+//! `--no-default-features --features wasm` for `wasm32-unknown-unknown`.
+
+use wasm_bindgen::prelude::*;
+
+use crate::{ElementArray, ElementIface, GainIface, OmniElementBuilder, Point};
+
+/// An array that can be driven from JavaScript
+#[wasm_bindgen]
+pub struct WasmArray {
+    array: ElementArray,
+}
+
+#[wasm_bindgen]
+impl WasmArray {
+    /// Linear array of `num` isotropic elements along x, `spacing` meters apart
+    #[wasm_bindgen(js_name = uniformLinear)]
+    pub fn uniform_linear(num: usize, spacing: f64) -> WasmArray {
+        let array = ElementArray::uniform_linear(num, spacing, |position: Point| -> Box<dyn ElementIface> {
+            Box::new(OmniElementBuilder::default().position(position).gain(1.0).build().unwrap())
+        });
+        WasmArray { array }
+    }
+
+    /// Number of elements in the array
+    pub fn len(&self) -> usize {
+        self.array.elements.len()
+    }
+
+    /// Whether the array has no elements
+    #[wasm_bindgen(js_name = isEmpty)]
+    pub fn is_empty(&self) -> bool {
+        self.array.elements.is_empty()
+    }
+
+    /// Steer the main beam to `(theta0, phi0)` at `frequency`
+    pub fn steer(&mut self, frequency: f64, theta0: f64, phi0: f64) {
+        self.array.steer(frequency, theta0, phi0);
+    }
+
+    /// Complex gain as `[re, im]`, or `undefined` if there is none in that direction
+    pub fn gain(&self, frequency: f64, theta: f64, phi: f64) -> Option<Vec<f64>> {
+        self.array
+            .get_gain(frequency, theta, phi)
+            .map(|gain| vec![gain.re, gain.im])
+    }
+}
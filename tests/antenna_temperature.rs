@@ -0,0 +1,42 @@
+use antenna_pattern_generator_lib as apg;
+
+use apg::GainIface;
+
+mod support;
+use support::{omni, point};
+
+#[test]
+fn uniform_sky() {
+    let step = 1.0 * apg::PI / 180.0;
+    let array = apg::ElementArray::new( vec![omni(point(0.0, 0.0))] );
+    let grid = array.sample_sphere(1e9, step, step);
+
+    let temperature = grid.antenna_temperature(|_, _| 290.0);
+    assert!((temperature - 290.0).abs() < 1e-9);
+}
+
+#[test]
+fn hot_spot() {
+    let frequency = 1e9;
+    let spacing = apg::SPEED_OF_LIGHT / frequency / 2.0;
+    let step = 1.0 * apg::PI / 180.0;
+
+    // hot spot straight up (theta = 0) on a cold sky
+    let sky = |theta: f64, _phi: f64| if theta < 10.0 * apg::PI / 180.0 { 5000.0 } else { 10.0 };
+
+    let omni_temperature = apg::ElementArray::new( vec![omni(point(0.0, 0.0))] )
+        .sample_sphere(frequency, step, step)
+        .antenna_temperature(sky);
+
+    let mut elements: Vec<Box<dyn apg::ElementIface>> = Vec::new();
+    for row in 0..6 {
+        for col in 0..6 {
+            elements.push(omni(point(col as f64 * spacing, row as f64 * spacing)));
+        }
+    }
+    let array_temperature = apg::ElementArray::new( elements )
+        .sample_sphere(frequency, step, step)
+        .antenna_temperature(sky);
+
+    assert!(array_temperature > 2.0 * omni_temperature);
+}
@@ -0,0 +1,78 @@
+use antenna_pattern_generator_lib as apg;
+
+use num::complex::Complex;
+
+fn sinc(x: f64) -> f64 {
+    if x == 0.0 { 1.0 } else { x.sin() / x }
+}
+
+#[test]
+fn uniform_rectangular_aperture() {
+    let frequency = 10e9;
+    let wavelength = apg::SPEED_OF_LIGHT / frequency;
+    let k = 2.0 * apg::PI / wavelength;
+    let (a, b) = (4.0 * wavelength, 2.5 * wavelength);
+    let uniform = |_: f64, _: f64| Complex::new(1.0, 0.0);
+
+    for (theta, phi) in [(0.0_f64, 0.0_f64), (0.1, 0.0), (0.3, 0.7), (0.6, 2.0), (1.2, 4.0)] {
+        let (u, v) = (theta.sin() * phi.cos(), theta.sin() * phi.sin());
+        let expected = a * b * sinc(k * a * u / 2.0) * sinc(k * b * v / 2.0);
+        let field = apg::aperture_far_field(uniform, (a, b), frequency, theta, phi);
+        assert!((field - expected).norm() < 1e-3 * a * b, "{} {}", field, expected);
+    }
+}
+
+#[test]
+fn offset_illumination() {
+    // a field that is only on the +x half acts like a smaller aperture shifted along x
+    let frequency = 10e9;
+    let wavelength = apg::SPEED_OF_LIGHT / frequency;
+    let (a, b) = (4.0 * wavelength, 4.0 * wavelength);
+    let half = |x: f64, _: f64| Complex::new(if x > 0.0 { 1.0 } else { 0.0 }, 0.0);
+
+    let (theta, phi): (f64, f64) = (0.2, 0.0);
+    let k = 2.0 * apg::PI / wavelength;
+    let u = theta.sin();
+    let expected = a / 2.0 * b * sinc(k * a * u / 4.0) * Complex::from_polar(1.0, k * a * u / 4.0);
+    let field = apg::aperture_far_field(half, (a, b), frequency, theta, phi);
+    assert!((field - expected).norm() < 1e-3 * a * b);
+}
+
+#[test]
+fn sampled_distribution() {
+    let frequency = 10e9;
+    let wavelength = apg::SPEED_OF_LIGHT / frequency;
+    let spacing = wavelength / 2.0;
+    let length = 8.0 * spacing;
+    // 8 elements at the centers of 8 equal cells of the aperture
+    let positions: Vec<apg::Point> = (0..8)
+        .map(|n| apg::PointBuilder::default().x((n as f64 - 3.5) * spacing).build().unwrap())
+        .collect();
+
+    let uniform = apg::sample_aperture_distribution(&positions, |_, _| Complex::new(1.0, 0.0));
+    assert!(uniform.iter().all(|w| *w == Complex::new(1.0, 0.0)));
+
+    let cosine = apg::sample_aperture_distribution(&positions, |x, _| Complex::new((apg::PI * x / length).cos(), 0.0));
+    for (w, p) in cosine.iter().zip(&positions) {
+        assert!((w.re - (apg::PI * p.x() / length).cos()).abs() < 1e-12);
+    }
+    // symmetric and tapered towards the edges
+    assert!((cosine[0] - cosine[7]).norm() < 1e-12);
+    assert!(cosine[0].re < cosine[2].re && cosine[2].re < cosine[3].re);
+
+    // and the weighted array approximates the continuous aperture
+    let mut array = apg::ElementArray::new( positions
+        .iter()
+        .map(|p| Box::new(apg::OmniElementBuilder::default().position(p.clone()).gain(1.0).build().unwrap()) as Box<dyn apg::ElementIface>)
+        .collect() );
+    for (n, w) in array.elements_mut().iter_mut().zip(&cosine) {
+        n.set_weight(*w);
+    }
+    let cell = spacing * wavelength;
+    let distribution = |x: f64, _: f64| Complex::new((apg::PI * x / length).cos(), 0.0);
+    for theta in [0.0, 0.1, 0.2] {
+        let discrete = apg::GainIface::get_gain(&array, frequency, theta, 0.0).unwrap() * cell;
+        let continuous = apg::aperture_far_field(distribution, (length, wavelength), frequency, theta, 0.0);
+        assert!((discrete - continuous).norm() < 0.05 * continuous.norm(), "{} vs {}", discrete, continuous);
+    }
+}
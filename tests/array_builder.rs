@@ -0,0 +1,37 @@
+use antenna_pattern_generator_lib as apg;
+
+use num::complex::Complex;
+
+#[test]
+fn triangular_taper() {
+    let spacing = apg::SPEED_OF_LIGHT / 1e9 / 2.0;
+    let elements: Vec<Box<dyn apg::ElementIface>> = (0..5)
+        .map(|n| Box::new(apg::OmniElementBuilder::default()
+            .position(apg::PointBuilder::default().x(n as f64 * spacing).build().unwrap())
+            .gain(1.0)
+            .weight(Complex::new(0.0, 3.0))
+            .build()
+            .unwrap()) as Box<dyn apg::ElementIface>)
+        .collect();
+
+    let array = apg::ElementArrayBuilder::default()
+        .elements(elements.clone())
+        .with_taper(|idx, count| {
+            let center = (count as f64 - 1.0) / 2.0;
+            Complex::new(1.0 - (idx as f64 - center).abs() / (center + 1.0), 0.0)
+        })
+        .build();
+
+    let weights: Vec<Complex<f64>> = array.elements().iter().map(|n| n.weight()).collect();
+    let expected = [1.0 / 3.0, 2.0 / 3.0, 1.0, 2.0 / 3.0, 1.0 / 3.0];
+    for (weight, expected) in weights.iter().zip(expected) {
+        assert!((weight - Complex::new(expected, 0.0)).norm() < 1e-12, "{} vs {}", weight, expected);
+    }
+    for (n, original) in array.elements().iter().zip(&elements) {
+        assert_eq!(n.position(), original.position());
+    }
+
+    // without a taper the elements keep their weights
+    let plain = apg::ElementArrayBuilder::default().elements(elements).build();
+    assert!(plain.elements().iter().all(|n| n.weight() == Complex::new(0.0, 3.0)));
+}
@@ -0,0 +1,67 @@
+use antenna_pattern_generator_lib as apg;
+
+use apg::GainIface;
+
+fn planar_array(frequency: f64) -> apg::ElementArray {
+    let spacing = apg::SPEED_OF_LIGHT / frequency / 2.0;
+    let mut elements: Vec<Box<dyn apg::ElementIface>> = Vec::new();
+    for row in 0..4 {
+        for col in 0..4 {
+            elements.push(Box::new(apg::OmniElementBuilder::default()
+                .position(apg::PointBuilder::default().x(col as f64 * spacing).y(row as f64 * spacing).build().unwrap())
+                .gain(1.0)
+                .build()
+                .unwrap()));
+        }
+    }
+    apg::ElementArray::new( elements )
+}
+
+#[test]
+fn broadside_peak() {
+    let frequency = 1e9;
+    let array = planar_array(frequency);
+    let step = 1.0 * apg::PI / 180.0;
+
+    for grid in [array.sample_azel(frequency, step, step), array.sample_elaz(frequency, step, step)] {
+        // omni elements radiate just as well out the back, so only search the front
+        let mut peak = (0, 0, 0.0);
+        for el_idx in 0..grid.num_el() {
+            for az_idx in 0..grid.num_az() {
+                if grid.az(az_idx).abs() > apg::PI / 2.0 {
+                    continue;
+                }
+                let gain = grid.get(el_idx, az_idx).norm();
+                if gain > peak.2 + 1e-9 {
+                    peak = (el_idx, az_idx, gain);
+                }
+            }
+        }
+        assert!(grid.az(peak.1).abs() < 1e-9);
+        assert!(grid.el(peak.0).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn matches_get_gain() {
+    let frequency = 1e9;
+    let array = planar_array(frequency);
+    let step = 5.0 * apg::PI / 180.0;
+    let deg = apg::PI / 180.0;
+
+    // pure azimuth and pure elevation offsets are easy to work out by hand
+    let conv = apg::AzElConvention::AzOverEl;
+    let (theta, phi) = conv.to_theta_phi(30.0 * deg, 0.0);
+    assert!((theta - 30.0 * deg).abs() < 1e-12 && phi.abs() < 1e-12);
+    let (theta, phi) = conv.to_theta_phi(0.0, 30.0 * deg);
+    assert!((theta - 30.0 * deg).abs() < 1e-12 && (phi - 90.0 * deg).abs() < 1e-12);
+
+    let grid = array.sample_azel(frequency, step, step);
+    for el_idx in 0..grid.num_el() {
+        for az_idx in 0..grid.num_az() {
+            let (theta, phi) = conv.to_theta_phi(grid.az(az_idx), grid.el(el_idx));
+            let expected = array.get_gain(frequency, theta, phi).unwrap();
+            assert!((grid.get(el_idx, az_idx) - expected).norm() < 1e-9);
+        }
+    }
+}
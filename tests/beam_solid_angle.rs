@@ -0,0 +1,38 @@
+use antenna_pattern_generator_lib as apg;
+
+use apg::GainIface;
+
+mod support;
+use support::{omni, point};
+
+#[test]
+fn omni_solid_angle() {
+    let frequency = 1e9;
+    let wavelength = apg::SPEED_OF_LIGHT / frequency;
+    let step = 1.0 * apg::PI / 180.0;
+
+    let grid = apg::ElementArray::new( vec![omni(point(0.0, 0.0))] ).sample_sphere(frequency, step, step);
+    assert!((grid.beam_solid_angle() - 4.0 * apg::PI).abs() < 1e-3);
+    assert!((grid.effective_aperture(frequency) - wavelength.powi(2) / (4.0 * apg::PI)).abs() < 1e-6);
+}
+
+#[test]
+fn directive_solid_angle() {
+    let frequency = 1e9;
+    let spacing = apg::SPEED_OF_LIGHT / frequency / 2.0;
+    let step = 1.0 * apg::PI / 180.0;
+
+    let omni_grid = apg::ElementArray::new( vec![omni(point(0.0, 0.0))] ).sample_sphere(frequency, step, step);
+
+    let mut elements = Vec::new();
+    for row in 0..8 {
+        for col in 0..8 {
+            elements.push(omni(point(col as f64 * spacing, row as f64 * spacing)));
+        }
+    }
+    let grid = apg::ElementArray::new( elements ).sample_sphere(frequency, step, step);
+
+    // two beams (front and back) of roughly 4*PI/(N*PI) each
+    assert!(grid.beam_solid_angle() < 0.1 * omni_grid.beam_solid_angle());
+    assert!(grid.effective_aperture(frequency) > 10.0 * omni_grid.effective_aperture(frequency));
+}
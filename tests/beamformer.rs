@@ -0,0 +1,62 @@
+use antenna_pattern_generator_lib as apg;
+
+use apg::GainIface;
+use num::complex::Complex;
+
+mod support;
+use support::linear_array;
+
+#[test]
+fn swap_excitations() {
+    let frequency = 1e9;
+    let spacing = apg::SPEED_OF_LIGHT / frequency / 2.0;
+    let mut array = linear_array(8, spacing);
+
+    let broadside = apg::Beamformer::from_array(&array);
+    let theta0 = 30_f64.to_radians();
+    let mut steered_array = linear_array(8, spacing);
+    steered_array.steer(frequency, theta0, 0.0);
+    let steered = apg::Beamformer::from_array(&steered_array);
+    assert_ne!(broadside, steered);
+
+    // the same geometry, excited one way and then the other
+    steered.apply(&mut array);
+    assert!((array.get_gain(frequency, theta0, 0.0).unwrap().norm() - 8.0).abs() < 1e-9);
+    assert!(array.get_gain(frequency, 0.0, 0.0).unwrap().norm() < 1.0);
+
+    broadside.apply(&mut array);
+    assert!((array.get_gain(frequency, 0.0, 0.0).unwrap().norm() - 8.0).abs() < 1e-9);
+    assert!(array.get_gain(frequency, theta0, 0.0).unwrap().norm() < 1.0);
+}
+
+#[test]
+fn text_round_trip() {
+    let beamformer = apg::Beamformer::new(vec![Complex::new(1.0, 0.0), Complex::new(-0.3, 0.1), Complex::new(1.0 / 3.0, -2e-7)]);
+    let parsed: apg::Beamformer = beamformer.to_string().parse().unwrap();
+    assert_eq!(parsed, beamformer);
+
+    let parsed: apg::Beamformer = "# comment\n\n1 2\n3 4\n".parse().unwrap();
+    assert_eq!(parsed.weights(), &[Complex::new(1.0, 2.0), Complex::new(3.0, 4.0)]);
+
+    match "1 2\n3\n".parse::<apg::Beamformer>() {
+        Err(apg::DataFileError::Parse { line, .. }) => assert_eq!(line, 2),
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[cfg(feature = "io")]
+#[test]
+fn save_and_load() {
+    let beamformer = apg::Beamformer::new(vec![Complex::new(0.5, -0.5), Complex::new(0.25, 1.0)]);
+    let path = std::env::temp_dir().join("apg_beamformer_test.txt");
+    beamformer.save(&path).unwrap();
+    assert_eq!(apg::Beamformer::load(&path).unwrap(), beamformer);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+#[should_panic]
+fn wrong_size() {
+    let mut array = linear_array(4, 0.1);
+    apg::Beamformer::new(vec![Complex::new(1.0, 0.0); 3]).apply(&mut array);
+}
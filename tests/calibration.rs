@@ -0,0 +1,128 @@
+use antenna_pattern_generator_lib as apg;
+
+use apg::{ArrayIface, GainIface};
+use num::complex::Complex;
+
+fn steered_array(frequency: f64) -> apg::ElementArray {
+    let spacing = apg::SPEED_OF_LIGHT / frequency / 2.0;
+    let mut array = apg::ElementArray::uniform_linear(8, spacing, |position| {
+        Box::new(apg::OmniElementBuilder::default().position(position).gain(1.0).build().unwrap())
+    });
+    array.steer(frequency, 0.3, 0.0);
+    array
+}
+
+/// What the hardware radiates when `commanded` is sent through channels with `errors`
+fn hardware(commanded: &apg::ElementArray, errors: &[Complex<f64>]) -> apg::ElementArray {
+    let mut actual = commanded.clone();
+    for (n, error) in actual.elements_mut().iter_mut().zip(errors) {
+        let weight = n.weight() * error;
+        n.set_weight(weight);
+    }
+    actual
+}
+
+#[test]
+fn removes_channel_errors() {
+    let frequency = 1e9;
+    let ideal = steered_array(frequency);
+    let errors: Vec<Complex<f64>> = (0..8)
+        .map(|n| Complex::from_polar(1.0 + 0.1 * (n as f64 * 1.7).sin(), 0.5 * (n as f64 * 2.3).cos()))
+        .collect();
+
+    // uncalibrated, the beam is off
+    let before = hardware(&ideal, &errors);
+    let check = [(0.3, 0.0), (0.0, 0.0), (0.8, 0.0), (0.5, 2.0)];
+    assert!(check.iter().any(|(theta, phi)| {
+        (before.get_gain(frequency, *theta, *phi).unwrap() - ideal.get_gain(frequency, *theta, *phi).unwrap()).norm() > 0.1
+    }));
+
+    // measure every element towards a source at boresight and calibrate
+    let (theta_cal, phi_cal) = (0.0, 0.0);
+    let measured = before.element_contributions(frequency, theta_cal, phi_cal);
+    let mut commanded = ideal.clone();
+    commanded.calibrate(frequency, &measured, theta_cal, phi_cal);
+
+    let after = hardware(&commanded, &errors);
+    for (theta, phi) in check {
+        let expected = ideal.get_gain(frequency, theta, phi).unwrap();
+        assert!((after.get_gain(frequency, theta, phi).unwrap() - expected).norm() < 1e-9);
+    }
+}
+
+#[test]
+fn calibration_vector() {
+    let frequency = 1e9;
+    let ideal = steered_array(frequency);
+    let check = [(0.3, 0.0), (0.0, 0.0), (0.8, 0.0), (0.5, 2.0)];
+
+    // all ones changes nothing
+    let mut ones = ideal.clone();
+    ones.set_calibration(&[Complex::new(1.0, 0.0); 8]);
+    for (theta, phi) in check {
+        assert_eq!(ones.get_gain(frequency, theta, phi), ideal.get_gain(frequency, theta, phi));
+    }
+
+    // channel errors spoil the beam
+    let errors: Vec<Complex<f64>> = (0..8)
+        .map(|n| Complex::from_polar(1.0 + 0.1 * (n as f64 * 1.7).sin(), 0.5 * (n as f64 * 2.3).cos()))
+        .collect();
+    let mut actual = hardware(&ideal, &errors);
+    assert!(check.iter().any(|(theta, phi)| {
+        (actual.get_gain(frequency, *theta, *phi).unwrap() - ideal.get_gain(frequency, *theta, *phi).unwrap()).norm() > 0.1
+    }));
+
+    // the inverse correction restores it without touching the weights
+    let weights: Vec<Complex<f64>> = actual.elements().iter().map(|n| n.weight()).collect();
+    let correction: Vec<Complex<f64>> = errors.iter().map(|error| 1.0 / error).collect();
+    actual.set_calibration(&correction);
+    assert_eq!(actual.calibration(), Some(&correction[..]));
+    assert!(actual.elements().iter().zip(&weights).all(|(n, weight)| n.weight() == *weight));
+    for (theta, phi) in check {
+        let expected = ideal.get_gain(frequency, theta, phi).unwrap();
+        assert!((actual.get_gain(frequency, theta, phi).unwrap() - expected).norm() < 1e-9);
+    }
+
+    // and it carries through to the flattened elements
+    let field = actual.get_field(frequency, 0.3, 0.0).unwrap();
+    let expected = ideal.get_field(frequency, 0.3, 0.0).unwrap();
+    assert!((field.e_theta - expected.e_theta).norm() < 1e-9);
+
+    actual.clear_calibration();
+    assert!(actual.calibration().is_none());
+}
+
+#[test]
+fn calibration_in_every_path() {
+    // a correction on the ideal weights has to look just like hardware with those channel errors
+    let frequency = 1e9;
+    let ideal = steered_array(frequency);
+    let errors: Vec<Complex<f64>> = (0..8)
+        .map(|n| Complex::from_polar(1.0 + 0.1 * (n as f64 * 1.7).sin(), 0.5 * (n as f64 * 2.3).cos()))
+        .collect();
+    let actual = hardware(&ideal, &errors);
+    let mut calibrated = ideal.clone();
+    calibrated.set_calibration(&errors);
+
+    let track = [(0.3, 0.0), (0.0, 0.0), (0.8, 0.0), (0.5, 2.0)];
+    let along = calibrated.gain_along_track(frequency, &track);
+    assert!(along.iter().zip(ideal.gain_along_track(frequency, &track)).any(|(a, b)| (a - b).abs() > 0.1));
+    for (a, b) in along.iter().zip(actual.gain_along_track(frequency, &track)) {
+        assert!((a - b).abs() < 1e-9);
+    }
+
+    let point = apg::PointBuilder::default().x(0.2).y(-0.1).z(1.5).build().unwrap();
+    let near = calibrated.get_gain_at_point(frequency, &point).unwrap();
+    assert!((near - ideal.get_gain_at_point(frequency, &point).unwrap()).norm() > 1e-3);
+    assert!((near - actual.get_gain_at_point(frequency, &point).unwrap()).norm() < 1e-12);
+
+    let step = 10.0 * apg::PI / 180.0;
+    let difference = calibrated.monopulse_difference(frequency, apg::Axis::X, step, step);
+    let uncorrected = ideal.monopulse_difference(frequency, apg::Axis::X, step, step);
+    let expected = actual.monopulse_difference(frequency, apg::Axis::X, step, step);
+    let changed = difference.values().iter().flatten().zip(uncorrected.values().iter().flatten()).any(|(a, b)| (a - b).norm() > 0.1);
+    assert!(changed);
+    for (a, b) in difference.values().iter().flatten().zip(expected.values().iter().flatten()) {
+        assert!((a - b).norm() < 1e-9);
+    }
+}
@@ -0,0 +1,62 @@
+use antenna_pattern_generator_lib as apg;
+
+use apg::{ArrayIface, GainIface};
+use num::complex::Complex;
+
+mod support;
+use support::{omni, point};
+
+#[test]
+fn composite_of_single_elements() {
+    let frequency = 1e9;
+    let spacing = 0.2;
+
+    let flat = apg::ElementArray::new(vec![omni(point(0.0, 0.0)), omni(point(spacing, 0.0))]);
+    let composite = apg::CompositeArray::new(vec![
+        Box::new(apg::ElementArray::new(vec![omni(point(0.0, 0.0))])),
+        Box::new(apg::ElementArray::new(vec![omni(point(spacing, 0.0))])),
+    ]);
+    assert_eq!(composite.num_elements(), 2);
+
+    for (theta, phi) in [(0.0, 0.0), (0.4, 0.0), (1.3, 0.5), (2.8, 3.0)] {
+        let expected = flat.get_gain(frequency, theta, phi).unwrap();
+        assert!((composite.get_gain(frequency, theta, phi).unwrap() - expected).norm() < 1e-12);
+    }
+
+    // sub-array weights act like element weights
+    let mut composite = composite;
+    let mut flat = flat;
+    let weight = Complex::from_polar(0.5, 1.0);
+    composite.set_weight(1, weight);
+    flat.elements_mut()[1].set_weight(weight);
+    let nested = apg::CompositeArray::new(vec![Box::new(composite)]);
+    assert_eq!(nested.num_elements(), 2);
+    for (theta, phi) in [(0.0, 0.0), (0.4, 0.0), (1.3, 0.5)] {
+        let expected = flat.get_gain(frequency, theta, phi).unwrap();
+        assert!((nested.get_gain(frequency, theta, phi).unwrap() - expected).norm() < 1e-12);
+    }
+}
+
+#[test]
+fn flatten_two_levels() {
+    let frequency = 1e9;
+    let mut left = apg::ElementArray::new(vec![omni(point(0.0, 0.0)), omni(point(0.1, 0.0))]);
+    left.elements_mut()[1].set_weight(Complex::from_polar(0.8, 0.4));
+    let right = apg::ElementArray::new(vec![omni(point(0.2, 0.0)), omni(point(0.3, 0.0))]);
+
+    let mut inner = apg::CompositeArray::new(vec![Box::new(left), Box::new(right)]);
+    inner.set_weight(1, Complex::from_polar(0.5, -1.0));
+    let mut outer = apg::CompositeArray::new(vec![
+        Box::new(inner),
+        Box::new(apg::ElementArray::new(vec![omni(point(0.5, 0.0))])),
+    ]);
+    outer.set_weight(0, Complex::from_polar(2.0, 0.3));
+
+    let flat = outer.flatten();
+    assert_eq!(flat.elements().len(), 5);
+    assert!((flat.elements()[3].weight() - Complex::from_polar(1.0, -0.7)).norm() < 1e-12);
+    for (theta, phi) in [(0.0, 0.0), (0.4, 0.0), (1.3, 0.5), (2.8, 3.0)] {
+        let expected = outer.get_gain(frequency, theta, phi).unwrap();
+        assert!((flat.get_gain(frequency, theta, phi).unwrap() - expected).norm() < 1e-12);
+    }
+}
@@ -0,0 +1,34 @@
+use antenna_pattern_generator_lib as apg;
+
+use num::complex::Complex;
+
+mod support;
+use support::separation;
+
+#[test]
+fn pencil_beam_footprint() {
+    let deg = apg::PI / 180.0;
+    let center = (30.0 * deg, 45.0 * deg);
+    // symmetric gaussian pencil beam, 3 dB down at 10 deg from its center
+    let half_power = 10.0 * deg;
+    let grid = apg::PatternGrid::from_fn(1.0 * deg, 1.0 * deg, |theta, phi| {
+        let offset = separation((theta, phi), center) / half_power;
+        Complex::new((-0.5 * 2_f64.ln() * offset * offset).exp(), 0.0)
+    });
+
+    let level = -10.0 * 2_f64.log10();
+    let footprint = grid.contour(level);
+    assert!(footprint.len() > 40);
+    for point in &footprint {
+        assert!((separation(*point, center) - half_power).abs() < 0.1 * deg, "{:?}", point);
+    }
+
+    // the outline goes all the way round the beam
+    for quadrant in 0..4 {
+        let covered = footprint.iter().any(|(theta, phi)| {
+            let (dx, dy) = (theta * phi.cos() - center.0 * center.1.cos(), theta * phi.sin() - center.0 * center.1.sin());
+            (dy.atan2(dx).rem_euclid(2.0 * apg::PI) / (0.5 * apg::PI)) as usize == quadrant
+        });
+        assert!(covered);
+    }
+}
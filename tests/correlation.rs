@@ -0,0 +1,31 @@
+#![cfg(feature = "ndarray")]
+
+use antenna_pattern_generator_lib as apg;
+
+mod support;
+use support::omni;
+
+#[test]
+fn correlation_matrix() {
+    let frequency = 1e9;
+    let wavelength = apg::SPEED_OF_LIGHT / frequency;
+    let step = 1.0 * apg::PI / 180.0;
+
+    let array = apg::ElementArray::uniform_linear_wl(4, 0.3, frequency, omni);
+    let matrix = array.correlation_matrix(frequency, step, step);
+    assert_eq!(matrix.dim(), (4, 4));
+
+    for m in 0..4 {
+        // an isotropic element radiates 4*PI
+        assert!((matrix[[m, m]].re - 4.0 * apg::PI).abs() < 1e-3);
+        assert!(matrix[[m, m]].im.abs() < 1e-9);
+        for n in 0..4 {
+            assert!((matrix[[m, n]] - matrix[[n, m]].conj()).norm() < 1e-9);
+        }
+    }
+
+    // two isotropic elements overlap by 4*PI * sinc(k*d)
+    let k = 2.0 * apg::PI / wavelength;
+    let kd = k * 0.3 * wavelength;
+    assert!((matrix[[0, 1]].re - 4.0 * apg::PI * kd.sin() / kd).abs() < 1e-3);
+}
@@ -0,0 +1,39 @@
+use antenna_pattern_generator_lib as apg;
+
+use apg::ElementIface;
+
+#[test]
+fn read_cst_export() {
+    // x-polarized source with a cos(theta) pattern, 15 deg in theta and 90 deg in phi
+    let position = apg::PointBuilder::default().y(0.25).build().unwrap();
+    let element = apg::DataElement::from_cst("tests/data/x_pol_cst.txt", position.clone()).unwrap();
+    let deg = apg::PI / 180.0;
+
+    assert_eq!(element.position(), &position);
+    assert!((element.data().theta_step() - 15.0 * deg).abs() < 1e-9);
+    assert!((element.data().phi_step() - 90.0 * deg).abs() < 1e-9);
+
+    for phi in [0.0, 90.0, 180.0, 270.0] {
+        for theta in [0.0, 30.0, 60.0, 120.0, 180.0] {
+            let (theta, phi): (f64, f64) = (theta * deg, phi * deg);
+            let co = element.pattern(1e9, theta, phi).unwrap();
+            let cross = element.cross().unwrap().interpolate(theta, phi);
+            assert!((co.re - theta.cos()).abs() < 1e-5, "co at ({}, {}) = {}", theta, phi, co);
+            assert!(co.im.abs() < 1e-5);
+            assert!(cross.norm() < 1e-5);
+        }
+    }
+}
+
+#[test]
+fn rejects_other_files() {
+    let err = apg::DataElement::from_cst("tests/data/x_pol.cut", apg::Point::default());
+    assert!(matches!(err, Err(apg::DataFileError::Parse { line: 1, .. })));
+}
+
+#[test]
+fn rejects_non_finite_angles() {
+    let contents = std::fs::read_to_string("tests/data/x_pol_cst.txt").unwrap().replacen("          15.000", "             nan", 1);
+    let err = apg::DataElement::from_cst_str(&contents, apg::Point::default());
+    assert!(matches!(err, Err(apg::DataFileError::Parse { line: 4, .. })));
+}
@@ -0,0 +1,58 @@
+use antenna_pattern_generator_lib as apg;
+
+use apg::ElementIface;
+use num::complex::Complex;
+
+#[test]
+fn synthesized_phase_center() {
+    let frequency = 1e9;
+    let wavelength = apg::SPEED_OF_LIGHT / frequency;
+    let k = 2.0 * apg::PI / wavelength;
+    let offset = 0.1 * wavelength;
+    let step = 1.0 * apg::PI / 180.0;
+
+    let flat = apg::PatternGrid::from_fn(step, step, |_, _| Complex::new(1.0, 0.0));
+    let center = apg::PointBuilder::default().x(offset).build().unwrap();
+    let element = apg::DataElement::with_synthesized_phase(&flat, apg::PhaseModel::PhaseCenter { center, frequency });
+
+    // a phase center offset along x gives a phase of k*dx*sin(theta) in the phi = 0 plane
+    for degrees in [0.0, 5.5, 10.0, 30.0, 60.25, 90.0] {
+        let theta: f64 = degrees * apg::PI / 180.0;
+        let gain = element.pattern(frequency, theta, 0.0).unwrap();
+        assert!((gain.norm() - 1.0).abs() < 1e-3);
+        assert!((gain.arg() - k * offset * theta.sin()).abs() < 1e-3);
+    }
+
+    let constant = apg::DataElement::with_synthesized_phase(&flat, apg::PhaseModel::Constant);
+    assert!(constant.pattern(frequency, 0.3, 1.2).unwrap().arg().abs() < 1e-12);
+}
+
+#[test]
+fn symmetry() {
+    let step = 5.0 * apg::PI / 180.0;
+
+    // z-directed dipole: a body of revolution, symmetric front to back
+    let dipole = apg::PatternGrid::from_fn(step, step, |theta, _| Complex::new(theta.sin(), 0.0));
+    let report = apg::DataElement::new(dipole, apg::Point::default()).check_symmetry(1e-9);
+    assert!(report.rotational);
+    assert_eq!(report.rotation_order, 72);
+    assert!(report.mirror_xz && report.mirror_yz && report.mirror_xy);
+
+    // forward looking beam that is wider in one plane than the other
+    let beam = apg::PatternGrid::from_fn(step, step, |theta, phi| {
+        Complex::new(theta.cos().max(0.0) * (1.0 + 0.5 * (2.0 * phi).cos()), 0.0)
+    });
+    let report = apg::DataElement::new(beam, apg::Point::default()).check_symmetry(1e-9);
+    assert!(!report.rotational);
+    assert_eq!(report.rotation_order, 2);
+    assert!(report.mirror_xz && report.mirror_yz);
+    assert!(!report.mirror_xy);
+
+    // a beam squinted towards +x loses the YZ mirror and the rotations
+    let squint = apg::PatternGrid::from_fn(step, step, |theta, phi| {
+        Complex::new((1.0 + theta.sin() * phi.cos()) * theta.cos().max(0.0), 0.0)
+    });
+    let report = apg::DataElement::new(squint, apg::Point::default()).check_symmetry(0.01);
+    assert_eq!(report.rotation_order, 1);
+    assert!(report.mirror_xz && !report.mirror_yz);
+}
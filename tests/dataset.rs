@@ -0,0 +1,48 @@
+#![cfg(feature = "json")]
+
+use antenna_pattern_generator_lib as apg;
+
+use apg::GainIface;
+
+#[test]
+fn json_round_trip() {
+    let frequency = 1e9;
+    let spacing = apg::SPEED_OF_LIGHT / frequency / 2.0;
+    let mut array = apg::ElementArray::uniform_linear(4, spacing, |position| {
+        Box::new(apg::OmniElementBuilder::default().position(position).gain(1.0).build().unwrap())
+    });
+    array.steer(frequency, 0.3, 0.0);
+    let step = 10.0 * apg::PI / 180.0;
+    let grid = array.sample_sphere(frequency, step, step);
+
+    let mut dataset = apg::PatternDataset::new(&grid, frequency);
+    dataset.metadata_mut().insert("source".to_string(), "4 element ULA".to_string());
+    dataset.metadata_mut().insert("scan_deg".to_string(), "17.2".to_string());
+
+    std::fs::create_dir_all("tests/output").unwrap();
+    let path = std::path::Path::new("tests/output/dataset.json");
+    dataset.save_json(path).unwrap();
+    let read = apg::PatternDataset::load_json(path).unwrap();
+
+    assert_eq!(read, dataset);
+    assert_eq!(read.frequency(), frequency);
+    assert_eq!(read.metadata()["source"], "4 element ULA");
+
+    let read_grid = read.grid();
+    assert_eq!(read_grid.num_theta(), grid.num_theta());
+    assert_eq!(read_grid.num_phi(), grid.num_phi());
+    assert!((read_grid.theta_step() - grid.theta_step()).abs() < 1e-12);
+    assert!((read_grid.phi_step() - grid.phi_step()).abs() < 1e-12);
+    assert_eq!(read_grid.values(), grid.values());
+}
+
+#[test]
+fn mismatched_axes() {
+    std::fs::create_dir_all("tests/output").unwrap();
+    let path = std::path::Path::new("tests/output/dataset_bad.json");
+    std::fs::write(path, r#"{"frequency":1e9,"theta":[0.0,1.0],"phi":[0.0],"values":[[[1.0,0.0]]],"metadata":{}}"#).unwrap();
+    assert!(matches!(apg::PatternDataset::load_json(path), Err(apg::DataFileError::IncompleteGrid)));
+
+    std::fs::write(path, "{ not json").unwrap();
+    assert!(matches!(apg::PatternDataset::load_json(path), Err(apg::DataFileError::Parse { .. })));
+}
@@ -0,0 +1,30 @@
+use antenna_pattern_generator_lib as apg;
+
+use apg::ArrayIface;
+
+#[test]
+fn harrington_limit() {
+    let frequency = 1e9;
+    let wavelength = apg::SPEED_OF_LIGHT / frequency;
+
+    let small = apg::max_directivity_bound(0.5 * wavelength, frequency);
+    let large = apg::max_directivity_bound(2.0 * wavelength, frequency);
+    assert!(large > small);
+    // ka = PI
+    assert!((small - (apg::PI * apg::PI + 2.0 * apg::PI)).abs() < 1e-9);
+
+    // 8 elements half a wavelength apart, centered on the origin
+    let spacing = wavelength / 2.0;
+    let array = apg::ElementArray::new( (0..8)
+        .map(|n| Box::new(apg::OmniElementBuilder::default()
+            .position(apg::PointBuilder::default().x((n as f64 - 3.5) * spacing).build().unwrap())
+            .gain(1.0)
+            .build()
+            .unwrap()) as Box<dyn apg::ElementIface>)
+        .collect() );
+    let radius = 3.5 * spacing;
+    let step = 1.0 * apg::PI / 180.0;
+    let directivity = 10_f64.powf(array.peak_to_average(frequency, step, step) / 10.0);
+    assert!(directivity > 7.0);
+    assert!(directivity < apg::max_directivity_bound(radius, frequency));
+}
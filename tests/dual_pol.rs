@@ -0,0 +1,83 @@
+use antenna_pattern_generator_lib as apg;
+
+use apg::ElementIface;
+use num::complex::Complex;
+
+/// Ludwig-3 tables for a cos(theta) port that is co-polar (x) or cross-polar (y)
+fn port(x_polarized: bool) -> (apg::PatternGrid, apg::PatternGrid) {
+    let step = 5.0 * apg::PI / 180.0;
+    let pattern = |theta: f64, _| Complex::new(theta.cos().max(0.0), 0.0);
+    let zero = |_, _| Complex::new(0.0, 0.0);
+    if x_polarized {
+        (apg::PatternGrid::from_fn(step, step, pattern), apg::PatternGrid::from_fn(step, step, zero))
+    } else {
+        (apg::PatternGrid::from_fn(step, step, zero), apg::PatternGrid::from_fn(step, step, pattern))
+    }
+}
+
+#[test]
+fn quadrature_ports_are_circular() {
+    let frequency = 10e9;
+    let mut element = apg::DualPolElement::new(port(true), port(false), apg::Point::default());
+    let root_half = 0.5_f64.sqrt();
+    element.set_port_weights([Complex::new(root_half, 0.0), Complex::new(0.0, root_half)]);
+
+    for (theta, phi) in [(0.0, 0.0), (0.3, 0.5), (0.7, 2.0), (1.0, 4.0)] {
+        let field = element.get_field(frequency, theta, phi).unwrap();
+        let (rhc, lhc) = field.circular();
+        assert!(lhc.norm() < 1e-12);
+        assert!((rhc.norm() - theta.cos()).abs() < 0.01);
+    }
+
+    // and the other sense with the phase flipped
+    element.set_port_weights([Complex::new(root_half, 0.0), Complex::new(0.0, -root_half)]);
+    let (rhc, lhc) = element.get_field(frequency, 0.3, 0.5).unwrap().circular();
+    assert!(rhc.norm() < 1e-12 && lhc.norm() > 0.9);
+}
+
+#[test]
+fn single_port_is_linear() {
+    let frequency = 10e9;
+    let mut element = apg::DualPolElement::new(port(true), port(false), apg::Point::default());
+
+    element.set_port_weights([Complex::new(1.0, 0.0), Complex::new(0.0, 0.0)]);
+    for (theta, phi) in [(0.0, 0.0), (0.4, 1.0), (0.9, 3.0)] {
+        let (co, cross) = element.get_field(frequency, theta, phi).unwrap().ludwig3(phi);
+        assert!(co.norm() > 0.5 && cross.norm() < 1e-12);
+        assert_eq!(element.pattern(frequency, theta, phi).unwrap(), co);
+    }
+
+    element.set_port_weights([Complex::new(0.0, 0.0), Complex::new(1.0, 0.0)]);
+    for (theta, phi) in [(0.0, 0.0), (0.4, 1.0), (0.9, 3.0)] {
+        let (co, cross) = element.get_field(frequency, theta, phi).unwrap().ludwig3(phi);
+        assert!(co.norm() < 1e-12 && cross.norm() > 0.5);
+    }
+}
+
+#[test]
+fn port_isolation() {
+    let frequency = 10e9;
+    let orthogonal = apg::DualPolElement::new(port(true), port(false), apg::Point::default());
+    for (theta, phi) in [(0.0, 0.0), (0.4, 1.0), (0.9, 3.0)] {
+        assert!(orthogonal.isolation(frequency, theta, phi) > 100.0);
+    }
+
+    // identical ports aren't isolated at all
+    let same = apg::DualPolElement::new(port(true), port(true), apg::Point::default());
+    assert!(same.isolation(frequency, 0.4, 1.0).abs() < 1e-9);
+
+    // ports 45 deg apart share half their power
+    let step = 5.0 * apg::PI / 180.0;
+    let slant = |theta: f64, _| Complex::new(theta.cos().max(0.0) * 0.5_f64.sqrt(), 0.0);
+    let slanted = (apg::PatternGrid::from_fn(step, step, slant), apg::PatternGrid::from_fn(step, step, slant));
+    let tilted = apg::DualPolElement::new(port(true), slanted, apg::Point::default());
+    assert!((tilted.isolation(frequency, 0.4, 1.0) - 10.0 * 2_f64.log10()).abs() < 1e-9);
+}
+
+#[test]
+fn isolation_without_pattern() {
+    // both ports are zero behind the element, where nothing can couple
+    let frequency = 10e9;
+    let element = apg::DualPolElement::new(port(true), port(true), apg::Point::default());
+    assert_eq!(element.isolation(frequency, 2.5, 1.0), f64::INFINITY);
+}
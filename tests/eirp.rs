@@ -0,0 +1,24 @@
+use antenna_pattern_generator_lib as apg;
+
+use apg::GainIface;
+
+mod support;
+use support::omni;
+
+#[test]
+fn eirp_map() {
+    let frequency = 1e9;
+    let step = 2.0 * apg::PI / 180.0;
+    let mut array = apg::ElementArray::uniform_linear_wl(6, 0.5, frequency, omni);
+    let peak_gain_dbi = array.sample_sphere(frequency, step, step).peak_gain_dbi();
+
+    let map = array.eirp_map(30.0, frequency, step, step);
+    let peak = map.values().iter().flatten().cloned().fold(f64::MIN, f64::max);
+    assert!((peak - (30.0 + peak_gain_dbi)).abs() < 1e-9);
+    assert!((map.floor_db() - (peak - 100.0)).abs() < 1e-9);
+
+    array.set_feed_loss_db(2.0);
+    let map = array.eirp_map(30.0, frequency, step, step);
+    let lossy_peak = map.values().iter().flatten().cloned().fold(f64::MIN, f64::max);
+    assert!((lossy_peak - (peak - 2.0)).abs() < 1e-9);
+}
@@ -0,0 +1,28 @@
+use antenna_pattern_generator_lib as apg;
+
+use apg::GainIface;
+use num::complex::Complex;
+
+mod support;
+use support::omni;
+
+#[test]
+fn contributions_sum_to_gain() {
+    let frequency = 1e9;
+    let mut array = apg::ElementArray::uniform_linear_wl(8, 0.5, frequency, omni);
+    array.steer(frequency, 0.3, 0.0);
+    // one hot element
+    array.elements_mut()[5].set_weight(Complex::new(4.0, 0.0));
+
+    for (theta, phi) in [(0.0, 0.0), (0.3, 0.0), (1.2, 0.0), (0.7, 2.0)] {
+        let contributions = array.element_contributions(frequency, theta, phi);
+        assert_eq!(contributions.len(), 8);
+        let total: Complex<f64> = contributions.iter().sum();
+        assert_eq!(total, array.get_gain(frequency, theta, phi).unwrap());
+
+        let loudest = (0..8)
+            .max_by(|a, b| contributions[*a].norm().partial_cmp(&contributions[*b].norm()).unwrap())
+            .unwrap();
+        assert_eq!(loudest, 5);
+    }
+}
@@ -0,0 +1,54 @@
+use antenna_pattern_generator_lib as apg;
+
+use apg::{ElementIface, GainIface};
+use num::complex::Complex;
+
+#[test]
+fn delay_phase_slope() {
+    let delay = 0.2e-9;
+    let mut element = apg::OmniElementBuilder::default()
+        .position(apg::Point::default())
+        .gain(1.0)
+        .delay(delay)
+        .build()
+        .unwrap();
+    assert_eq!(element.delay(), delay);
+    element.set_weight(Complex::from_polar(1.0, 0.3));
+
+    // the phase falls linearly with frequency, on top of the weight
+    let mut previous: Option<f64> = None;
+    for idx in 0..20 {
+        let frequency = 1e9 + idx as f64 * 50e6;
+        let gain = element.get_gain(frequency, 0.4, 1.0).unwrap();
+        assert!((gain.norm() - 1.0).abs() < 1e-12);
+        let phase = (gain / Complex::from_polar(1.0, 0.3)).arg();
+        if let Some(previous) = previous {
+            let step = (phase - previous + apg::PI).rem_euclid(2.0 * apg::PI) - apg::PI;
+            assert!((step - -2.0 * apg::PI * 50e6 * delay).abs() < 1e-9);
+        }
+        previous = Some(phase);
+    }
+
+    // a delay of a whole period is invisible
+    element.set_delay(1e-9);
+    let gain = element.get_gain(1e9, 0.4, 1.0).unwrap();
+    assert!((gain - Complex::from_polar(1.0, 0.3)).norm() < 1e-9);
+}
+
+#[test]
+fn delay_in_array() {
+    let frequency = 1.5e9;
+    let mut array = apg::ElementArray::uniform_linear_wl(4, 0.5, frequency, |position| {
+        Box::new(apg::OmniElementBuilder::default().position(position).gain(1.0).build().unwrap())
+    });
+    for (idx, n) in array.elements_mut().iter_mut().enumerate() {
+        n.set_delay(idx as f64 * 0.1e-9);
+    }
+
+    let contributions = array.element_contributions(frequency, 0.7, 0.2);
+    for (n, contribution) in array.elements().iter().zip(&contributions) {
+        assert!((n.get_gain(frequency, 0.7, 0.2).unwrap() - contribution).norm() < 1e-12);
+    }
+    let total: Complex<f64> = contributions.iter().sum();
+    assert!((array.get_gain(frequency, 0.7, 0.2).unwrap() - total).norm() < 1e-12);
+}
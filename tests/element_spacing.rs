@@ -20,7 +20,7 @@ fn element_spacing() {
             .build()
             .unwrap());
 
-    let array = Box::new(apg::ElementArray( vec![e0, e1] ) );
+    let array = Box::new(apg::ElementArray::new( vec![e0, e1] ) );
 
     write_to_file(
         array,
@@ -0,0 +1,40 @@
+use antenna_pattern_generator_lib as apg;
+
+use apg::ElementIface;
+use num::complex::Complex;
+
+mod support;
+use support::peak_sidelobe_db;
+
+fn embedded(scale: f64) -> apg::DataElement {
+    let step = 1.0 * apg::PI / 180.0;
+    let pattern = apg::PatternGrid::from_fn(step, step, |theta, _| Complex::new(scale * theta.cos().max(0.0), 0.0));
+    apg::DataElement::new(pattern, apg::Point::default())
+}
+
+#[test]
+fn edge_elements() {
+    let frequency = 1e9;
+    let spacing = apg::SPEED_OF_LIGHT / frequency / 2.0;
+    let identical = apg::ElementArray::uniform_linear(16, spacing, |position| {
+        let mut element = embedded(1.0);
+        element.set_position(position);
+        Box::new(element)
+    });
+
+    // the outer two elements at each end couple less and radiate less
+    let mut measured = identical.clone();
+    measured.elements_mut()[3].set_weight(Complex::new(0.0, 1.0));
+    for idx in [0, 1, 14, 15] {
+        measured.set_embedded_pattern(idx, embedded(if idx == 0 || idx == 15 { 0.4 } else { 0.7 }));
+    }
+    assert_eq!(measured.elements()[1].position(), identical.elements()[1].position());
+    assert_eq!(measured.elements()[3].weight(), Complex::new(0.0, 1.0));
+    measured.elements_mut()[3].set_weight(Complex::new(1.0, 0.0));
+
+    // the weaker edges act as a taper and pull the sidelobes down
+    let uniform = peak_sidelobe_db(&identical, frequency, 0.0);
+    let tapered = peak_sidelobe_db(&measured, frequency, 0.0);
+    assert!((uniform + 13.3).abs() < 0.5, "{}", uniform);
+    assert!(tapered < uniform - 1.0, "{} vs {}", tapered, uniform);
+}
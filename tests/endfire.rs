@@ -0,0 +1,46 @@
+use antenna_pattern_generator_lib as apg;
+
+use num::complex::Complex;
+
+mod support;
+use support::omni;
+
+#[test]
+fn endfire_gain() {
+    let frequency = 1e9;
+    let wavelength = apg::SPEED_OF_LIGHT / frequency;
+    let k = 2.0 * apg::PI / wavelength;
+    let spacing = 0.25 * wavelength;
+
+    let mut array = apg::ElementArray::uniform_linear(8, spacing, omni);
+    assert_eq!(array.endfire_direction(), Some((apg::PI / 2.0, 0.0)));
+
+    // unphased, the array factor is sin(N*psi/2) / sin(psi/2) with psi = k*d
+    let psi = k * spacing;
+    let expected = (8.0 * psi / 2.0).sin() / (psi / 2.0).sin();
+    assert!((array.endfire_gain(frequency).unwrap().norm() - expected.abs()).abs() < 1e-9);
+
+    // with endfire phasing every element adds in phase
+    array.steer(frequency, apg::PI / 2.0, 0.0);
+    assert!((array.endfire_gain(frequency).unwrap().norm() - 8.0).abs() < 1e-12);
+}
+
+#[test]
+fn endfire_along_z() {
+    let frequency = 1e9;
+    let spacing = 0.25 * apg::SPEED_OF_LIGHT / frequency;
+    let mut elements = Vec::new();
+    for idx in (0..4).rev() {
+        elements.push(omni(apg::PointBuilder::default().z(idx as f64 * spacing).build().unwrap()));
+    }
+    let mut array = apg::ElementArray::new(elements);
+
+    // the last element is lowest, so the axis points down the z axis
+    assert_eq!(array.endfire_direction(), Some((apg::PI, 0.0)));
+    array.steer(frequency, apg::PI, 0.0);
+    assert!((array.endfire_gain(frequency).unwrap().norm() - 4.0).abs() < 1e-12);
+
+    let single = apg::ElementArray::new(vec![omni(apg::Point::default())]);
+    assert_eq!(single.endfire_direction(), None);
+    assert_eq!(single.endfire_gain(frequency), None::<Complex<f64>>);
+}
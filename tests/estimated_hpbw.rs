@@ -0,0 +1,61 @@
+use antenna_pattern_generator_lib as apg;
+
+use apg::GainIface;
+use num::complex::Complex;
+
+mod support;
+use support::linear_array;
+
+/// Full angle between the half-power points either side of broadside in the XZ plane
+fn measured_hpbw(array: &apg::ElementArray, frequency: f64) -> f64 {
+    let peak = array.get_gain(frequency, 0.0, 0.0).unwrap().norm_sqr();
+    let step = 0.01 * apg::PI / 180.0;
+    let half = (0..)
+        .map(|idx| idx as f64 * step)
+        .find(|theta| array.get_gain(frequency, *theta, 0.0).unwrap().norm_sqr() < peak / 2.0)
+        .unwrap();
+    2.0 * half
+}
+
+#[test]
+fn uniform_ula() {
+    let frequency = 1e9;
+    let spacing = apg::SPEED_OF_LIGHT / frequency / 2.0;
+    let array = linear_array(16, spacing);
+
+    let (xz, yz) = array.estimated_hpbw(frequency);
+    let measured = measured_hpbw(&array, frequency);
+    assert!((xz - measured).abs() < 0.1 * measured, "{} vs {}", xz, measured);
+    // no aperture across the line
+    assert!(yz.is_infinite());
+}
+
+#[test]
+fn taper_broadens() {
+    let frequency = 1e9;
+    let spacing = apg::SPEED_OF_LIGHT / frequency / 2.0;
+    let mut array = linear_array(16, spacing);
+    let uniform = array.estimated_hpbw(frequency).0;
+
+    for (n, weight) in array.elements_mut().iter_mut().zip(apg::taylor_linear(16, 5, -30.0)) {
+        n.set_weight(Complex::new(weight, 0.0));
+    }
+    let (tapered, _) = array.estimated_hpbw(frequency);
+    let measured = measured_hpbw(&array, frequency);
+    assert!(tapered > uniform);
+    assert!((tapered - measured).abs() < 0.1 * measured, "{} vs {}", tapered, measured);
+}
+
+#[test]
+fn scan_broadening() {
+    let frequency = 1e9;
+    let spacing = apg::SPEED_OF_LIGHT / frequency / 2.0;
+    let array = linear_array(32, spacing);
+
+    let theta0 = 60.0 * apg::PI / 180.0;
+    assert!((array.beam_broadening_factor(0.0) - 1.0).abs() < 1e-12);
+    assert!((array.beam_broadening_factor(theta0) - 2.0).abs() < 1e-12);
+
+    let measured = array.measured_broadening_factor(frequency, theta0, 0.0);
+    assert!((measured - 2.0).abs() < 0.1, "{}", measured);
+}
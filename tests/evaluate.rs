@@ -0,0 +1,25 @@
+use antenna_pattern_generator_lib as apg;
+
+use apg::{ArrayIface, GainIface};
+
+#[test]
+fn consistent_representations() {
+    let frequency = 1e9;
+    let spacing = apg::SPEED_OF_LIGHT / frequency / 2.0;
+    let array = apg::ElementArray::uniform_linear(4, spacing, |position| {
+        Box::new(apg::OmniElementBuilder::default().position(position).gain(1.0).build().unwrap())
+    });
+
+    for (theta, phi) in [(0.0, 0.0), (0.3, 1.0), (1.2, 4.0)] {
+        let result = array.evaluate(frequency, theta, phi).unwrap();
+        assert_eq!(result.field, array.get_gain(frequency, theta, phi).unwrap());
+        assert!((result.power - result.magnitude.powi(2)).abs() < 1e-12);
+        assert!((result.magnitude - result.field.norm()).abs() < 1e-12);
+        assert!((result.db - 20.0 * result.magnitude.log10()).abs() < 1e-9);
+    }
+
+    // broadside: four in phase
+    let broadside = array.evaluate(frequency, 0.0, 0.0).unwrap();
+    assert!((broadside.power - 16.0).abs() < 1e-9);
+    assert!((broadside.db - 10.0 * 16_f64.log10()).abs() < 1e-9);
+}
@@ -0,0 +1,59 @@
+use antenna_pattern_generator_lib as apg;
+
+use num::complex::Complex;
+
+fn tapered_steered(frequency: f64) -> apg::ElementArray {
+    let spacing = apg::SPEED_OF_LIGHT / frequency / 2.0;
+    let mut array = apg::ElementArray::uniform_linear(8, spacing, |position| {
+        Box::new(apg::OmniElementBuilder::default().position(position).gain(1.0).build().unwrap())
+    });
+    for (n, weight) in array.elements_mut().iter_mut().zip(apg::taylor_linear(8, 4, -30.0)) {
+        n.set_weight(Complex::new(weight, 0.0));
+    }
+    array.steer(frequency, 20_f64.to_radians(), 0.0);
+    array
+}
+
+#[test]
+fn reflects_taper_and_steering() {
+    let frequency = 1e9;
+    let spacing = apg::SPEED_OF_LIGHT / frequency / 2.0;
+    let table = tapered_steered(frequency).excitation_table();
+    let taper = apg::taylor_linear(8, 4, -30.0);
+
+    assert_eq!(table.len(), 8);
+    for (idx, (position, weight)) in table.iter().enumerate() {
+        assert!((position.x() - idx as f64 * spacing).abs() < 1e-12);
+        assert!((weight.norm() - taper[idx]).abs() < 1e-12);
+    }
+    // a linear phase progression of k d sin(theta0) per element
+    let step = apg::PI * 20_f64.to_radians().sin();
+    for pair in table.windows(2) {
+        let delta = (pair[0].1 / pair[1].1).arg();
+        assert!((delta - step).abs() < 1e-9, "{} vs {}", delta, step);
+    }
+}
+
+#[cfg(feature = "io")]
+#[test]
+fn csv() {
+    let frequency = 1e9;
+    let table = tapered_steered(frequency).excitation_table();
+
+    std::fs::create_dir_all("tests/output").unwrap();
+    let path = std::path::Path::new("tests/output/excitations.csv");
+    apg::io::write_excitation_csv(&table, path).unwrap();
+
+    let contents = std::fs::read_to_string(path).unwrap();
+    let mut lines = contents.lines();
+    assert_eq!(lines.next(), Some("x_m,y_m,z_m,amplitude_db,phase_deg"));
+    let rows: Vec<Vec<f64>> = lines.map(|line| line.split(',').map(|v| v.parse().unwrap()).collect()).collect();
+    assert_eq!(rows.len(), 8);
+    for (row, (position, weight)) in rows.iter().zip(&table) {
+        assert_eq!(row[0], position.x());
+        assert!((row[3] - 20.0 * weight.norm().log10()).abs() < 1e-12);
+        assert!((row[4] - weight.arg().to_degrees()).abs() < 1e-12);
+    }
+    // the Taylor taper peaks at 0 dB in the middle
+    assert!(rows[3][3].abs() < 0.5 && rows[0][3] < -3.0);
+}
@@ -0,0 +1,56 @@
+use antenna_pattern_generator_lib as apg;
+
+mod support;
+use support::omni;
+
+#[test]
+fn failures_degrade_the_beam() {
+    let frequency = 10e9;
+    let wavelength = apg::SPEED_OF_LIGHT / frequency;
+    let array = apg::HexArrayBuilder::default()
+        .rings(2)
+        .spacing(0.6 * wavelength)
+        .build(omni);
+
+    let healthy = array.failure_study(frequency, 0.0, 20, 1);
+    assert_eq!(healthy, apg::FailureStats::default());
+
+    let mut last = 0.0;
+    for prob in [0.05, 0.15, 0.3] {
+        let stats = array.failure_study(frequency, prob, 200, 7);
+        assert!(stats.mean_gain_loss_db > last, "{} at {}", stats.mean_gain_loss_db, prob);
+        assert!(stats.worst_gain_loss_db >= stats.mean_gain_loss_db);
+        assert!(stats.worst_sidelobe_rise_db >= stats.mean_sidelobe_rise_db);
+        last = stats.mean_gain_loss_db;
+    }
+
+    // same seed, same answer
+    assert_eq!(array.failure_study(frequency, 0.1, 10, 3), array.failure_study(frequency, 0.1, 10, 3));
+}
+
+#[test]
+fn total_failures_are_counted_apart() {
+    let frequency = 10e9;
+    let wavelength = apg::SPEED_OF_LIGHT / frequency;
+    let array = support::linear_array(4, 0.5 * wavelength);
+
+    let mut last = 0.0;
+    for prob in [0.3, 0.5, 0.7] {
+        let stats = array.failure_study(frequency, prob, 50, 3);
+        assert!(stats.mean_gain_loss_db.is_finite() && stats.worst_sidelobe_rise_db.is_finite(), "{:?}", stats);
+        // losing all 4 elements is likely enough to happen in 50 trials from 0.5 up
+        assert!(stats.lost_beam_trials < 50 && (prob < 0.5 || stats.lost_beam_trials > 0), "{:?}", stats);
+        assert!(stats.mean_gain_loss_db > last, "{:?}", stats);
+        last = stats.mean_gain_loss_db;
+    }
+
+    // every trial loses everything
+    let stats = array.failure_study(frequency, 1.0, 10, 11);
+    assert_eq!(stats, apg::FailureStats { lost_beam_trials: 10, ..Default::default() });
+}
+
+#[test]
+fn empty_array() {
+    let array = apg::ElementArray::new(Vec::new());
+    assert_eq!(array.failure_study(10e9, 0.5, 10, 1), apg::FailureStats::default());
+}
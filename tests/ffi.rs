@@ -0,0 +1,34 @@
+#![cfg(feature = "ffi")]
+
+use antenna_pattern_generator_lib as apg;
+
+use apg::ffi;
+use apg::GainIface;
+
+#[test]
+fn gain_through_handle() {
+    let frequency = 1e9;
+    let spacing = apg::SPEED_OF_LIGHT / frequency / 2.0;
+    let reference = apg::ElementArray::uniform_linear(4, spacing, |position| {
+        Box::new(apg::OmniElementBuilder::default().position(position).gain(1.0).build().unwrap())
+    });
+
+    let handle = ffi::apg_uniform_linear_create(4, spacing);
+    assert!(!handle.is_null());
+
+    let (mut re, mut im) = (0.0, 0.0);
+    let status = unsafe { ffi::apg_array_gain(handle, frequency, 0.7, 0.2, &mut re, &mut im) };
+    assert_eq!(status, ffi::APG_OK);
+    let expected = reference.get_gain(frequency, 0.7, 0.2).unwrap();
+    assert_eq!((re, im), (expected.re, expected.im));
+
+    let status = unsafe { ffi::apg_array_gain(handle, frequency, 0.7, 0.2, std::ptr::null_mut(), &mut im) };
+    assert_eq!(status, ffi::APG_NULL_POINTER);
+    let status = unsafe { ffi::apg_array_gain(std::ptr::null(), frequency, 0.7, 0.2, &mut re, &mut im) };
+    assert_eq!(status, ffi::APG_NULL_POINTER);
+
+    unsafe {
+        ffi::apg_array_destroy(handle);
+        ffi::apg_array_destroy(std::ptr::null_mut());
+    }
+}
@@ -0,0 +1,26 @@
+use antenna_pattern_generator_lib as apg;
+
+use num::complex::Complex;
+
+#[test]
+fn recovers_cos_4() {
+    let step = 2.0 * apg::PI / 180.0;
+    let grid = apg::PatternGrid::from_fn(step, step, |theta, phi| {
+        // a little phase and a back lobe shouldn't matter
+        let value = if theta < apg::PI / 2.0 { 3.0 * theta.cos().powi(4) } else { 0.001 };
+        Complex::from_polar(value, phi)
+    });
+
+    let (n, peak) = apg::fit_cos_n(&grid);
+    assert!((n - 4.0).abs() < 1e-6, "{}", n);
+    assert!((peak - 3.0).abs() < 1e-6, "{}", peak);
+}
+
+#[test]
+fn flat_pattern_has_no_exponent() {
+    let step = 2.0 * apg::PI / 180.0;
+    let grid = apg::PatternGrid::from_fn(step, step, |_, _| Complex::new(2.0, 0.0));
+    let (n, peak) = apg::fit_cos_n(&grid);
+    assert!(n.abs() < 1e-9);
+    assert!((peak - 2.0).abs() < 1e-9);
+}
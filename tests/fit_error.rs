@@ -0,0 +1,46 @@
+use antenna_pattern_generator_lib as apg;
+
+use num::complex::Complex;
+
+mod support;
+use support::separation;
+
+/// sin(x)/x pencil beam, `hpbw` wide
+fn beam(center: (f64, f64), hpbw: f64, peak: f64) -> apg::PatternGrid {
+    let step = 0.5 * apg::PI / 180.0;
+    apg::PatternGrid::from_fn(step, 2.0 * step, |theta, phi| {
+        // sin(x)/x is at half power for x = 1.39156
+        let x = 1.39156 * separation((theta, phi), center) / (hpbw / 2.0);
+        Complex::new(peak * if x == 0.0 { 1.0 } else { x.sin() / x }, 0.0)
+    })
+}
+
+#[test]
+fn compared_to_itself() {
+    let grid = beam((0.3, 1.0), 0.2, 1.0);
+    let report = grid.fit_error_vs(&grid);
+    assert_eq!(report.peak_gain_error_db, 0.0);
+    assert_eq!(report.pointing_error, 0.0);
+    assert_eq!(report.beamwidth_error, 0.0);
+    // the reference is interpolated, which is only exact to rounding
+    assert!(report.rms_sidelobe_error_db < 1e-9);
+}
+
+#[test]
+fn shifted_and_widened() {
+    let deg = apg::PI / 180.0;
+    let reference = beam((0.0, 0.0), 10.0 * deg, 1.0);
+
+    // steered off by 5 deg and 1 dB hotter
+    let shifted = beam((5.0 * deg, 0.0), 10.0 * deg, 10_f64.powf(0.05));
+    let report = shifted.fit_error_vs(&reference);
+    assert!((report.peak_gain_error_db - 1.0).abs() < 1e-6);
+    assert!((report.pointing_error - 5.0 * deg).abs() < 0.1 * deg);
+    assert!(report.beamwidth_error.abs() < 0.5 * deg);
+    assert!(report.rms_sidelobe_error_db > 0.0);
+
+    let widened = beam((0.0, 0.0), 14.0 * deg, 1.0);
+    let report = widened.fit_error_vs(&reference);
+    assert!(report.pointing_error < 1e-9);
+    assert!((report.beamwidth_error - 4.0 * deg).abs() < 0.5 * deg, "{}", report.beamwidth_error / deg);
+}
@@ -0,0 +1,32 @@
+use antenna_pattern_generator_lib as apg;
+
+use apg::GainIface;
+
+mod support;
+use support::omni;
+
+/// Gain (dB) at a signed angle from boresight in the XZ plane
+fn cut_db(array: &apg::ElementArray, frequency: f64, degrees: f64) -> f64 {
+    let phi = if degrees < 0.0 { apg::PI } else { 0.0 };
+    20.0 * array.get_gain(frequency, degrees.abs().to_radians(), phi).unwrap().norm().log10()
+}
+
+#[test]
+fn flattop_sector() {
+    let frequency = 1e9;
+    let mut array = apg::ElementArray::uniform_linear_wl(16, 0.5, frequency, omni);
+    array.synthesize_flattop(frequency, 40_f64.to_radians(), 1.0);
+
+    let sector: Vec<f64> = (-80..=80).map(|idx| cut_db(&array, frequency, idx as f64 * 0.25)).collect();
+    let high = sector.iter().cloned().fold(f64::MIN, f64::max);
+    let low = sector.iter().cloned().fold(f64::MAX, f64::min);
+    assert!(high - low <= 1.0, "{}", high - low);
+
+    // well clear of the sector edges the pattern is down by more than 15 dB
+    let mean = sector.iter().sum::<f64>() / sector.len() as f64;
+    for idx in 35..=90 {
+        let degrees = idx as f64;
+        assert!(cut_db(&array, frequency, degrees) < mean - 15.0);
+        assert!(cut_db(&array, frequency, -degrees) < mean - 15.0);
+    }
+}
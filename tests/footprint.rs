@@ -0,0 +1,38 @@
+use antenna_pattern_generator_lib as apg;
+
+use apg::GainIface;
+
+mod support;
+use support::omni;
+
+#[test]
+fn coverage_improves() {
+    let frequency = 10e9;
+    let wavelength = apg::SPEED_OF_LIGHT / frequency;
+    let mut array = apg::HexArrayBuilder::default()
+        .rings(4)
+        .spacing(0.5 * wavelength)
+        .build(omni);
+
+    // a wide wedge off to one side, much bigger than the uniform beam
+    let deg = apg::PI / 180.0;
+    let coverage: Vec<(f64, f64)> = (0..6)
+        .flat_map(|t| (0..5).map(move |p| ((10.0 + 4.0 * t as f64) * deg, (-20.0 + 10.0 * p as f64) * deg)))
+        .map(|(theta, phi)| (theta, phi.rem_euclid(2.0 * apg::PI)))
+        .collect();
+    let worst = |array: &apg::ElementArray| {
+        coverage.iter()
+            .map(|(theta, phi)| array.get_gain(frequency, *theta, *phi).unwrap().norm())
+            .fold(f64::INFINITY, f64::min)
+    };
+
+    let uniform = worst(&array);
+    array.synthesize_footprint(&coverage, frequency, 30);
+    let shaped = worst(&array);
+    assert!(shaped > 2.0 * uniform, "{} vs {}", shaped, uniform);
+
+    // phase only
+    for n in array.elements() {
+        assert!((n.weight().norm() - 1.0).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,34 @@
+use antenna_pattern_generator_lib as apg;
+
+use apg::{ArrayIface, GainIface};
+
+mod support;
+use support::omni;
+
+#[test]
+fn flat_layout() {
+    let frequency = 1e9;
+    let step = 10.0 * apg::PI / 180.0;
+    let mut array = apg::ElementArray::uniform_linear_wl(5, 0.5, frequency, omni);
+    array.steer(frequency, 0.4, 0.3);
+
+    // the same directions as the sphere grid reshape to the same samples
+    let grid = array.sample_sphere(frequency, step, step);
+    let thetas: Vec<f64> = (0..grid.num_theta()).map(|idx| grid.theta(idx)).collect();
+    let phis: Vec<f64> = (0..grid.num_phi()).map(|idx| grid.phi(idx)).collect();
+    let (values, rows, cols) = array.gain_flat(frequency, &thetas, &phis);
+    assert_eq!((rows, cols), (grid.num_phi(), grid.num_theta()));
+    assert_eq!(values.len(), rows * cols);
+    for (phi_idx, row) in values.chunks(cols).enumerate() {
+        for (theta_idx, value) in row.iter().enumerate() {
+            assert_eq!(*value, grid.get(phi_idx, theta_idx));
+        }
+    }
+
+    // arbitrary, unevenly spaced directions
+    let thetas = [0.0, 0.25, 1.0];
+    let phis = [3.0, 0.1];
+    let (values, rows, cols) = array.gain_flat(frequency, &thetas, &phis);
+    assert_eq!((rows, cols), (2, 3));
+    assert_eq!(values[cols + 2], array.get_gain(frequency, 1.0, 0.1).unwrap());
+}
@@ -0,0 +1,46 @@
+use antenna_pattern_generator_lib as apg;
+
+use apg::{ElementIface, GainIface};
+use num::complex::Complex;
+
+#[test]
+fn interpolated_gain() {
+    let patch = apg::PatchElementBuilder::default()
+        .position(apg::PointBuilder::default().x(0.1).build().unwrap())
+        .length(0.03)
+        .width(0.04)
+        .build()
+        .unwrap();
+    let table = vec![(2e9, 1.0), (3e9, 0.5), (4e9, 0.25)];
+    let element = apg::GainTableElement::new(Box::new(patch.clone()), table.clone());
+    assert_eq!(element.position(), patch.position());
+
+    let (theta, phi) = (0.4, 1.2);
+    for (frequency, gain) in &table {
+        let expected = patch.get_gain(*frequency, theta, phi).unwrap() * *gain;
+        assert!((element.get_gain(*frequency, theta, phi).unwrap() - expected).norm() < 1e-12);
+    }
+
+    // a quarter of the way from 3 to 4 GHz
+    assert!((element.gain_at(3.25e9) - 0.4375).abs() < 1e-12);
+    let expected = patch.get_gain(3.25e9, theta, phi).unwrap() * 0.4375;
+    assert!((element.get_gain(3.25e9, theta, phi).unwrap() - expected).norm() < 1e-12);
+
+    // held at the ends of the band
+    assert_eq!(element.gain_at(1e9), 1.0);
+    assert_eq!(element.gain_at(5e9), 0.25);
+}
+
+#[test]
+fn weight_passes_through() {
+    let omni = apg::OmniElementBuilder::default()
+        .position(apg::Point::default())
+        .gain(1.0)
+        .build()
+        .unwrap();
+    let mut element = apg::GainTableElement::new(Box::new(omni), vec![(1e9, 2.0)]);
+    element.set_weight(Complex::new(0.0, 0.5));
+    assert_eq!(element.element().weight(), Complex::new(0.0, 0.5));
+    let gain = element.get_gain(1.5e9, 0.3, 0.0).unwrap();
+    assert!((gain - Complex::new(0.0, 1.0)).norm() < 1e-12);
+}
@@ -0,0 +1,74 @@
+use antenna_pattern_generator_lib as apg;
+
+use apg::GainIface;
+
+mod support;
+use support::omni;
+
+#[test]
+fn conversions() {
+    assert_eq!(apg::dbi_to_dbd(2.15), 0.0);
+    assert_eq!(apg::dbi_to_dbd(10.0), 7.85);
+    assert_eq!(apg::dbi_to_dbic(5.0, apg::Polarization::Circular), 5.0);
+    assert!((apg::dbi_to_dbic(5.0, apg::Polarization::Linear) - 1.99).abs() < 0.01);
+}
+
+#[test]
+fn antenna_factor() {
+    // 0 dBi at 100 MHz: 20 log10(100) - 29.79
+    assert!((apg::gain_to_antenna_factor(0.0, 100e6) - 10.21).abs() < 0.05);
+    for gain_dbi in [-5.0, 0.0, 2.15, 12.0] {
+        let af = apg::gain_to_antenna_factor(gain_dbi, 300e6);
+        assert!((apg::antenna_factor_to_gain(af, 300e6) - gain_dbi).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn effective_height() {
+    // a half-wave dipole (73 ohms) has an effective height of about wavelength / PI
+    let frequency = 300e6;
+    let wavelength = apg::SPEED_OF_LIGHT / frequency;
+    let height = apg::gain_to_effective_height(apg::DIPOLE_GAIN_DBI, frequency, 73.0);
+    assert!((height - wavelength / apg::PI).abs() < 0.01 * wavelength / apg::PI);
+
+    for gain_dbi in [-5.0, 0.0, 2.15, 12.0] {
+        let height = apg::gain_to_effective_height(gain_dbi, frequency, 50.0);
+        assert!((apg::effective_height_to_gain(height, frequency, 50.0) - gain_dbi).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn peak_gain_references() {
+    let frequency = 1e9;
+    let step = 1.0 * apg::PI / 180.0;
+    let array = apg::ElementArray::uniform_linear_wl(6, 0.5, frequency, omni);
+    let grid = array.sample_sphere(frequency, step, step);
+
+    assert!((grid.peak_gain_dbi() - 10.0 * 6_f64.log10()).abs() < 0.05);
+    assert!((grid.peak_gain_dbi() - grid.peak_gain_dbd() - 2.15).abs() < 1e-12);
+}
+
+#[test]
+fn horizon_band_gain() {
+    let frequency = 1e9;
+    let spacing = apg::SPEED_OF_LIGHT / frequency / 2.0;
+    let step = apg::PI / 180.0;
+    let mut planar = apg::PlanarArray::uniform(8, 8, spacing, spacing, omni);
+    let (low, high) = (0.0, 5_f64.to_radians());
+
+    // a broadside pencil beam puts little gain on the horizon
+    let grid = planar.array().sample_sphere(frequency, step, step);
+    let broadside = grid.max_gain_in_elevation_band(low, high);
+    assert!(broadside < grid.peak_gain_dbi() - 10.0, "{} vs {}", broadside, grid.peak_gain_dbi());
+
+    // the peak is found in a band around the beam
+    assert!((grid.max_gain_in_elevation_band(80_f64.to_radians(), 90_f64.to_radians()) - grid.peak_gain_dbi()).abs() < 1e-9);
+
+    // steered down to 10 degrees elevation the beam spills onto the horizon
+    planar.array_mut().steer(frequency, 80_f64.to_radians(), 0.0);
+    let grid = planar.array().sample_sphere(frequency, step, step);
+    let steered = grid.max_gain_in_elevation_band(low, high);
+    assert!(steered > broadside + 10.0, "{} vs {}", steered, broadside);
+
+    assert_eq!(grid.max_gain_in_elevation_band(0.5, 0.4), f64::NEG_INFINITY);
+}
@@ -0,0 +1,51 @@
+use antenna_pattern_generator_lib as apg;
+
+use apg::{ElementIface, GainIface};
+
+#[test]
+fn measured_hpbw() {
+    let frequency = 10e9;
+    let deg = apg::PI / 180.0;
+
+    for hpbw in [5.0 * deg, 20.0 * deg, 60.0 * deg] {
+        let element = apg::GaussianBeamElementBuilder::default()
+            .position(apg::Point::default())
+            .hpbw(hpbw)
+            .build()
+            .unwrap();
+        assert_eq!(element.hpbw(), hpbw);
+
+        // half power angle by bisection
+        let power = |theta: f64| element.get_gain(frequency, theta, 0.3).unwrap().norm_sqr();
+        let (mut low, mut high) = (0.0, apg::PI);
+        for _ in 0..60 {
+            let mid = 0.5 * (low + high);
+            if power(mid) > 0.5 { low = mid } else { high = mid }
+        }
+        assert!((2.0 * low - hpbw).abs() < 1e-9);
+
+        // no sidelobes: falls away monotonically
+        let grid = element.sample_sphere(frequency, 1.0 * deg, 10.0 * deg);
+        for row in grid.values() {
+            assert!(row.windows(2).all(|pair| pair[1].norm() <= pair[0].norm()));
+        }
+    }
+}
+
+#[test]
+fn positional_phase() {
+    let frequency = 10e9;
+    let wavelength = apg::SPEED_OF_LIGHT / frequency;
+    let element = apg::GaussianBeamElementBuilder::default()
+        .position(apg::PointBuilder::default().x(0.25 * wavelength).build().unwrap())
+        .hpbw(0.5)
+        .gain(2.0)
+        .build()
+        .unwrap();
+
+    let theta: f64 = 0.2;
+    let gain = element.get_gain(frequency, theta, 0.0).unwrap();
+    assert!((gain.norm() - element.pattern(frequency, theta, 0.0).unwrap().norm()).abs() < 1e-12);
+    assert!((gain.arg() - 0.5 * apg::PI * theta.sin()).abs() < 1e-12);
+    assert_eq!(element.pattern(frequency, 0.0, 0.0).unwrap().re, 2.0);
+}
@@ -0,0 +1,26 @@
+use antenna_pattern_generator_lib as apg;
+
+use apg::ElementIface;
+
+#[test]
+fn read_grasp_cut() {
+    // x-polarized source with a cos(theta) pattern, cut at phi = 0 and 90 deg
+    // from theta = -180 to 180 deg in 15 deg steps
+    let element = apg::DataElement::from_grasp_cut("tests/data/x_pol.cut").unwrap();
+    let deg = apg::PI / 180.0;
+
+    assert!((element.data().theta_step() - 15.0 * deg).abs() < 1e-9);
+    assert!((element.data().phi_step() - 90.0 * deg).abs() < 1e-9);
+
+    // phi = 180 and 270 deg only come from the negative theta half of each cut
+    for phi in [0.0, 90.0, 180.0, 270.0] {
+        for theta in [0.0, 30.0, 45.0, 90.0, 150.0] {
+            let (theta, phi): (f64, f64) = (theta * deg, phi * deg);
+            let co = element.pattern(1e9, theta, phi).unwrap();
+            let cross = element.cross().unwrap().interpolate(theta, phi);
+            assert!((co.re - theta.cos()).abs() < 1e-6, "co at ({}, {}) = {}", theta, phi, co);
+            assert!(co.im.abs() < 1e-6);
+            assert!(cross.norm() < 1e-6, "cross at ({}, {}) = {}", theta, phi, cross);
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use antenna_pattern_generator_lib as apg;
+
+use apg::GainIface;
+
+mod support;
+use support::omni;
+
+#[test]
+fn matches_scalar_gain() {
+    let frequency = 10e9;
+    let wavelength = apg::SPEED_OF_LIGHT / frequency;
+    let mut array = apg::HexArrayBuilder::default()
+        .rings(3)
+        .spacing(0.6 * wavelength)
+        .build(omni);
+    array.steer(frequency, 0.4, 1.0);
+
+    // a pass sweeping through the beam
+    let track: Vec<(f64, f64)> = (0..50)
+        .map(|idx| (-0.8 + 0.035 * idx as f64, 1.0))
+        .map(|(theta, phi): (f64, f64)| if theta < 0.0 { (-theta, phi + apg::PI) } else { (theta, phi) })
+        .collect();
+
+    let gains = array.gain_along_track(frequency, &track);
+    assert_eq!(gains.len(), track.len());
+    for (gain, (theta, phi)) in gains.iter().zip(&track) {
+        let expected = array.get_gain(frequency, *theta, *phi).unwrap().norm();
+        assert!((gain - expected).abs() < 1e-9 * expected.max(1.0));
+    }
+
+    assert!(array.gain_along_track(frequency, &[]).is_empty());
+}
+
+#[test]
+fn follows_moving_target() {
+    let frequency = 10e9;
+    let wavelength = apg::SPEED_OF_LIGHT / frequency;
+    let mut array = apg::HexArrayBuilder::default()
+        .rings(4)
+        .spacing(0.6 * wavelength)
+        .build(omni);
+    let peak = array.get_gain(frequency, 0.0, 0.0).unwrap().norm();
+
+    // moving steadily across the sky, through the pole
+    let history: Vec<(f64, f64)> = (0..30)
+        .map(|idx| (-0.6 + 0.04 * idx as f64, 0.7))
+        .map(|(theta, phi): (f64, f64)| if theta < 0.0 { (-theta, phi + apg::PI) } else { (theta, phi) })
+        .collect();
+
+    let gains = array.track(frequency, &history, 20);
+    assert_eq!(gains.len(), 20);
+    // lagging a step behind until there are two positions to extrapolate from
+    assert!(gains[1] < gains[0]);
+    // omni elements have no scan loss, so on target is the broadside peak
+    for (idx, gain) in gains.iter().enumerate().skip(2) {
+        assert!((gain - peak).abs() < 1e-6 * peak, "step {}: {} vs {}", idx, gain, peak);
+    }
+
+    assert_eq!(array.track(frequency, &history, 100).len(), history.len());
+}
@@ -0,0 +1,48 @@
+use antenna_pattern_generator_lib as apg;
+
+/// Unevenly spaced line along x, so nothing is linear phase by symmetry
+fn line(frequency: f64) -> apg::ElementArray {
+    let wavelength = apg::SPEED_OF_LIGHT / frequency;
+    apg::ElementArray::new([0.0, 0.4, 1.1, 1.5, 2.3]
+        .iter()
+        .map(|x| -> Box<dyn apg::ElementIface> {
+            Box::new(apg::OmniElementBuilder::default()
+                .position(apg::PointBuilder::default().x(x * wavelength).build().unwrap())
+                .gain(1.0)
+                .build()
+                .unwrap())
+        })
+        .collect())
+}
+
+#[test]
+fn true_time_delay_is_flat() {
+    let center = 1e9;
+    let theta0: f64 = 30.0_f64.to_radians();
+    let freqs: Vec<f64> = (0..41).map(|idx| 0.8e9 + idx as f64 * 10e6).collect();
+
+    // true time delay steering: the delays line up every element at theta0
+    let common = 2e-9;
+    let mut ttd = line(center);
+    for n in ttd.elements_mut() {
+        let delay = common + n.position().x() * theta0.sin() / apg::SPEED_OF_LIGHT;
+        n.set_delay(delay);
+    }
+    for delay in ttd.group_delay(&freqs, theta0, 0.0) {
+        assert!((delay - common).abs() < 1e-15);
+    }
+
+    // phase steering is only right at the center frequency
+    let mut phased = line(center);
+    phased.steer(center, theta0, 0.0);
+    let delays = phased.group_delay(&freqs, theta0, 0.0);
+    let spread = delays.iter().cloned().fold(f64::MIN, f64::max) - delays.iter().cloned().fold(f64::MAX, f64::min);
+    assert!(spread > 1e-11, "{}", spread);
+}
+
+#[test]
+fn short_sweeps() {
+    let array = line(1e9);
+    assert!(array.group_delay(&[], 0.0, 0.0).is_empty());
+    assert_eq!(array.group_delay(&[1e9], 0.0, 0.0), vec![0.0]);
+}
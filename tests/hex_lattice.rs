@@ -0,0 +1,104 @@
+use antenna_pattern_generator_lib as apg;
+
+mod support;
+use support::omni;
+
+#[test]
+fn hexagonal_positions() {
+    let frequency = 1e9;
+    let spacing = 0.6 * apg::SPEED_OF_LIGHT / frequency;
+    let array = apg::HexArrayBuilder::default().rings(3).spacing(spacing).build(omni);
+
+    assert_eq!(array.elements().len(), 37);
+
+    // the center element has six nearest neighbours all one spacing away
+    let center = array.elements().iter().find(|n| n.position().x().abs() < 1e-12 && n.position().y().abs() < 1e-12);
+    let center = center.unwrap().position();
+    let neighbours = array.elements().iter().filter(|n| {
+        let p = n.position();
+        (((p.x() - center.x()).powi(2) + (p.y() - center.y()).powi(2)).sqrt() - spacing).abs() < 1e-9
+    });
+    assert_eq!(neighbours.count(), 6);
+
+    match array.detect_lattice() {
+        apg::Lattice::Hexagonal { spacing: detected } => assert!((detected - spacing).abs() < 1e-9),
+        other => panic!("expected a hexagonal lattice, got {:?}", other),
+    }
+}
+
+#[test]
+fn scans_further_than_square() {
+    let frequency = 1e9;
+    let spacing = 0.6 * apg::SPEED_OF_LIGHT / frequency;
+
+    let hex = apg::HexArrayBuilder::default().rings(3).spacing(spacing).build(omni);
+
+    let mut elements = Vec::new();
+    for row in 0..6 {
+        for col in 0..6 {
+            elements.push(omni(apg::PointBuilder::default().x(col as f64 * spacing).y(row as f64 * spacing).build().unwrap()));
+        }
+    }
+    let square = apg::ElementArray::new( elements );
+    assert!(matches!(square.detect_lattice(), apg::Lattice::Rectangular { .. }));
+
+    let hex_scan = hex.detect_lattice().max_scan(frequency).unwrap();
+    let square_scan = square.detect_lattice().max_scan(frequency).unwrap();
+    assert!((square_scan - (1.0_f64 / 0.6 - 1.0).asin()).abs() < 1e-9);
+    assert!(hex_scan > square_scan);
+}
+
+#[test]
+fn lattice_has_to_hold_every_element() {
+    let at = |x: f64, y: f64| omni(apg::PointBuilder::default().x(x).y(y).build().unwrap());
+    let height = 3.0_f64.sqrt() / 2.0;
+
+    // two rows of a triangular lattice, no element has six neighbours
+    let strip: Vec<_> = (0..4).map(|col| at(col as f64, 0.0)).chain((0..3).map(|col| at(col as f64 + 0.5, height))).collect();
+    match apg::ElementArray::new(strip).detect_lattice() {
+        apg::Lattice::Hexagonal { spacing } => assert!((spacing - 1.0).abs() < 1e-9),
+        other => panic!("expected a hexagonal lattice, got {:?}", other),
+    }
+
+    // one hexagonal cell with two stray elements isn't a lattice
+    let cell = apg::HexArrayBuilder::default().rings(1).spacing(1.0).build(omni);
+    let mut elements = cell.elements().to_vec();
+    elements.push(at(7.3, 2.1));
+    elements.push(at(-5.0, 9.4));
+    let stray = apg::ElementArray::new(elements);
+    assert_eq!(stray.detect_lattice(), apg::Lattice::Irregular);
+    assert_eq!(stray.detect_lattice().max_scan(1e9), None);
+
+    // a thinned rectangular grid is still rectangular
+    let thinned: Vec<_> = (0..12)
+        .filter(|idx| ![5, 10].contains(idx))
+        .map(|idx| at((idx % 4) as f64, (idx / 4) as f64 * 0.5))
+        .collect();
+    assert_eq!(apg::ElementArray::new(thinned).detect_lattice(), apg::Lattice::Rectangular { dx: 1.0, dy: 0.5 });
+}
+
+#[test]
+fn degenerate_positions() {
+    let at = |x: f64, y: f64| omni(apg::PointBuilder::default().x(x).y(y).build().unwrap());
+
+    let coincident = apg::ElementArray::new(vec![at(0.0, 0.0), at(0.0, 0.0), at(1.0, 0.0)]);
+    assert_eq!(coincident.detect_lattice(), apg::Lattice::Irregular);
+
+    let not_finite = apg::ElementArray::new(vec![at(0.0, 0.0), at(1.0, 0.0), at(f64::NAN, 1.0)]);
+    assert_eq!(not_finite.detect_lattice(), apg::Lattice::Irregular);
+}
+
+#[test]
+fn spacing_for_scan() {
+    assert!((apg::max_spacing_for_scan(90.0) - 0.5).abs() < 1e-12);
+    assert!((apg::max_spacing_for_scan(0.0) - 1.0).abs() < 1e-12);
+    assert!(apg::max_spacing_for_scan(1.0) < 1.0);
+    assert!(apg::max_spacing_for_scan(1.0) > 0.98);
+
+    // spacing chosen for a 30 degree scan puts the grating lobe right at the edge
+    let frequency = 1e9;
+    let wavelength = apg::SPEED_OF_LIGHT / frequency;
+    let spacing = apg::max_spacing_for_scan(30.0) * wavelength;
+    let scan = apg::Lattice::Linear { spacing }.max_scan(frequency).unwrap();
+    assert!((scan.to_degrees() - 30.0).abs() < 1e-9);
+}
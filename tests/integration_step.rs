@@ -0,0 +1,51 @@
+use antenna_pattern_generator_lib as apg;
+
+use apg::ArrayIface;
+use num::complex::Complex;
+
+mod support;
+use support::omni;
+
+/// Exact directivity of weighted omnis, peak over the integral of
+/// sum_mn w_m conj(w_n) sinc(k * |p_m - p_n|)
+fn omni_directivity(array: &apg::ElementArray, frequency: f64) -> f64 {
+    let k = 2.0 * apg::PI * frequency / apg::SPEED_OF_LIGHT;
+    let peak: f64 = array.elements().iter().map(|n| n.weight().norm()).sum();
+    let mut average = Complex::new(0.0, 0.0);
+    for m in array.elements() {
+        for n in array.elements() {
+            let (a, b) = (m.position(), n.position());
+            let kd = k * ((a.x() - b.x()).powi(2) + (a.y() - b.y()).powi(2) + (a.z() - b.z()).powi(2)).sqrt();
+            let sinc = if kd == 0.0 { 1.0 } else { kd.sin() / kd };
+            average += m.weight() * n.weight().conj() * sinc;
+        }
+    }
+    peak * peak / average.re
+}
+
+#[test]
+fn recommended_step_is_accurate() {
+    let frequency = 10e9;
+    let wavelength = apg::SPEED_OF_LIGHT / frequency;
+
+    let line = apg::ElementArray::uniform_linear_wl(8, 0.5, frequency, omni);
+    let mut hex = apg::HexArrayBuilder::default()
+        .rings(2)
+        .spacing(0.6 * wavelength)
+        .build(omni);
+    hex.steer(frequency, 0.3, 0.7);
+
+    for array in [&line, &hex] {
+        let exact = omni_directivity(array, frequency);
+        for target in [0.05, 0.01] {
+            let (theta_step, phi_step) = apg::recommended_integration_step(array, frequency, target);
+            let directivity = 10_f64.powf(array.peak_to_average(frequency, theta_step, phi_step) / 10.0);
+            assert!(((directivity - exact) / exact).abs() < target, "{} vs {} at {}", directivity, exact, target);
+        }
+    }
+
+    // finer targets need finer steps
+    let coarse = apg::recommended_integration_step(&hex, frequency, 0.05);
+    let fine = apg::recommended_integration_step(&hex, frequency, 0.001);
+    assert!(fine.0 < coarse.0 && fine.1 < coarse.1);
+}
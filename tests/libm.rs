@@ -0,0 +1,45 @@
+#![cfg(feature = "libm")]
+
+use antenna_pattern_generator_lib as apg;
+
+use apg::{ElementIface, GainIface};
+use num::complex::Complex;
+
+// the same formulas as the crate, written with the std f64 methods
+fn std_phase(position: &apg::Point, frequency: f64, theta: f64, phi: f64) -> Complex<f64> {
+    let k = 2.0 * apg::PI * frequency / apg::SPEED_OF_LIGHT;
+    let phase = k * (position.x() * phi.cos() * theta.sin() + position.y() * phi.sin() * theta.sin() + position.z() * theta.cos());
+    Complex::new(phase.cos(), phase.sin())
+}
+
+fn std_patch(length: f64, width: f64, frequency: f64, theta: f64, phi: f64) -> f64 {
+    let k = 2.0 * apg::PI * frequency / apg::SPEED_OF_LIGHT;
+    let inside = k * width * theta.sin() * phi.sin() / 2.0;
+    let sinc = if inside == 0.0 { 1.0 } else { inside.sin() / inside };
+    let value = sinc * (k * length * theta.sin() * phi.cos()).cos();
+    ((value * phi.cos()).powi(2) + (value * theta.cos() * phi.sin()).powi(2)).sqrt()
+}
+
+#[test]
+fn matches_std() {
+    let frequency = 10e9;
+    let position = apg::PointBuilder::default().x(0.013).y(-0.021).z(0.004).build().unwrap();
+    let omni = apg::OmniElementBuilder::default().position(position.clone()).gain(1.0).build().unwrap();
+    let patch = apg::PatchElementBuilder::default()
+        .position(apg::Point::default())
+        .length(0.015)
+        .width(0.012)
+        .build()
+        .unwrap();
+
+    for theta in [0.0, 0.3, 0.9, 1.4] {
+        for phi in [0.0, 0.5, 2.0, 4.5] {
+            let gain = omni.get_gain(frequency, theta, phi).unwrap();
+            assert!((gain - std_phase(&position, frequency, theta, phi)).norm() < 1e-12);
+
+            let pattern = patch.pattern(frequency, theta, phi).unwrap();
+            assert!((pattern.re - std_patch(0.015, 0.012, frequency, theta, phi)).abs() < 1e-12);
+            assert_eq!(pattern.im, 0.0);
+        }
+    }
+}
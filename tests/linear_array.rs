@@ -0,0 +1,26 @@
+use antenna_pattern_generator_lib as apg;
+
+use apg::GainIface;
+
+mod support;
+use support::omni;
+
+#[test]
+fn half_wavelength_spacing() {
+    let frequency = 1e9;
+    let wavelength = apg::SPEED_OF_LIGHT / frequency;
+
+    let array = apg::ElementArray::uniform_linear_wl(4, 0.5, frequency, omni);
+    assert_eq!(array.elements().len(), 4);
+    for (idx, n) in array.elements().iter().enumerate() {
+        assert!((n.position().x() - idx as f64 * wavelength / 2.0).abs() < 1e-12);
+        assert_eq!(n.position().y(), 0.0);
+        assert_eq!(n.position().z(), 0.0);
+    }
+
+    let meters = apg::ElementArray::uniform_linear(4, wavelength / 2.0, omni);
+    for theta in [0.0, 0.3, 1.1] {
+        let expected = meters.get_gain(frequency, theta, 0.0).unwrap();
+        assert!((array.get_gain(frequency, theta, 0.0).unwrap() - expected).norm() < 1e-12);
+    }
+}
@@ -0,0 +1,55 @@
+use antenna_pattern_generator_lib as apg;
+
+use apg::GainIface;
+
+fn patch(position: apg::Point) -> Box<dyn apg::ElementIface> {
+    Box::new(apg::PatchElementBuilder::default()
+        .position(position)
+        .length(0.015)
+        .width(0.012)
+        .build()
+        .unwrap())
+}
+
+/// Widest first null of the main beam over a set of phi cuts (radians)
+fn main_beam_cone(array: &apg::ElementArray, frequency: f64) -> f64 {
+    (0..24)
+        .map(|p| {
+            let phi = p as f64 * 15.0 * apg::PI / 180.0;
+            let cut: Vec<f64> = (0..900)
+                .map(|t| array.get_gain(frequency, t as f64 * 0.1 * apg::PI / 180.0, phi).unwrap().norm())
+                .collect();
+            let first_null = (1..cut.len()).find(|idx| cut[*idx] > cut[*idx - 1]).unwrap();
+            first_null as f64 * 0.1 * apg::PI / 180.0
+        })
+        .fold(0.0, f64::max)
+}
+
+/// Largest gain more than `cone` from boresight relative to boresight (dB), on a 1 degree grid
+fn peak_sidelobe_db(array: &apg::ElementArray, frequency: f64, cone: f64) -> f64 {
+    let sidelobe = (0..360)
+        .flat_map(|p| (0..=90).map(move |t| (t as f64 * apg::PI / 180.0, p as f64 * apg::PI / 180.0)))
+        .filter(|(theta, _)| *theta > cone)
+        .map(|(theta, phi)| array.get_gain(frequency, theta, phi).unwrap().norm())
+        .fold(0.0, f64::max);
+    20.0 * (sidelobe / array.get_gain(frequency, 0.0, 0.0).unwrap().norm()).log10()
+}
+
+#[test]
+fn minimax_beats_taylor() {
+    let frequency = 10e9;
+    let spacing = apg::SPEED_OF_LIGHT / frequency / 2.0;
+
+    let mut taylor = apg::PlanarArray::uniform(6, 6, spacing, spacing, patch);
+    let taper = apg::taylor_linear(6, 3, -30.0);
+    taylor.apply_separable_taper(&taper, &taper);
+    let cone = main_beam_cone(taylor.array(), frequency);
+    let reference_db = peak_sidelobe_db(taylor.array(), frequency, cone);
+
+    let mut array = apg::PlanarArray::uniform(6, 6, spacing, spacing, patch).array().clone();
+    array.minimize_peak_sidelobe(cone.to_degrees(), frequency);
+    assert!((array.get_gain(frequency, 0.0, 0.0).unwrap().norm() - 1.0).abs() < 1e-9);
+
+    let optimized_db = peak_sidelobe_db(&array, frequency, cone);
+    assert!(optimized_db < reference_db, "{} {}", optimized_db, reference_db);
+}
@@ -0,0 +1,46 @@
+use antenna_pattern_generator_lib as apg;
+
+use apg::GainIface;
+
+mod support;
+use support::{linear_array, omni, point};
+
+#[test]
+fn difference_pattern() {
+    let frequency = 1e9;
+    let wavelength = apg::SPEED_OF_LIGHT / frequency;
+    let spacing = wavelength / 2.0;
+    let k = 2.0 * apg::PI / wavelength;
+
+    let array = apg::ElementArray::new( vec![omni(point(0.0, 0.0)), omni(point(spacing, 0.0))] );
+    let step = 0.5 * apg::PI / 180.0;
+    let diff = array.monopulse_difference(frequency, apg::Axis::X, step, step);
+
+    // deep null at boresight compared to the sum channel
+    let sum = array.get_gain(frequency, 0.0, 0.0).unwrap().norm();
+    assert!(diff.get(0, 0).norm() / sum < 1e-9);
+
+    // two elements give |D| = 2|sin(k d sin(theta) / 2)| in the phi = 0 plane
+    for theta_idx in 1..20 {
+        let theta = diff.theta(theta_idx);
+        let expected = 2.0 * (k * spacing * theta.sin() / 2.0).sin().abs();
+        assert!((diff.get(0, theta_idx).norm() - expected).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn error_slope() {
+    let frequency = 1e9;
+    let wavelength = apg::SPEED_OF_LIGHT / frequency;
+    let spacing = wavelength / 2.0;
+    let k = 2.0 * apg::PI / wavelength;
+
+    // two elements give |D/S| = tan(k d sin(theta) / 2)
+    let small = apg::ElementArray::new( vec![omni(point(0.0, 0.0)), omni(point(spacing, 0.0))] );
+    let small_slope = small.monopulse_slope(frequency, apg::Axis::X);
+    assert!((small_slope - k * spacing / 2.0).abs() < 1e-3);
+
+    let large = linear_array(8, spacing);
+    let large_slope = large.monopulse_slope(frequency, apg::Axis::X);
+    assert!(large_slope > 2.0 * small_slope);
+}
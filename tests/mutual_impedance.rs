@@ -0,0 +1,19 @@
+use antenna_pattern_generator_lib as apg;
+
+#[test]
+fn half_wave_dipoles() {
+    let frequency = 300e6;
+    let wavelength = apg::SPEED_OF_LIGHT / frequency;
+
+    // induced-EMF values for side-by-side half-wave dipoles (Balanis, Fig. 8.20)
+    for (spacing, r, x) in [(0.5, -12.5, -29.9), (1.0, 4.0, 17.7)] {
+        let z = apg::mutual_impedance_dipoles(spacing * wavelength, 0.5 * wavelength, frequency);
+        assert!((z.re - r).abs() < 0.1, "R at {} wavelengths = {}", spacing, z.re);
+        assert!((z.im - x).abs() < 0.1, "X at {} wavelengths = {}", spacing, z.im);
+    }
+
+    // a very thin wire gives the well known 73 + j42.5 self impedance
+    let z = apg::mutual_impedance_dipoles(1e-5 * wavelength, 0.5 * wavelength, frequency);
+    assert!((z.re - 73.1).abs() < 0.1);
+    assert!((z.im - 42.5).abs() < 0.1);
+}
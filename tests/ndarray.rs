@@ -0,0 +1,84 @@
+#![cfg(feature = "ndarray")]
+
+use antenna_pattern_generator_lib as apg;
+
+use apg::GainIface;
+
+mod support;
+use support::omni;
+
+#[test]
+fn sample_to_ndarray() {
+    let frequency = 1e9;
+    let step = 10.0 * apg::PI / 180.0;
+    let array = apg::ElementArray::uniform_linear_wl(3, 0.7, frequency, omni);
+
+    let samples = array.sample_to_ndarray(frequency, step, 2.0 * step);
+    assert_eq!(samples.dim(), (18, 19));
+    for ((phi_idx, theta_idx), value) in samples.indexed_iter() {
+        let expected = array.get_gain(frequency, theta_idx as f64 * step, phi_idx as f64 * 2.0 * step).unwrap();
+        assert!((value - expected).norm() < 1e-12);
+    }
+}
+
+#[test]
+fn phase_grid() {
+    use apg::ArrayIface;
+
+    let frequency = 1e9;
+    let wavelength = apg::SPEED_OF_LIGHT / frequency;
+    let position = apg::PointBuilder::default().x(1.3 * wavelength).y(-0.6 * wavelength).z(2.1 * wavelength).build().unwrap();
+    let array = apg::ElementArray::new(vec![omni(position.clone())]);
+
+    let thetas: Vec<f64> = (0..=36).map(|idx| idx as f64 * 5.0 * apg::PI / 180.0).collect();
+    let phis: Vec<f64> = (0..72).map(|idx| idx as f64 * 5.0 * apg::PI / 180.0).collect();
+    let wrapped = array.phase_grid(frequency, &thetas, &phis, apg::PhaseWrapping::Wrapped);
+    let unwrapped = array.phase_grid(frequency, &thetas, &phis, apg::PhaseWrapping::Unwrapped);
+    assert_eq!(wrapped.dim(), (72, 37));
+
+    let k = 2.0 * apg::PI / wavelength;
+    let wrap = |phase: f64| (phase + apg::PI).rem_euclid(2.0 * apg::PI) - apg::PI;
+    for ((phi_idx, theta_idx), phase) in wrapped.indexed_iter() {
+        let (theta, phi) = (thetas[theta_idx], phis[phi_idx]);
+        // the positional phase of a single element, exp(+j k r·û)
+        let expected = k * (position.x() * theta.sin() * phi.cos() + position.y() * theta.sin() * phi.sin() + position.z() * theta.cos());
+        assert!(wrap(phase - expected).abs() < 1e-9);
+        assert!(phase.abs() <= apg::PI);
+        assert!(wrap(unwrapped[[phi_idx, theta_idx]] - phase).abs() < 1e-9);
+    }
+
+    // no jumps left along theta
+    for row in unwrapped.rows() {
+        assert!(row.iter().zip(row.iter().skip(1)).all(|(a, b)| (b - a).abs() < apg::PI));
+    }
+    assert!(unwrapped.iter().any(|phase| phase.abs() > apg::PI));
+}
+
+#[test]
+fn gain_uv() {
+    use apg::ArrayIface;
+
+    let frequency = 1e9;
+    let spacing = apg::SPEED_OF_LIGHT / frequency / 2.0;
+    let mut planar = apg::PlanarArray::uniform(4, 5, spacing, spacing, omni);
+    planar.array_mut().steer(frequency, 0.4, 1.1);
+    let array = planar.array();
+
+    let axis: Vec<f64> = (0..=20).map(|idx| -1.0 + idx as f64 * 0.1).collect();
+    let uv = array.gain_uv(frequency, &axis, &axis);
+    assert_eq!(uv.dim(), (21, 21));
+    assert!(uv[[0, 0]].re.is_nan());
+    assert!((uv[[10, 10]] - array.get_gain(frequency, 0.0, 0.0).unwrap()).norm() < 1e-12);
+
+    // theta/phi samples of the front hemisphere land on the same gain in u-v
+    let step = 10.0 * apg::PI / 180.0;
+    let grid = array.sample_sphere(frequency, step, step);
+    for phi_idx in 0..grid.num_phi() {
+        for theta_idx in 0..=9 {
+            let (theta, phi) = (grid.theta(theta_idx), grid.phi(phi_idx));
+            let (u, v) = (theta.sin() * phi.cos(), theta.sin() * phi.sin());
+            let value = array.gain_uv(frequency, &[u], &[v])[[0, 0]];
+            assert!((value - grid.get(phi_idx, theta_idx)).norm() < 1e-9, "{} {}", theta, phi);
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use antenna_pattern_generator_lib as apg;
+
+use apg::GainIface;
+
+mod support;
+use support::{omni, point};
+
+#[test]
+fn singular_at_element() {
+    let frequency = 1e9;
+    let spacing = apg::SPEED_OF_LIGHT / frequency / 2.0;
+    let array = apg::ElementArray::new( vec![omni(point(0.0, 0.0)), omni(point(spacing, 0.0))] );
+
+    let on_element = apg::PointBuilder::default().x(spacing).build().unwrap();
+    assert_eq!(array.get_gain_at_point(frequency, &on_element), Err(apg::GainError::Singular(1)));
+}
+
+#[test]
+fn approaches_far_field() {
+    let frequency = 1e9;
+    let spacing = apg::SPEED_OF_LIGHT / frequency / 2.0;
+    let array = apg::ElementArray::new( vec![omni(point(0.0, 0.0)), omni(point(spacing, 0.0))] );
+
+    // far away along z the 1/r-scaled near field magnitude matches the far field
+    let distance = 1e4;
+    let far = apg::PointBuilder::default().x(spacing / 2.0).z(distance).build().unwrap();
+    let near_field = array.get_gain_at_point(frequency, &far).unwrap();
+    let far_field = array.get_gain(frequency, 0.0, 0.0).unwrap();
+    assert!((near_field.norm() * distance - far_field.norm()).abs() < 1e-6);
+}
+
+#[test]
+fn scan_plane() {
+    let frequency = 1e9;
+    let wavelength = apg::SPEED_OF_LIGHT / frequency;
+    let spacing = wavelength / 2.0;
+    let array = apg::ElementArray::new( vec![omni(point(-spacing / 2.0, 0.0)), omni(point(spacing / 2.0, 0.0))] );
+
+    let distance = 20.0 * wavelength;
+    let plane = array.near_field_plane(frequency, distance, (8.0 * wavelength, 4.0 * wavelength), wavelength / 4.0).unwrap();
+    assert_eq!(plane.x().len(), 33);
+    assert_eq!(plane.y().len(), 17);
+    let (center_y, center_x) = (8, 16);
+    assert!(plane.x()[center_x].abs() < 1e-12 && plane.y()[center_y].abs() < 1e-12);
+
+    // the center of the plane is on boresight, where the far field peaks
+    let center = plane.get(center_y, center_x);
+    let far_field = array.get_gain(frequency, 0.0, 0.0).unwrap();
+    assert!((center.norm() * distance - far_field.norm()).abs() < 0.01 * far_field.norm());
+    let peak = plane.values().iter().flatten().map(|value| value.norm()).fold(0.0, f64::max);
+    assert_eq!(peak, center.norm());
+
+    // the phase falls away from the center like a spherical wave from the array
+    let k = 2.0 * apg::PI / wavelength;
+    for (y_idx, x_idx) in [(center_y, 0), (center_y, 32), (0, center_x), (16, 32)] {
+        let (x, y) = (plane.x()[x_idx], plane.y()[y_idx]);
+        let expected = -k * ((x * x + y * y + distance * distance).sqrt() - distance);
+        let actual = (plane.get(y_idx, x_idx) / center).arg();
+        let error = (actual - expected + apg::PI).rem_euclid(2.0 * apg::PI) - apg::PI;
+        assert!(error.abs() < 0.05, "({}, {}): {} vs {}", x, y, actual, expected);
+    }
+
+    // a plane through the elements hits them
+    assert!(array.near_field_plane(frequency, 0.0, (wavelength, 0.0), wavelength / 4.0).is_err());
+}
@@ -0,0 +1,27 @@
+//! Runs with and without the `io` feature:
+//! `cargo test --no-default-features --test no_io`
+
+use antenna_pattern_generator_lib as apg;
+
+use apg::GainIface;
+
+#[test]
+fn core_gain() {
+    let frequency = 1e9;
+    let spacing = apg::SPEED_OF_LIGHT / frequency / 2.0;
+    let array = apg::ElementArray::uniform_linear(4, spacing, |position| {
+        Box::new(apg::OmniElementBuilder::default().position(position).gain(1.0).build().unwrap())
+    });
+    assert!((array.get_gain(frequency, 0.0, 0.0).unwrap().norm() - 4.0).abs() < 1e-9);
+}
+
+#[test]
+fn parse_in_memory_data() {
+    // pattern files can still be loaded without the filesystem
+    let contents = include_str!("data/x_pol_cst.txt");
+    let element = apg::DataElement::from_cst_str(contents, apg::Point::default()).unwrap();
+    assert!((element.get_gain(1e9, 0.0, 0.0).unwrap().re - 1.0).abs() < 1e-5);
+
+    let contents = include_str!("data/x_pol.cut");
+    assert!(apg::DataElement::from_grasp_cut_str(contents).is_ok());
+}
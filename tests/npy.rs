@@ -0,0 +1,33 @@
+#![cfg(feature = "npy")]
+
+use antenna_pattern_generator_lib as apg;
+
+use apg::GainIface;
+use ndarray::Array2;
+use num::complex::Complex;
+
+#[test]
+fn npy_round_trip() {
+    let frequency = 1e9;
+    let spacing = apg::SPEED_OF_LIGHT / frequency / 2.0;
+    let array = apg::ElementArray::new( (0..4)
+        .map(|n| Box::new(apg::OmniElementBuilder::default()
+            .position(apg::PointBuilder::default().x(n as f64 * spacing).build().unwrap())
+            .gain(1.0)
+            .build()
+            .unwrap()) as Box<dyn apg::ElementIface>)
+        .collect() );
+
+    let step = 5.0 * apg::PI / 180.0;
+    let grid = array.sample_sphere(frequency, step, step);
+    let data: Array2<Complex<f64>> = Array2::from_shape_fn((grid.num_phi(), grid.num_theta()), |(phi_idx, theta_idx)| {
+        grid.get(phi_idx, theta_idx)
+    });
+
+    std::fs::create_dir_all("tests/output").unwrap();
+    let path = std::path::Path::new("tests/output/four_element.npy");
+    apg::io::write_pattern_npy(&data, path).unwrap();
+
+    let read: Array2<Complex<f64>> = ndarray_npy::read_npy(path).unwrap();
+    assert_eq!(read, data);
+}
@@ -0,0 +1,32 @@
+use antenna_pattern_generator_lib as apg;
+
+use apg::ElementIface;
+
+#[test]
+fn read_nsi_export() {
+    // x-polarized source with a cos(theta) pattern, cut at phi = 0 and 90 deg
+    // from theta = -180 to 180 deg in 15 deg steps
+    let element = apg::DataElement::from_nsi("tests/data/x_pol_nsi.txt").unwrap();
+    let deg = apg::PI / 180.0;
+
+    assert_eq!(element.position(), &apg::Point::default());
+    assert!((element.data().theta_step() - 15.0 * deg).abs() < 1e-9);
+    assert!((element.data().phi_step() - 90.0 * deg).abs() < 1e-9);
+
+    for phi in [0.0, 90.0, 180.0, 270.0] {
+        for theta in [0.0, 30.0, 45.0, 120.0, 180.0] {
+            let (theta, phi): (f64, f64) = (theta * deg, phi * deg);
+            let co = element.pattern(1e9, theta, phi).unwrap();
+            let cross = element.cross().unwrap().interpolate(theta, phi);
+            assert!((co.re - theta.cos()).abs() < 1e-5, "co at ({}, {}) = {}", theta, phi, co);
+            assert!(co.im.abs() < 1e-5);
+            assert!(cross.norm() < 1e-5);
+        }
+    }
+}
+
+#[test]
+fn rejects_non_finite_angles() {
+    let contents = "Theta Phi Co Phase Cross Phase\n0.0 0.0 0.0 0.0 -240.0 0.0\nnan 0.0 0.0 0.0 -240.0 0.0\n";
+    assert!(matches!(apg::DataElement::from_nsi_str(contents), Err(apg::DataFileError::Parse { line: 3, .. })));
+}
@@ -0,0 +1,38 @@
+use antenna_pattern_generator_lib as apg;
+
+use apg::ElementIface;
+
+fn patch(ground: apg::GroundPlane) -> apg::PatchElement {
+    apg::PatchElementBuilder::default()
+        .position(apg::Point::default())
+        .length(0.015)
+        .width(0.012)
+        .ground(ground)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn back_hemisphere() {
+    let frequency = 10e9;
+    let deg = apg::PI / 180.0;
+    let ideal = patch(apg::GroundPlane::Ideal);
+    let finite = patch(apg::GroundPlane::Finite { back_level_db: -20.0 });
+
+    let boresight = ideal.pattern(frequency, 0.0, 0.0).unwrap().norm();
+    assert!((boresight - 1.0).abs() < 1e-12);
+
+    for theta in [95.0, 120.0, 150.0, 180.0] {
+        for phi in [0.0, 45.0, 90.0, 200.0] {
+            assert_eq!(ideal.pattern(frequency, theta * deg, phi * deg).unwrap().norm(), 0.0);
+        }
+    }
+
+    // front hemisphere untouched, back lobe at the requested level
+    for theta in [0.0, 30.0, 60.0, 89.0] {
+        let (theta, phi) = (theta * deg, 30.0 * deg);
+        assert_eq!(ideal.pattern(frequency, theta, phi), finite.pattern(frequency, theta, phi));
+    }
+    let back = finite.pattern(frequency, apg::PI, 0.0).unwrap().norm();
+    assert!((20.0 * back.log10() + 20.0).abs() < 1e-9);
+}
@@ -0,0 +1,44 @@
+use antenna_pattern_generator_lib as apg;
+
+fn patch_array(frequency: f64) -> apg::ElementArray {
+    let spacing = apg::SPEED_OF_LIGHT / frequency / 2.0;
+    apg::ElementArray::uniform_linear(8, spacing, |position| {
+        Box::new(apg::PatchElementBuilder::default()
+            .position(position)
+            .length(0.4 * spacing)
+            .width(0.5 * spacing)
+            .build()
+            .unwrap())
+    })
+}
+
+#[test]
+fn overlap() {
+    let frequency = 1e9;
+    let step = 2.0 * apg::PI / 180.0;
+    let a = patch_array(frequency);
+    let b = patch_array(frequency);
+
+    let same = apg::pattern_coupling(&a, &b, frequency, step, step);
+    assert!((same - 1.0).abs() < 1e-9);
+
+    // steered far apart the beams hardly overlap
+    let mut left = patch_array(frequency);
+    let mut right = patch_array(frequency);
+    left.steer(frequency, 45_f64.to_radians(), apg::PI);
+    right.steer(frequency, 45_f64.to_radians(), 0.0);
+    let apart = apg::pattern_coupling(&left, &right, frequency, step, step);
+    assert!(apart < 0.25, "{}", apart);
+
+    // and pointed opposite ways (one board flipped to face -z) not at all
+    let back = apg::DataElement::new(
+        apg::PatternGrid::from_fn(step, step, |theta, _| num::complex::Complex::new((-theta.cos()).max(0.0), 0.0)),
+        apg::Point::default(),
+    );
+    let front = apg::DataElement::new(
+        apg::PatternGrid::from_fn(step, step, |theta, _| num::complex::Complex::new(theta.cos().max(0.0), 0.0)),
+        apg::Point::default(),
+    );
+    assert!(apg::pattern_coupling(&front, &back, frequency, step, step) < 1e-9);
+    assert!(same > apart);
+}
@@ -0,0 +1,39 @@
+use antenna_pattern_generator_lib as apg;
+
+use apg::GainIface;
+
+mod support;
+use support::omni;
+
+#[test]
+fn omni_reads_zero_dbi() {
+    let frequency = 1e9;
+    let step = 1.0 * apg::PI / 180.0;
+    let element = omni(apg::Point::default());
+
+    let power = element.sample_sphere(frequency, step, step).radiated_power();
+    let cut = element.sample_cut(frequency, 0.0, step);
+    assert_eq!(cut.angles().len(), 361);
+    for dbi in cut.normalized_to_isotropic(power) {
+        assert!(dbi.abs() < 0.01, "{}", dbi);
+    }
+}
+
+#[test]
+fn array_cut_in_dbi() {
+    let frequency = 1e9;
+    let step = 1.0 * apg::PI / 180.0;
+    let array = apg::ElementArray::uniform_linear_wl(8, 0.5, frequency, omni);
+
+    // the peak of the cut through the fan beam is about 10*log10(8) dBi
+    let power = array.sample_sphere(frequency, step, step).radiated_power();
+    let cut = array.sample_cut(frequency, 0.0, step);
+    let peak = cut.normalized_to_isotropic(power).into_iter().fold(f64::NEG_INFINITY, f64::max);
+    assert!((peak - 10.0 * 8_f64.log10()).abs() < 0.1, "{}", peak);
+
+    // normalizing to the cut's own peak only shifts it
+    let own = cut.normalize_db();
+    for (a, b) in own.iter().zip(cut.normalized_to_isotropic(power)) {
+        assert!((a - (b - peak)).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,41 @@
+use antenna_pattern_generator_lib as apg;
+
+use apg::GainIface;
+
+mod support;
+use support::omni;
+
+#[test]
+fn contour_matches_hpbw() {
+    let frequency = 1e9;
+    let n = 16;
+    let array = apg::ElementArray::uniform_linear_wl(n, 0.5, frequency, omni);
+    let step = 0.25 * apg::PI / 180.0;
+    let db = array.sample_sphere(frequency, step, 5.0 * apg::PI / 180.0).normalize_db(-60.0);
+
+    // broadside beam of a line along x is a fan; the peak sample is in the
+    // phi = 90 deg plane or at theta = 0, both broadside
+    let (theta, phi) = db.peak_angle();
+    assert!((theta.sin() * phi.cos()).abs() < 1e-9);
+    assert!(db.values().iter().flatten().all(|value| *value <= 0.0 && *value >= db.floor_db()));
+    assert_eq!(db[(0, 0)], 0.0);
+
+    // half power point of sin(N*x)/(N*sin(x)), x = PI/2 * sin(theta), by bisection
+    let array_factor = |theta: f64| {
+        let x = apg::PI / 2.0 * theta.sin();
+        ((n as f64 * x).sin() / (n as f64 * x.sin())).powi(2)
+    };
+    let (mut low, mut high) = (1e-6, 0.1);
+    for _ in 0..60 {
+        let mid = 0.5 * (low + high);
+        if array_factor(mid) > 0.5 { low = mid } else { high = mid }
+    }
+    let hpbw = 2.0 * low;
+
+    let half_beam = db.contour(-10.0 * 2_f64.log10())
+        .into_iter()
+        .filter(|(_, phi)| *phi == 0.0)
+        .map(|(theta, _)| theta)
+        .fold(f64::INFINITY, f64::min);
+    assert!((2.0 * half_beam - hpbw).abs() < 0.05 * hpbw, "{} vs {}", 2.0 * half_beam, hpbw);
+}
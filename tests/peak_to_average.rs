@@ -0,0 +1,27 @@
+use antenna_pattern_generator_lib as apg;
+
+use apg::ArrayIface;
+
+mod support;
+use support::omni;
+
+#[test]
+fn directive_array_is_peakier() {
+    let frequency = 10e9;
+    let step = 1.0 * apg::PI / 180.0;
+
+    let single = apg::ElementArray::new(vec![omni(apg::Point::default())]);
+    let omni_ratio = single.peak_to_average(frequency, step, step);
+    assert!(omni_ratio.abs() < 0.01, "{}", omni_ratio);
+
+    // a half wavelength line of N omnis has a directivity of N
+    let line = apg::ElementArray::uniform_linear_wl(8, 0.5, frequency, omni);
+    let line_ratio = line.peak_to_average(frequency, step, step);
+    assert!((line_ratio - 10.0 * 8_f64.log10()).abs() < 0.1, "{}", line_ratio);
+
+    let hex = apg::HexArrayBuilder::default()
+        .rings(3)
+        .spacing(0.5 * apg::SPEED_OF_LIGHT / frequency)
+        .build(omni);
+    assert!(hex.peak_to_average(frequency, step, step) > line_ratio);
+}
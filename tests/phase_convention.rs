@@ -0,0 +1,45 @@
+// The phase convention is process-wide, so everything that changes it lives
+// in this one test.
+
+use antenna_pattern_generator_lib as apg;
+
+use apg::GainIface;
+
+mod support;
+use support::omni;
+
+/// Theta of the largest gain in the phi = 0 half of the XZ plane (radians)
+fn peak_theta(array: &apg::ElementArray, frequency: f64) -> f64 {
+    (0..=900)
+        .map(|idx| idx as f64 * 0.1 * apg::PI / 180.0)
+        .max_by(|a, b| {
+            let ga = array.get_gain(frequency, *a, 0.0).unwrap().norm();
+            let gb = array.get_gain(frequency, *b, 0.0).unwrap().norm();
+            ga.partial_cmp(&gb).unwrap()
+        })
+        .unwrap()
+}
+
+#[test]
+fn steering_under_both_conventions() {
+    let frequency = 1e9;
+    let theta0 = 20.0 * apg::PI / 180.0;
+    assert_eq!(apg::phase_convention(), apg::PhaseConvention::Positive);
+
+    let mut gains = Vec::new();
+    for convention in [apg::PhaseConvention::Positive, apg::PhaseConvention::Negative] {
+        apg::set_phase_convention(convention);
+        assert_eq!(apg::phase_convention(), convention);
+
+        let mut array = apg::ElementArray::uniform_linear_wl(16, 0.5, frequency, omni);
+        array.steer(frequency, theta0, 0.0);
+        assert!((peak_theta(&array, frequency) - theta0).abs() < 0.2 * apg::PI / 180.0);
+
+        let unsteered = apg::ElementArray::uniform_linear_wl(2, 0.3, frequency, omni);
+        gains.push(unsteered.get_gain(frequency, 1.0, 0.5).unwrap());
+    }
+    apg::set_phase_convention(apg::PhaseConvention::Positive);
+
+    // the same physical array, with the phase conjugated
+    assert!((gains[0] - gains[1].conj()).norm() < 1e-12);
+}
@@ -0,0 +1,46 @@
+use antenna_pattern_generator_lib as apg;
+
+use apg::GainIface;
+
+mod support;
+use support::omni;
+
+/// Highest gain (dB relative to the peak) along the XZ cut beyond `main_beam_deg` from boresight
+fn peak_sidelobe_db(array: &apg::ElementArray, frequency: f64, main_beam_deg: f64) -> f64 {
+    let cut: Vec<(f64, f64)> = (-900..=900)
+        .map(|idx| {
+            let degrees = idx as f64 * 0.1;
+            let phi = if degrees < 0.0 { apg::PI } else { 0.0 };
+            (degrees, array.get_gain(frequency, degrees.abs().to_radians(), phi).unwrap().norm())
+        })
+        .collect();
+    let peak = cut.iter().map(|(_, gain)| *gain).fold(0.0, f64::max);
+    let sidelobe = cut.iter().filter(|(degrees, _)| degrees.abs() > main_beam_deg).map(|(_, gain)| *gain).fold(0.0, f64::max);
+    20.0 * (sidelobe / peak).log10()
+}
+
+#[test]
+fn phase_only_taper_lowers_sidelobes() {
+    let frequency = 1e9;
+    let taper = apg::taylor_linear(16, 4, -30.0);
+
+    let uniform = apg::ElementArray::uniform_linear_wl(16, 0.5, frequency, omni);
+    // the -30 dB taper's main beam reaches its first null at about 11 degrees
+    let uniform_sll = peak_sidelobe_db(&uniform, frequency, 11.0);
+
+    let mut tapered = apg::ElementArray::uniform_linear_wl(16, 0.5, frequency, omni);
+    for (n, t) in tapered.elements_mut().iter_mut().zip(&taper) {
+        let weight = n.weight() * *t;
+        n.set_weight(weight);
+    }
+    let tapered_sll = peak_sidelobe_db(&tapered, frequency, 11.0);
+
+    let mut array = apg::ElementArray::uniform_linear_wl(16, 0.5, frequency, omni);
+    array.phase_only_taper_approx(&taper, frequency, 30);
+    let approx_sll = peak_sidelobe_db(&array, frequency, 11.0);
+
+    // phase only: every weight keeps its amplitude
+    assert!(array.elements().iter().all(|n| (n.weight().norm() - 1.0).abs() < 1e-12));
+    assert!(approx_sll < uniform_sll - 0.5, "{} vs {}", approx_sll, uniform_sll);
+    assert!(approx_sll > tapered_sll, "{} vs {}", approx_sll, tapered_sll);
+}
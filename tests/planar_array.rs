@@ -0,0 +1,58 @@
+use antenna_pattern_generator_lib as apg;
+
+use apg::GainIface;
+
+mod support;
+use support::{omni, peak_sidelobe_db};
+
+#[test]
+fn separable_taper() {
+    let frequency = 1e9;
+    let spacing = apg::SPEED_OF_LIGHT / frequency / 2.0;
+    let mut array = apg::PlanarArray::uniform(12, 16, spacing, spacing, omni);
+    assert_eq!(array.array().elements().len(), 12 * 16);
+
+    array.apply_separable_taper(&apg::taylor_linear(16, 5, -30.0), &[1.0; 12]);
+
+    // Taylor along the rows (x), uniform along the columns (y)
+    let row_plane = peak_sidelobe_db(&array, frequency, 0.0);
+    let col_plane = peak_sidelobe_db(&array, frequency, apg::PI / 2.0);
+    assert!(row_plane < -29.0 && row_plane > -31.0, "{}", row_plane);
+    assert!((col_plane + 13.3).abs() < 0.5, "{}", col_plane);
+}
+
+#[test]
+fn taylor_linear_weights() {
+    let taper = apg::taylor_linear(10, 4, -25.0);
+    assert_eq!(taper.len(), 10);
+    assert!((taper[4] - 1.0).abs() < 1e-12);
+    for idx in 0..5 {
+        assert!((taper[idx] - taper[9 - idx]).abs() < 1e-12);
+    }
+    assert!(taper[0] < taper[4]);
+}
+
+#[test]
+fn project_to_line() {
+    let frequency = 1e9;
+    let spacing = apg::SPEED_OF_LIGHT / frequency / 2.0;
+    let mut planar = apg::PlanarArray::uniform(4, 5, spacing, spacing, omni);
+    planar.apply_separable_taper(&apg::taylor_linear(5, 3, -25.0), &apg::taylor_linear(4, 3, -25.0));
+
+    // the XZ plane has one element per column, the diagonal one per anti-diagonal
+    for (phi_cut, count) in [(0.0, 5), (apg::PI / 4.0, 8), (0.3, 20)] {
+        let mut steered = planar.clone();
+        steered.array_mut().steer(frequency, 0.4, phi_cut);
+        let linear = steered.project_to_line(phi_cut);
+        assert_eq!(linear.elements().len(), count);
+
+        for idx in 0..=36 {
+            let theta = idx as f64 * 5.0 * apg::PI / 180.0;
+            for phi in [phi_cut, phi_cut + apg::PI] {
+                let expected = steered.get_gain(frequency, theta, phi).unwrap();
+                let gain = linear.get_gain(frequency, theta, phi).unwrap();
+                assert!((gain - expected).norm() < 1e-9, "phi_cut {} theta {} phi {}", phi_cut, theta, phi);
+            }
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use antenna_pattern_generator_lib as apg;
+
+use apg::ArrayIface;
+use num::complex::Complex;
+
+/// Array of cos(theta) elements with cross-polar tables `cross_level` below co-pol
+fn array(frequency: f64, cross_level: f64) -> apg::ElementArray {
+    let step = 2.0 * apg::PI / 180.0;
+    let co = apg::PatternGrid::from_fn(step, step, |theta, _| Complex::new(theta.cos().max(0.0), 0.0));
+    let cross = apg::PatternGrid::from_fn(step, step, |theta, _| Complex::new(cross_level * theta.cos().max(0.0), 0.0));
+    let spacing = apg::SPEED_OF_LIGHT / frequency / 2.0;
+    apg::ElementArray::uniform_linear(8, spacing, |position| {
+        Box::new(apg::DataElement::new_polarized(co.clone(), cross.clone(), position))
+    })
+}
+
+#[test]
+fn main_beam_xpd() {
+    let frequency = 1e9;
+    let region: Vec<(f64, f64)> = (0..10)
+        .flat_map(|t| (0..8).map(move |p| ((t as f64 * 2.0).to_radians(), (p as f64 * 45.0).to_radians())))
+        .collect();
+
+    let ideal = array(frequency, 0.0);
+    assert!(ideal.average_xpd(frequency, &region) > 100.0);
+
+    // -30 dB cross-pol in every element comes straight through
+    let leaky = array(frequency, 10_f64.powf(-30.0 / 20.0));
+    assert!((leaky.average_xpd(frequency, &region) - 30.0).abs() < 1e-6);
+
+    // the field is the sum of the element fields
+    let field = leaky.get_field(frequency, 0.1, 0.3).unwrap();
+    let (co, _) = field.ludwig3(0.3);
+    assert!((co - apg::GainIface::get_gain(&leaky, frequency, 0.1, 0.3).unwrap()).norm() < 1e-12);
+}
@@ -0,0 +1,57 @@
+use antenna_pattern_generator_lib as apg;
+
+use num::complex::Complex;
+
+mod support;
+use support::peak_sidelobe_db;
+
+fn taylor_array(frequency: f64) -> apg::ElementArray {
+    let spacing = apg::SPEED_OF_LIGHT / frequency / 2.0;
+    let mut array = apg::ElementArray::uniform_linear(16, spacing, |position| {
+        Box::new(apg::OmniElementBuilder::default().position(position).gain(1.0).build().unwrap())
+    });
+    for (n, weight) in array.elements_mut().iter_mut().zip(apg::taylor_linear(16, 5, -35.0)) {
+        n.set_weight(Complex::new(weight, 0.0));
+    }
+    array
+}
+
+#[test]
+fn repeatable() {
+    let frequency = 1e9;
+    let mut a = taylor_array(frequency);
+    let mut b = taylor_array(frequency);
+    a.perturb_positions(0.001, 7);
+    b.perturb_positions(0.001, 7);
+    let positions = |array: &apg::ElementArray| -> Vec<apg::Point> {
+        array.elements().iter().map(|n| n.position().clone()).collect()
+    };
+    assert_eq!(positions(&a), positions(&b));
+    assert_ne!(positions(&a), positions(&taylor_array(frequency)));
+
+    b = taylor_array(frequency);
+    b.perturb_positions(0.001, 8);
+    assert_ne!(positions(&a), positions(&b));
+}
+
+#[test]
+fn jitter_raises_sidelobes() {
+    let frequency = 1e9;
+    let wavelength = apg::SPEED_OF_LIGHT / frequency;
+    let nominal = peak_sidelobe_db(&taylor_array(frequency), frequency, 0.0);
+
+    let mean_sidelobe = |std_dev: f64| -> f64 {
+        (0..10)
+            .map(|seed| {
+                let mut array = taylor_array(frequency);
+                array.perturb_positions(std_dev, seed);
+                peak_sidelobe_db(&array, frequency, 0.0)
+            })
+            .sum::<f64>() / 10.0
+    };
+    let small = mean_sidelobe(0.005 * wavelength);
+    let large = mean_sidelobe(0.1 * wavelength);
+    assert!(nominal < -34.0);
+    assert!(small < large, "{} vs {}", small, large);
+    assert!(large > nominal + 5.0, "{} vs {}", large, nominal);
+}
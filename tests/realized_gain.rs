@@ -0,0 +1,22 @@
+use antenna_pattern_generator_lib as apg;
+
+use apg::GainIface;
+
+mod support;
+use support::omni;
+
+#[test]
+fn feed_loss() {
+    let frequency = 1e9;
+    let mut array = apg::ElementArray::uniform_linear_wl(8, 0.5, frequency, omni);
+    assert_eq!(array.feed_loss_db(), 0.0);
+    array.set_feed_loss_db(2.0);
+
+    // broadside peak of a line along x
+    let (theta, phi) = (apg::PI / 2.0, apg::PI / 2.0);
+    let lossless = array.get_gain(frequency, theta, phi).unwrap();
+    let realized = array.realized_gain(frequency, theta, phi).unwrap();
+    assert!((lossless.norm() - 8.0).abs() < 1e-9);
+    assert!((20.0 * (realized.norm() / lossless.norm()).log10() + 2.0).abs() < 1e-12);
+    assert!((realized.arg() - lossless.arg()).abs() < 1e-12);
+}
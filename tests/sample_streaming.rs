@@ -0,0 +1,26 @@
+use antenna_pattern_generator_lib as apg;
+
+use apg::GainIface;
+
+mod support;
+use support::omni;
+
+#[test]
+fn streamed_rows_match_grid() {
+    let frequency = 1e9;
+    let step = 5.0 * apg::PI / 180.0;
+    let mut array = apg::ElementArray::uniform_linear_wl(6, 0.5, frequency, omni);
+    array.steer(frequency, 0.3, 1.0);
+
+    let grid = array.sample_sphere(frequency, step, step);
+    let mut streamed = Vec::new();
+    let mut indices = Vec::new();
+    array.sample_sphere_streaming(frequency, step, step, |phi_idx, row| {
+        indices.push(phi_idx);
+        streamed.extend_from_slice(row);
+    });
+
+    assert_eq!(indices, (0..grid.num_phi()).collect::<Vec<usize>>());
+    let expected: Vec<_> = grid.values().iter().flatten().cloned().collect();
+    assert_eq!(streamed, expected);
+}
@@ -0,0 +1,36 @@
+#![cfg(feature = "simd")]
+
+use antenna_pattern_generator_lib as apg;
+
+use apg::GainIface;
+use num::complex::Complex;
+
+#[test]
+fn matches_scalar_sum() {
+    let frequency = 10e9;
+    let wavelength = apg::SPEED_OF_LIGHT / frequency;
+    // an odd count leaves a partly filled last chunk
+    let elements = (0..37)
+        .map(|n| Box::new(apg::OmniElementBuilder::default()
+            .position(apg::PointBuilder::default()
+                .x((n % 6) as f64 * 0.6 * wavelength)
+                .y((n / 6) as f64 * 0.6 * wavelength)
+                .z(0.01 * n as f64)
+                .build()
+                .unwrap())
+            .gain(1.0)
+            .build()
+            .unwrap()) as Box<dyn apg::ElementIface>)
+        .collect();
+    let mut array = apg::ElementArray::new(elements);
+    array.steer(frequency, 0.5, 2.0);
+
+    for (theta, phi) in [(0.0, 0.0), (0.5, 2.0), (1.4, 4.0), (2.9, 0.3)] {
+        let scalar: Complex<f64> = array.elements()
+            .iter()
+            .map(|n| n.get_gain(frequency, theta, phi).unwrap())
+            .sum();
+        let gain = array.get_gain(frequency, theta, phi).unwrap();
+        assert!((gain - scalar).norm() < 1e-9 * scalar.norm().max(1.0));
+    }
+}
@@ -0,0 +1,40 @@
+use antenna_pattern_generator_lib as apg;
+
+mod support;
+use support::omni;
+
+#[test]
+fn uniform_aps() {
+    let frequency = 1e9;
+
+    // isotropic scattering decorrelates elements by sinc(k*d)
+    let sparse = apg::ElementArray::uniform_linear_wl(3, 5.0, frequency, omni);
+    let matrix = sparse.spatial_correlation_matrix(frequency, |_, _| 1.0);
+    assert_eq!(matrix.len(), 3);
+    for (m, row) in matrix.iter().enumerate() {
+        for (n, value) in row.iter().enumerate() {
+            assert!((value - matrix[n][m].conj()).norm() < 1e-12);
+            if m == n {
+                assert!((value.re - 1.0).abs() < 1e-9);
+            } else {
+                assert!(value.norm() < 0.02);
+            }
+        }
+    }
+
+    let dense = apg::ElementArray::uniform_linear_wl(2, 0.05, frequency, omni);
+    let matrix = dense.spatial_correlation_matrix(frequency, |_, _| 1.0);
+    assert!(matrix[0][1].norm() > 0.95);
+}
+
+#[test]
+fn narrow_aps() {
+    let frequency = 1e9;
+    let array = apg::ElementArray::uniform_linear_wl(2, 5.0, frequency, omni);
+
+    // power from a single direction keeps the elements fully correlated
+    let matrix = array.spatial_correlation_matrix(frequency, |theta, phi| {
+        if (theta - apg::PI / 4.0).abs() < 1e-6 && phi == 0.0 { 1.0 } else { 0.0 }
+    });
+    assert!((matrix[0][1].norm() - 1.0).abs() < 1e-9);
+}
@@ -0,0 +1,345 @@
+use antenna_pattern_generator_lib as apg;
+
+use num::complex::Complex;
+
+mod support;
+use support::linear_array;
+
+#[test]
+fn quantization_pointing_error() {
+    let frequency = 1e9;
+    let spacing = apg::SPEED_OF_LIGHT / frequency / 2.0;
+    let deg = apg::PI / 180.0;
+    let steer_angles = [7.0, 13.0, 22.0, 31.0, 37.0];
+
+    let mean_error = |bits: u32| {
+        steer_angles.iter().map(|angle| {
+            let mut array = linear_array(8, spacing);
+            array.steer(frequency, angle * deg, 0.0);
+            array.quantize_phases(bits);
+            array.pointing_error(frequency, (angle * deg, 0.0)).0.abs()
+        }).sum::<f64>() / steer_angles.len() as f64
+    };
+
+    // an unquantized beam lands where it was steered
+    let mut array = linear_array(8, spacing);
+    array.steer(frequency, 20.0 * deg, 0.0);
+    assert!(array.pointing_error(frequency, (20.0 * deg, 0.0)).0.abs() < 1e-6);
+
+    let coarse = mean_error(2);
+    let fine = mean_error(6);
+    assert!(coarse > fine);
+    assert!(fine < 0.1 * deg);
+}
+
+#[test]
+fn interpolated_steering() {
+    let frequency = 1e9;
+    let spacing = apg::SPEED_OF_LIGHT / frequency / 2.0;
+    let deg = apg::PI / 180.0;
+
+    let mut elements: Vec<Box<dyn apg::ElementIface>> = Vec::new();
+    for row in 0..8 {
+        for col in 0..8 {
+            elements.push(Box::new(apg::OmniElementBuilder::default()
+                .position(apg::PointBuilder::default().x(col as f64 * spacing).y(row as f64 * spacing).build().unwrap())
+                .gain(1.0)
+                .build()
+                .unwrap()));
+        }
+    }
+    let mut array = apg::ElementArray::new( elements );
+    // 8x8 at half wavelength has a beamwidth of about 0.22 in u
+    let table = apg::ScanTable::new(&array, frequency, 0.04);
+    assert_eq!(table.num_elements(), 64);
+
+    for (theta0, phi0) in [(23.3, 41.7), (5.1, 300.2), (47.9, 181.0)] {
+        array.steer_interpolated(&table, theta0 * deg, phi0 * deg).unwrap();
+        let (dtheta, dphi) = array.pointing_error(frequency, (theta0 * deg, phi0 * deg));
+        let offset = (dtheta.powi(2) + (dphi * (theta0 * deg).sin()).powi(2)).sqrt();
+        assert!(offset < 0.1 * deg, "{}", offset / deg);
+
+        // amplitudes are kept
+        assert!(array.elements().iter().all(|n| (n.weight().norm() - 1.0).abs() < 1e-12));
+    }
+
+    // on a grid point the weights are exactly those of steer
+    let mut exact = array.clone();
+    exact.steer(frequency, (0.4_f64).asin(), 0.0);
+    array.steer_interpolated(&table, (0.4_f64).asin(), 0.0).unwrap();
+    for (a, b) in array.elements().iter().zip(exact.elements()) {
+        assert!((a.weight() - b.weight()).norm() < 1e-9);
+    }
+
+    assert_eq!(array.steer_interpolated(&table, 100.0 * deg, 0.0), Err(apg::ScanTableError::OutsideTable));
+}
+
+#[test]
+fn stale_scan_table() {
+    let frequency = 1e9;
+    let spacing = apg::SPEED_OF_LIGHT / frequency / 2.0;
+    let mut array = linear_array(8, spacing);
+    let table = apg::ScanTable::new(&array, frequency, 0.05);
+    let weights: Vec<Complex<f64>> = array.elements().iter().map(|n| n.weight()).collect();
+
+    // moving an element after the table was built
+    let mut moved = array.clone();
+    moved.elements_mut()[3].set_position(apg::PointBuilder::default().x(0.5).build().unwrap());
+    assert_eq!(moved.steer_interpolated(&table, 0.3, 0.0), Err(apg::ScanTableError::ElementMoved(3)));
+
+    // a table built for a different array
+    let mut other = linear_array(4, spacing);
+    assert_eq!(other.steer_interpolated(&table, 0.3, 0.0), Err(apg::ScanTableError::ElementCount(8)));
+
+    // nothing was steered
+    assert!(array.elements().iter().zip(&weights).all(|(n, weight)| n.weight() == *weight));
+    array.steer_interpolated(&table, 0.3, 0.0).unwrap();
+}
+
+#[test]
+fn aperture_fill_bandwidth() {
+    let deg = apg::PI / 180.0;
+    let small = linear_array(8, 0.015);
+    let large = linear_array(32, 0.015);
+
+    // 8 elements span 7 * 15 mm
+    let expected = apg::SPEED_OF_LIGHT / (7.0 * 0.015 * (30.0 * deg).sin());
+    assert!((small.aperture_fill_bandwidth(30.0 * deg, 0.0) - expected).abs() < 1e-6 * expected);
+
+    assert!(large.aperture_fill_bandwidth(30.0 * deg, 0.0) < small.aperture_fill_bandwidth(30.0 * deg, 0.0));
+    assert!(small.aperture_fill_bandwidth(60.0 * deg, 0.0) < small.aperture_fill_bandwidth(30.0 * deg, 0.0));
+    assert!(small.aperture_fill_bandwidth(0.0, 0.0).is_infinite());
+}
+
+fn cos_q_array(num: usize, spacing: f64, q: i32) -> apg::ElementArray {
+    let step = 2.0 * apg::PI / 180.0;
+    let pattern = apg::PatternGrid::from_fn(step, step, |theta, _| Complex::new(theta.cos().max(0.0).powi(q), 0.0));
+    apg::ElementArray::new( (0..num)
+        .map(|n| Box::new(apg::DataElement::new(
+            pattern.clone(),
+            apg::PointBuilder::default().x(n as f64 * spacing).build().unwrap(),
+        )) as Box<dyn apg::ElementIface>)
+        .collect() )
+}
+
+#[test]
+fn scan_volume() {
+    let frequency = 1e9;
+    let spacing = apg::SPEED_OF_LIGHT / frequency / 2.0;
+
+    let broad = cos_q_array(4, spacing, 1);
+    let directive = cos_q_array(16, spacing, 8);
+
+    // within 3 dB of broadside
+    let broad_volume = broad.scan_volume(frequency, 3.0);
+    let directive_volume = directive.scan_volume(frequency, 3.0);
+    assert!(broad_volume > directive_volume);
+
+    // cos(theta) is 3 dB down at 45 deg: a cone of 2*PI*(1 - cos(45 deg)) steradians
+    let cone = 2.0 * apg::PI * (1.0 - (apg::PI / 4.0).cos());
+    assert!((broad_volume - cone).abs() < 0.05 * cone, "{} vs {}", broad_volume, cone);
+
+    // only broadside itself loses nothing, and everything in front keeps something
+    assert!(broad.scan_volume(frequency, 0.0) < 0.01);
+    let hemisphere = 2.0 * apg::PI;
+    assert!((broad.scan_volume(frequency, 100.0) - hemisphere).abs() < 0.05 * hemisphere);
+}
+
+#[test]
+fn scan_pattern_includes_element_roll_off() {
+    let frequency = 10e9;
+    let wavelength = apg::SPEED_OF_LIGHT / frequency;
+    let step = 1.0 * apg::PI / 180.0;
+    let scan = 50.0 * apg::PI / 180.0;
+
+    let patch = |position: apg::Point| -> Box<dyn apg::ElementIface> {
+        Box::new(apg::PatchElementBuilder::default()
+            .position(position)
+            .length(0.49 * wavelength)
+            .width(0.4 * wavelength)
+            .build()
+            .unwrap())
+    };
+    let omni = |position: apg::Point| -> Box<dyn apg::ElementIface> {
+        Box::new(apg::OmniElementBuilder::default()
+            .position(position)
+            .gain(1.0)
+            .build()
+            .unwrap())
+    };
+    let hex = apg::HexArrayBuilder::default().rings(3).spacing(0.5 * wavelength).clone();
+    let patches = hex.build(patch);
+    let omnis = hex.build(omni);
+
+    // peak power over the average power
+    let directivity = |grid: apg::PatternGrid| {
+        let peak = grid.values().iter().flatten().map(|value| value.norm_sqr()).fold(0.0, f64::max);
+        peak / (grid.radiated_power() / (4.0 * apg::PI))
+    };
+    let loss = |array: &apg::ElementArray| {
+        directivity(array.scan_pattern(frequency, scan, 0.0, step, step))
+            / directivity(array.scan_pattern(frequency, 0.0, 0.0, step, step))
+    };
+
+    // scanned omnis lose directivity from the array factor alone (roughly
+    // cos(scan) for a planar array), patches add their own roll-off on top
+    let omni_loss = loss(&omnis);
+    let patch_loss = loss(&patches);
+    assert!(omni_loss < 0.9 && omni_loss > 0.5, "{}", omni_loss);
+    assert!(patch_loss < omni_loss, "{} vs {}", patch_loss, omni_loss);
+
+    // the array itself isn't steered
+    assert!(patches.elements().iter().all(|n| n.weight() == Complex::new(1.0, 0.0)));
+}
+
+#[test]
+fn bits_for_pointing_budget() {
+    let frequency = 10e9;
+    let wavelength = apg::SPEED_OF_LIGHT / frequency;
+    let patch = |position: apg::Point| -> Box<dyn apg::ElementIface> {
+        Box::new(apg::PatchElementBuilder::default()
+            .position(position)
+            .length(0.49 * wavelength)
+            .width(0.4 * wavelength)
+            .build()
+            .unwrap())
+    };
+    let mut array = apg::HexArrayBuilder::default()
+        .rings(3)
+        .spacing(0.55 * wavelength)
+        .build(patch);
+    array.steer(frequency, 0.37, 0.5);
+
+    let loose = apg::bits_for_pointing_budget(&array, 1.0, -5.0, frequency).unwrap();
+    let tight = apg::bits_for_pointing_budget(&array, 0.02, -5.0, frequency).unwrap();
+    assert!(tight > loose, "{} vs {}", tight, loose);
+
+    // no amount of phase bits gets a uniform array to -40 dB sidelobes
+    assert_eq!(apg::bits_for_pointing_budget(&array, 1.0, -40.0, frequency), None);
+    assert_eq!(array.elements()[1].weight().norm(), 1.0);
+}
+
+#[test]
+fn scan_ripple() {
+    let frequency = 1e9;
+    let spacing = apg::SPEED_OF_LIGHT / frequency / 2.0;
+    let array = cos_q_array(8, spacing, 1);
+
+    let sector = |max_deg: f64| -> Vec<(f64, f64)> {
+        (0..=10).map(|idx| ((idx as f64 * max_deg / 10.0).to_radians(), 0.0)).collect()
+    };
+    let near = array.scan_ripple_db(frequency, &sector(10.0));
+    let wide = array.scan_ripple_db(frequency, &sector(60.0));
+    assert!(near < wide, "{} vs {}", near, wide);
+    // roughly the cos(theta) element roll-off at 60 deg
+    assert!(wide > 3.0 && wide < 9.0, "{}", wide);
+
+    // a single direction has no ripple
+    assert_eq!(array.scan_ripple_db(frequency, &[(0.3, 0.0)]), 0.0);
+}
+
+#[test]
+fn hybrid_beamforming() {
+    let frequency = 1e9;
+    let spacing = apg::SPEED_OF_LIGHT / frequency / 2.0;
+    let array = linear_array(16, spacing);
+    let theta0 = 30_f64.to_radians();
+
+    let mut steered = array.clone();
+    steered.steer(frequency, theta0, 0.0);
+    let weights = apg::Beamformer::from_array(&steered).weights().to_vec();
+    let ones = vec![Complex::new(1.0, 0.0); 16];
+
+    // one element per subarray is element-level digital beamforming
+    let analog: Vec<Vec<Complex<f64>>> = ones.iter().map(|w| vec![*w]).collect();
+    // and one subarray is analog beamforming
+    let single = vec![weights.clone()];
+    for (theta, phi) in [(theta0, 0.0), (0.2, 0.0), (1.0, 2.0)] {
+        let expected = apg::GainIface::get_gain(&steered, frequency, theta, phi).unwrap();
+        assert!((array.hybrid_gain(frequency, theta, phi, &analog, &weights) - expected).norm() < 1e-9);
+        assert!((array.hybrid_gain(frequency, theta, phi, &single, &[Complex::new(1.0, 0.0)]) - expected).norm() < 1e-9);
+    }
+
+    // subarrays of 4 with analog phase shifters: full gain at the scan angle
+    let analog: Vec<Vec<Complex<f64>>> = weights.chunks(4).map(|chunk| chunk.to_vec()).collect();
+    let digital = vec![Complex::new(1.0, 0.0); 4];
+    assert!((array.hybrid_gain(frequency, theta0, 0.0, &analog, &digital).norm() - 16.0).abs() < 1e-9);
+
+    // without them the subarrays stay at broadside and only the digital
+    // weights (taken at the subarray centers) steer: the subarray factor
+    // has a null at 30 deg and a grating lobe takes the beam
+    let analog: Vec<Vec<Complex<f64>>> = ones.chunks(4).map(|chunk| chunk.to_vec()).collect();
+    let digital: Vec<Complex<f64>> = weights.chunks(4).map(|chunk| chunk[0]).collect();
+    assert!(array.hybrid_gain(frequency, theta0, 0.0, &analog, &digital).norm() < 1e-6);
+    assert!((array.hybrid_gain(frequency, 0.0, 0.0, &analog, &digital).norm() - 16.0).abs() < 1e-9);
+}
+
+#[test]
+fn angular_ambiguity() {
+    let frequency = 1e9;
+    let wavelength = apg::SPEED_OF_LIGHT / frequency;
+    let step = apg::PI / 36.0;
+    let largest = |grid: &apg::PatternGrid| {
+        grid.values().iter().flatten().map(|value| value.norm()).fold(0.0, f64::max)
+    };
+
+    // peaks at the target, 30 degrees off broadside
+    let array = linear_array(8, wavelength / 2.0);
+    let ambiguity = array.angular_ambiguity(frequency, 6.0 * step, 0.0, step, step);
+    let at_target = ambiguity.get(0, 6).norm();
+    assert!((at_target - 8.0).abs() < 1e-9);
+    assert!((largest(&ambiguity) - at_target).abs() < 1e-9);
+    // no grating lobe at half wavelength spacing
+    assert!(ambiguity.get(0, 18).norm() < 0.5 * at_target);
+
+    // a wavelength apart, a target at broadside can't be told from one on the horizon
+    let sparse = linear_array(8, wavelength);
+    let ambiguity = sparse.angular_ambiguity(frequency, 0.0, 0.0, step, step);
+    let at_target = ambiguity.get(0, 0).norm();
+    assert!((at_target - 8.0).abs() < 1e-9);
+    assert!((ambiguity.get(0, 18).norm() - at_target).abs() < 1e-9);
+    assert!((ambiguity.get(36, 18).norm() - at_target).abs() < 1e-9);
+}
+
+#[test]
+fn peak_sidelobe_vs_scan() {
+    let frequency = 1e9;
+    let spacing = apg::SPEED_OF_LIGHT / frequency / 2.0;
+    let deg = apg::PI / 180.0;
+    let step = 2.0 * deg;
+    let pattern = apg::PatternGrid::from_fn(step, step, |theta, _| Complex::new(theta.cos().max(0.0).powi(2), 0.0));
+    let planar = apg::PlanarArray::uniform(8, 8, spacing, spacing, |position| {
+        Box::new(apg::DataElement::new(pattern.clone(), position))
+    });
+    let mut array = planar.array().clone();
+    let weights: Vec<Complex<f64>> = array.elements().iter().map(|n| n.weight()).collect();
+
+    let levels = array.peak_sidelobe_vs_scan(frequency, &[0.0, 20.0 * deg, 40.0 * deg, 60.0 * deg]);
+    assert_eq!(levels.len(), 4);
+    // about the -13.3 dB of a uniform taper at broadside
+    assert!((levels[0] + 13.3).abs() < 1.5, "{:?}", levels);
+    for pair in levels.windows(2) {
+        assert!(pair[1] > pair[0], "{:?}", levels);
+    }
+
+    // weights are restored
+    assert!(array.elements().iter().zip(&weights).all(|(n, weight)| n.weight() == *weight));
+}
+
+#[test]
+fn peak_sidelobe_vs_scan_linear() {
+    // a fan beam along the scan plane, which doesn't fit a cone around the beam
+    let frequency = 1e9;
+    let spacing = apg::SPEED_OF_LIGHT / frequency / 2.0;
+    let deg = apg::PI / 180.0;
+    let step = 2.0 * deg;
+    let pattern = apg::PatternGrid::from_fn(step, step, |theta, _| Complex::new(theta.cos().max(0.0).powi(2), 0.0));
+    let mut array = apg::ElementArray::uniform_linear(16, spacing, |position| {
+        Box::new(apg::DataElement::new(pattern.clone(), position))
+    });
+
+    let levels = array.peak_sidelobe_vs_scan(frequency, &[0.0, 20.0 * deg, -40.0 * deg, 60.0 * deg]);
+    assert!((levels[0] + 13.3).abs() < 0.5, "{:?}", levels);
+    assert!(levels[1] > levels[0] && levels[2] > levels[1] && levels[3] > levels[2], "{:?}", levels);
+    assert!(levels[3] < 0.0, "{:?}", levels);
+}
@@ -0,0 +1,43 @@
+use antenna_pattern_generator_lib as apg;
+
+use apg::{ElementIface, GainIface};
+use num::complex::Complex;
+
+mod support;
+use support::omni;
+
+#[test]
+fn subarray_as_element() {
+    let frequency = 1e9;
+    let spacing = apg::SPEED_OF_LIGHT / frequency / 2.0;
+
+    // 2x2 arrangement of 2x2 sub-arrays is the same as a 4x4 grid
+    let flat = apg::PlanarArray::uniform(4, 4, spacing, spacing, omni);
+    let mut subarrays: Vec<Box<dyn apg::ElementIface>> = Vec::new();
+    for row in 0..2 {
+        for col in 0..2 {
+            let position = apg::PointBuilder::default()
+                .x(2.0 * col as f64 * spacing)
+                .y(2.0 * row as f64 * spacing)
+                .build()
+                .unwrap();
+            let tile = apg::PlanarArray::uniform(2, 2, spacing, spacing, omni);
+            subarrays.push(Box::new(apg::SubarrayElement::new(tile.array().clone(), position)));
+        }
+    }
+    let tiled = apg::ElementArray::new(subarrays);
+
+    for (theta, phi) in [(0.0, 0.0), (0.3, 0.2), (1.0, 2.0), (2.5, 4.0)] {
+        let expected = flat.get_gain(frequency, theta, phi).unwrap();
+        assert!((tiled.get_gain(frequency, theta, phi).unwrap() - expected).norm() < 1e-9);
+    }
+
+    // the sub-array weight scales the whole tile
+    let mut tile = apg::SubarrayElement::new(
+        apg::ElementArray::uniform_linear_wl(2, 0.5, frequency, omni),
+        apg::Point::default(),
+    );
+    let before = tile.get_gain(frequency, 0.4, 0.1).unwrap();
+    tile.set_weight(Complex::new(0.0, 2.0));
+    assert!((tile.get_gain(frequency, 0.4, 0.1).unwrap() - before * Complex::new(0.0, 2.0)).norm() < 1e-12);
+}
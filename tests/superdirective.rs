@@ -0,0 +1,83 @@
+use antenna_pattern_generator_lib as apg;
+
+use apg::{ArrayIface, GainIface};
+use num::complex::Complex;
+
+mod support;
+use support::linear_array;
+
+fn set_weights(array: &mut apg::ElementArray, weights: &[Complex<f64>]) {
+    for (n, weight) in array.elements_mut().iter_mut().zip(weights) {
+        n.set_weight(*weight);
+    }
+}
+
+#[test]
+fn half_wavelength_is_conventional() {
+    let frequency = 1e9;
+    let spacing = apg::SPEED_OF_LIGHT / frequency / 2.0;
+    let array = linear_array(8, spacing);
+
+    let weights = array.max_directivity_weights(frequency, 0.0, 0.0).unwrap();
+    assert_eq!(weights.len(), 8);
+    for weight in &weights {
+        assert!((weight - Complex::new(1.0, 0.0)).norm() < 1e-2, "{}", weight);
+    }
+}
+
+#[test]
+fn close_spacing_is_superdirective() {
+    let frequency = 1e9;
+    let spacing = apg::SPEED_OF_LIGHT / frequency / 4.0;
+    let (theta0, phi0) = (apg::PI / 2.0, 0.0);
+    let step = apg::PI / 180.0;
+
+    let mut conventional = linear_array(5, spacing);
+    conventional.steer(frequency, theta0, phi0);
+    let mut optimum = linear_array(5, spacing);
+    let weights = optimum.max_directivity_weights(frequency, theta0, phi0).unwrap();
+    set_weights(&mut optimum, &weights);
+
+    let directivity = |array: &apg::ElementArray| array.peak_to_average(frequency, step, step);
+    assert!(directivity(&optimum) > directivity(&conventional) + 1.0,
+        "{} vs {}", directivity(&optimum), directivity(&conventional));
+
+    // gain in the look direction is real and positive, with the same total weight power
+    let gain = optimum.get_gain(frequency, theta0, phi0).unwrap();
+    assert!(gain.re > 0.0 && gain.im.abs() < 1e-9 * gain.re);
+    let power: f64 = weights.iter().map(|w| w.norm_sqr()).sum();
+    assert!((power - 5.0).abs() < 1e-9);
+
+    // but far less of it for the same weight power: the price of superdirectivity
+    let conventional_gain = conventional.get_gain(frequency, theta0, phi0).unwrap().norm();
+    assert!(gain.norm() < 0.5 * conventional_gain, "{} vs {}", gain.norm(), conventional_gain);
+}
+
+#[test]
+fn sensitivity() {
+    let frequency = 1e9;
+    let spacing = apg::SPEED_OF_LIGHT / frequency / 8.0;
+    let (theta0, phi0) = (apg::PI / 2.0, 0.0);
+
+    let mut uniform = linear_array(5, spacing);
+    uniform.steer(frequency, theta0, phi0);
+    assert!((uniform.excitation_sensitivity(frequency, theta0, phi0) - 0.2).abs() < 1e-9);
+
+    let mut optimum = linear_array(5, spacing);
+    let weights = optimum.max_directivity_weights(frequency, theta0, phi0).unwrap();
+    set_weights(&mut optimum, &weights);
+    let sensitivity = optimum.excitation_sensitivity(frequency, theta0, phi0);
+    // impractically sensitive
+    assert!(sensitivity > 1000.0 * 0.2, "{}", sensitivity);
+}
+
+#[test]
+fn coincident_elements() {
+    let frequency = 1e9;
+    let spacing = apg::SPEED_OF_LIGHT / frequency / 2.0;
+    let mut elements = linear_array(4, spacing).elements().to_vec();
+    elements.insert(2, support::omni(elements[1].position().clone()));
+    let array = apg::ElementArray::new(elements);
+
+    assert_eq!(array.max_directivity_weights(frequency, 0.0, 0.0), Err(apg::GainError::Singular(2)));
+}
@@ -1,3 +1,7 @@
+// not every test uses every helper, and `blosc` is only switched on by hand
+#![allow(dead_code, unexpected_cfgs)]
+
+use antenna_pattern_generator_lib as apg;
 use antenna_pattern_generator_lib::GainIface;
 use antenna_pattern_generator_lib::PI;
 #[cfg(feature = "blosc")]
@@ -43,3 +47,41 @@ pub fn write_to_file(
 
     Ok(())
 }
+
+/// Point in the XY plane
+pub fn point(x: f64, y: f64) -> apg::Point {
+    apg::PointBuilder::default().x(x).y(y).build().unwrap()
+}
+
+/// Angle between two (theta, phi) directions
+pub fn separation(a: (f64, f64), b: (f64, f64)) -> f64 {
+    let cos = a.0.cos() * b.0.cos() + a.0.sin() * b.0.sin() * (a.1 - b.1).cos();
+    cos.clamp(-1.0, 1.0).acos()
+}
+
+/// Omni element with a gain of 1 (0 dBi) at `position`
+pub fn omni(position: apg::Point) -> Box<dyn apg::ElementIface> {
+    Box::new(apg::OmniElementBuilder::default()
+        .position(position)
+        .gain(1.0)
+        .build()
+        .unwrap())
+}
+
+/// `num` omni elements along the x axis, `spacing` (meters) apart
+pub fn linear_array(num: usize, spacing: f64) -> apg::ElementArray {
+    apg::ElementArray::uniform_linear(num, spacing, omni)
+}
+
+/// Highest sidelobe (dB relative to boresight) along a theta cut at `phi`
+///
+/// The cut runs from boresight to the horizon in 0.1 degree steps and the
+/// main lobe ends where the gain first turns back up.
+pub fn peak_sidelobe_db(array: &impl GainIface, frequency: f64, phi: f64) -> f64 {
+    let cut: Vec<f64> = (0..=900)
+        .map(|idx| array.get_gain(frequency, idx as f64 * 0.1 * PI / 180.0, phi).unwrap().norm())
+        .collect();
+    let first_null = (1..cut.len()).find(|idx| cut[*idx] > cut[*idx - 1]).unwrap();
+    let sidelobe = cut[first_null..].iter().cloned().fold(0.0, f64::max);
+    20.0 * (sidelobe / cut[0]).log10()
+}
@@ -0,0 +1,15 @@
+use antenna_pattern_generator_lib as apg;
+
+#[test]
+fn truncation_grows_with_aperture() {
+    let frequency = 10e9;
+    let wavelength = apg::SPEED_OF_LIGHT / frequency;
+
+    // k * r = 2 * PI for a one wavelength radius
+    assert_eq!(apg::swe_truncation_order(wavelength, frequency), 17);
+    assert_eq!(apg::swe_truncation_order(0.0, frequency), 10);
+
+    let small = apg::swe_truncation_order(2.0 * wavelength, frequency);
+    let large = apg::swe_truncation_order(10.0 * wavelength, frequency);
+    assert!(large > small);
+}
@@ -0,0 +1,51 @@
+use antenna_pattern_generator_lib as apg;
+
+mod support;
+use support::peak_sidelobe_db;
+
+fn circular_array(frequency: f64, radius_wavelengths: f64) -> apg::ElementArray {
+    let spacing = apg::SPEED_OF_LIGHT / frequency / 2.0;
+    let radius = radius_wavelengths * apg::SPEED_OF_LIGHT / frequency;
+    let count = (radius / spacing).ceil() as i32;
+
+    let mut elements: Vec<Box<dyn apg::ElementIface>> = Vec::new();
+    for row in -count..=count {
+        for col in -count..=count {
+            let (x, y) = (col as f64 * spacing, row as f64 * spacing);
+            if (x * x + y * y).sqrt() <= radius {
+                elements.push(Box::new(apg::OmniElementBuilder::default()
+                    .position(apg::PointBuilder::default().x(x).y(y).build().unwrap())
+                    .gain(1.0)
+                    .build()
+                    .unwrap()));
+            }
+        }
+    }
+    apg::ElementArray::new( elements )
+}
+
+#[test]
+fn taylor_circular_sidelobes() {
+    let frequency = 1e9;
+    let mut array = circular_array(frequency, 8.0);
+
+    // a uniform circular aperture has -17.6 dB sidelobes
+    let uniform = peak_sidelobe_db(&array, frequency, 0.0);
+    assert!(uniform > -19.0);
+
+    // sampling the aperture with discrete elements costs a little of the target
+    array.apply_taylor_circular(5, -30.0);
+    for phi in [0.0, apg::PI / 2.0] {
+        assert!(peak_sidelobe_db(&array, frequency, phi) < -28.5);
+    }
+}
+
+#[test]
+fn taylor_circular_small_n_bar() {
+    let frequency = 1e9;
+    for n_bar in [0, 1] {
+        let mut array = circular_array(frequency, 2.0);
+        array.apply_taylor_circular(n_bar, -30.0);
+        assert!(array.elements().iter().all(|n| (n.weight().re - 1.0).abs() < 1e-12 && n.weight().im == 0.0));
+    }
+}
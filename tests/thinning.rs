@@ -0,0 +1,50 @@
+use antenna_pattern_generator_lib as apg;
+
+use num::complex::Complex;
+
+fn planar(rows: usize, cols: usize) -> apg::ElementArray {
+    let spacing = apg::SPEED_OF_LIGHT / 1e9 / 2.0;
+    let planar = apg::PlanarArray::uniform(rows, cols, spacing, spacing, |position| {
+        Box::new(apg::OmniElementBuilder::default().position(position).gain(1.0).build().unwrap())
+    });
+    planar.array().clone()
+}
+
+#[test]
+fn half_disabled() {
+    let mut array = planar(4, 4);
+    assert_eq!(array.sparsity(), 0.0);
+    assert_eq!(array.active_aperture_fill(), 1.0);
+
+    // checkerboard keeps the corners, so the aperture is intact
+    for (idx, n) in array.elements_mut().iter_mut().enumerate() {
+        if (idx / 4 + idx % 4) % 2 == 1 {
+            n.set_weight(Complex::new(0.0, 0.0));
+        }
+    }
+    assert_eq!(array.sparsity(), 0.5);
+    assert!((array.active_aperture_fill() - 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn edges_off() {
+    let mut array = planar(4, 4);
+    let spacing = apg::SPEED_OF_LIGHT / 1e9 / 2.0;
+    // keep the inner 2x2
+    for n in array.elements_mut() {
+        let (x, y) = (n.position().x(), n.position().y());
+        let edge = |c: f64| c < 0.5 * spacing || c > 2.5 * spacing;
+        if edge(x) || edge(y) {
+            n.set_weight(Complex::new(0.0, 0.0));
+        }
+    }
+    assert_eq!(array.sparsity(), 0.75);
+    // one spacing of three in each direction
+    assert!((array.active_aperture_fill() - 1.0 / 9.0).abs() < 1e-12);
+
+    for n in array.elements_mut() {
+        n.set_weight(Complex::new(0.0, 0.0));
+    }
+    assert_eq!(array.sparsity(), 1.0);
+    assert_eq!(array.active_aperture_fill(), 0.0);
+}
@@ -0,0 +1,44 @@
+use antenna_pattern_generator_lib as apg;
+
+use apg::GainIface;
+use num::complex::Complex;
+
+fn pattern() -> apg::DataElement {
+    let step = 5.0 * apg::PI / 180.0;
+    let grid = apg::PatternGrid::from_fn(step, step, |theta, _| Complex::new(2.0 * theta.cos().max(0.0), 0.0));
+    apg::DataElement::new(grid, apg::Point::default())
+}
+
+#[test]
+fn realized_gain_includes_mismatch() {
+    let element = apg::DataElement::with_match(pattern(), "tests/data/patch_match.s1p").unwrap();
+    let (theta, phi) = (0.3, 1.0);
+
+    for (frequency, s11_db) in [(2.3e9, -6.0), (2.4e9, -20.0), (2.5e9, -8.0)] {
+        let mismatch = 1.0 - 10_f64.powf(s11_db / 10.0);
+        assert!((element.mismatch_factor(frequency) - mismatch).abs() < 1e-12);
+
+        let gain = element.get_gain(frequency, theta, phi).unwrap().norm_sqr();
+        let realized = element.realized_gain(frequency, theta, phi).unwrap().norm_sqr();
+        assert!((realized - gain * mismatch).abs() < 1e-12 * gain);
+    }
+    assert!((element.s11(2.4e9) - Complex::from_polar(0.1, 10_f64.to_radians())).norm() < 1e-12);
+
+    // without match data the element is matched
+    let matched = pattern();
+    assert_eq!(matched.mismatch_factor(2.4e9), 1.0);
+    assert_eq!(matched.realized_gain(2.4e9, theta, phi), matched.get_gain(2.4e9, theta, phi));
+}
+
+#[test]
+fn option_line() {
+    let ri = apg::DataElement::with_match_str(pattern(), "# Hz S RI R 50\n1e9 0.3 -0.4\n2e9 0.0 0.0\n").unwrap();
+    assert!((ri.s11(1e9) - Complex::new(0.3, -0.4)).norm() < 1e-12);
+    assert!((ri.s11(1.5e9) - Complex::new(0.15, -0.2)).norm() < 1e-12);
+    assert!((ri.mismatch_factor(0.5e9) - 0.75).abs() < 1e-12);
+
+    let z = apg::DataElement::with_match_str(pattern(), "# GHz Z MA R 50\n1 0.5 0\n");
+    assert!(matches!(z, Err(apg::DataFileError::Parse { line: 1, .. })));
+    let columns = apg::DataElement::with_match_str(pattern(), "1 0.5\n");
+    assert!(matches!(columns, Err(apg::DataFileError::Parse { line: 1, .. })));
+}
@@ -0,0 +1,73 @@
+use antenna_pattern_generator_lib as apg;
+
+use num::complex::Complex;
+
+mod support;
+use support::omni;
+
+// a smooth pattern with a linear phase across u-v
+fn known(u: f64, v: f64) -> Complex<f64> {
+    let amplitude = (-(u * u + 2.0 * v * v)).exp();
+    Complex::from_polar(amplitude, 1.5 * u - 0.5 * v)
+}
+
+#[test]
+fn round_trip_inside_visible_circle() {
+    let axis: Vec<f64> = (0..=200).map(|idx| -1.0 + idx as f64 * 0.01).collect();
+    let uv = apg::UvGrid::from_fn(axis.clone(), axis.clone(), known);
+
+    let step = 0.5 * apg::PI / 180.0;
+    let pattern = uv.to_pattern(step, step);
+
+    // theta/phi samples in the front hemisphere follow the u-v pattern
+    let theta = 30_f64.to_radians();
+    let phi = 60_f64.to_radians();
+    let expected = known(theta.sin() * phi.cos(), theta.sin() * phi.sin());
+    assert!((pattern.interpolate(theta, phi) - expected).norm() < 1e-3);
+
+    // the back hemisphere isn't covered by u-v data
+    assert_eq!(pattern.interpolate(apg::PI * 0.75, 0.0), Complex::new(0.0, 0.0));
+
+    let back = apg::UvGrid::from_pattern(&pattern, axis.clone(), axis.clone());
+    for (v_idx, v) in axis.iter().enumerate() {
+        for (u_idx, u) in axis.iter().enumerate() {
+            let value = back.values()[v_idx][u_idx];
+            if u * u + v * v <= 0.9 * 0.9 {
+                assert!((value - known(*u, *v)).norm() < 5e-3, "u = {u}, v = {v}");
+            } else if u * u + v * v > 1.0 {
+                assert_eq!(value, Complex::new(0.0, 0.0));
+            }
+        }
+    }
+}
+
+#[test]
+fn interpolate_outside_rectangle() {
+    let axis = vec![-0.5, 0.0, 0.5];
+    let uv = apg::UvGrid::from_fn(axis.clone(), axis, |u, v| Complex::new(u + v, 0.0));
+    assert!((uv.interpolate(0.25, 0.1).unwrap().re - 0.35).abs() < 1e-12);
+    assert!(uv.interpolate(0.6, 0.0).is_none());
+}
+
+#[test]
+fn grating_lobe_on_visible_boundary() {
+    let frequency = 1e9;
+
+    // one wavelength spacing along x puts grating lobes at u = +/-1, as strong as the main beam
+    let array = apg::ElementArray::uniform_linear_wl(8, 1.0, frequency, omni);
+    let boundary = array.visible_region_gain(frequency, 360);
+    assert_eq!(boundary.len(), 360);
+    let (u, v, gain) = boundary[0];
+    assert!((u - 1.0).abs() < 1e-12 && v.abs() < 1e-12);
+    assert!((gain.norm() - 8.0).abs() < 1e-6, "{}", gain.norm());
+    let (u, _, gain) = boundary[180];
+    assert!((u + 1.0).abs() < 1e-12);
+    assert!((gain.norm() - 8.0).abs() < 1e-6, "{}", gain.norm());
+    assert!(boundary.iter().all(|(u, v, _)| (u * u + v * v - 1.0).abs() < 1e-12));
+
+    // at half a wavelength the horizon along the array is a null
+    let array = apg::ElementArray::uniform_linear_wl(8, 0.5, frequency, omni);
+    let boundary = array.visible_region_gain(frequency, 360);
+    assert!(boundary[0].2.norm() < 1e-6);
+    assert!(boundary[180].2.norm() < 1e-6);
+}
@@ -0,0 +1,38 @@
+use antenna_pattern_generator_lib as apg;
+
+use apg::GainIface;
+use num::complex::Complex;
+
+#[test]
+fn unguarded_patch_sinc() {
+    let frequency = 10e9;
+    let k = 2.0 * apg::PI * frequency / apg::SPEED_OF_LIGHT;
+    let width = 0.012;
+    let step = 10.0 * apg::PI / 180.0;
+
+    // the patch E-plane sinc without the check for zero
+    let grid = apg::PatternGrid::from_fn(step, step, |theta, _| {
+        let inside = k * width * theta.sin() / 2.0;
+        Complex::new(inside.sin() / inside, 0.0)
+    });
+
+    match grid.validate() {
+        Err(apg::PatternError::NonFinite(samples)) => {
+            // every phi row at theta = 0
+            let expected: Vec<(usize, usize)> = (0..grid.num_phi()).map(|phi_idx| (phi_idx, 0)).collect();
+            assert_eq!(samples, expected);
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+    assert!(grid.radiated_power().is_nan());
+
+    // the crate's own patch is guarded
+    let patch = apg::PatchElementBuilder::default()
+        .position(apg::Point::default())
+        .length(0.015)
+        .width(width)
+        .build()
+        .unwrap();
+    let array = apg::ElementArray::new(vec![Box::new(patch)]);
+    assert_eq!(array.sample_sphere(frequency, step, step).validate(), Ok(()));
+}
@@ -0,0 +1,55 @@
+#![cfg(feature = "io")]
+
+use antenna_pattern_generator_lib as apg;
+
+use apg::io::GainScale;
+use apg::GainIface;
+
+/// Vertices in the POINTS section of a legacy VTK file
+fn read_points(path: &std::path::Path) -> Vec<[f64; 3]> {
+    let contents = std::fs::read_to_string(path).unwrap();
+    let mut lines = contents.lines().skip_while(|line| !line.starts_with("POINTS"));
+    let count: usize = lines.next().unwrap().split_whitespace().nth(1).unwrap().parse().unwrap();
+    lines
+        .take(count)
+        .map(|line| {
+            let xyz: Vec<f64> = line.split_whitespace().map(|token| token.parse().unwrap()).collect();
+            [xyz[0], xyz[1], xyz[2]]
+        })
+        .collect()
+}
+
+#[test]
+fn radius_follows_gain() {
+    let frequency = 1e9;
+    let spacing = apg::SPEED_OF_LIGHT / frequency / 2.0;
+    let array = apg::ElementArray::uniform_linear(4, spacing, |position| {
+        Box::new(apg::OmniElementBuilder::default().position(position).gain(1.0).build().unwrap())
+    });
+    let step = 10.0 * apg::PI / 180.0;
+    let grid = array.sample_sphere(frequency, step, step);
+
+    std::fs::create_dir_all("tests/output").unwrap();
+    let path = std::path::Path::new("tests/output/four_element.vtk");
+
+    apg::io::write_vtk(&grid, path, GainScale::Linear).unwrap();
+    let points = read_points(path);
+    assert_eq!(points.len(), grid.num_theta() * grid.num_phi());
+    let samples = grid.values().iter().flatten();
+    for (point, value) in points.iter().zip(samples) {
+        let radius = (point[0].powi(2) + point[1].powi(2) + point[2].powi(2)).sqrt();
+        assert!((radius - value.norm()).abs() < 1e-9);
+    }
+    // broadside peak straight up the z axis
+    let top = points[0];
+    assert!((top[2] - 4.0).abs() < 1e-9);
+
+    apg::io::write_vtk(&grid, path, GainScale::Db { floor_db: -40.0 }).unwrap();
+    let points = read_points(path);
+    let peak = grid.values().iter().flatten().map(|value| value.norm()).fold(0.0, f64::max);
+    for (point, value) in points.iter().zip(grid.values().iter().flatten()) {
+        let radius = (point[0].powi(2) + point[1].powi(2) + point[2].powi(2)).sqrt();
+        let expected = (20.0 * (value.norm() / peak).log10() + 40.0).max(0.0);
+        assert!((radius - expected).abs() < 1e-6);
+    }
+}
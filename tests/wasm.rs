@@ -0,0 +1,26 @@
+#![cfg(feature = "wasm")]
+
+use antenna_pattern_generator_lib as apg;
+
+use apg::wasm::WasmArray;
+use apg::GainIface;
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::wasm_bindgen_test;
+
+// runs natively with `cargo test` and in the browser with wasm-bindgen-test-runner
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn two_element_gain() {
+    let frequency = 1e9;
+    let spacing = apg::SPEED_OF_LIGHT / frequency / 2.0;
+    let reference = apg::ElementArray::uniform_linear(2, spacing, |position| {
+        Box::new(apg::OmniElementBuilder::default().position(position).gain(1.0).build().unwrap())
+    });
+
+    let array = WasmArray::uniform_linear(2, spacing);
+    assert_eq!(array.len(), 2);
+
+    let gain = array.gain(frequency, 0.7, 0.2).unwrap();
+    let expected = reference.get_gain(frequency, 0.7, 0.2).unwrap();
+    assert_eq!(gain, vec![expected.re, expected.im]);
+}